@@ -0,0 +1,41 @@
+-- Domestic/international flag used to pick tax rules
+ALTER TABLE packages ADD COLUMN is_international BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Create tax rules table
+CREATE TABLE tax_rules (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(100) NOT NULL,
+    category_id UUID REFERENCES categories(id) ON DELETE CASCADE,
+    region VARCHAR(20) NOT NULL DEFAULT 'all',
+    rate_basis_points INTEGER NOT NULL,
+    mode VARCHAR(20) NOT NULL DEFAULT 'exclusive',
+    is_active BOOLEAN DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CONSTRAINT tax_rules_region_check CHECK (region IN ('all', 'domestic', 'international')),
+    CONSTRAINT tax_rules_mode_check CHECK (mode IN ('inclusive', 'exclusive')),
+    CONSTRAINT tax_rules_rate_check CHECK (rate_basis_points >= 0 AND rate_basis_points <= 10000)
+);
+
+-- Split booking totals into base and tax
+ALTER TABLE bookings ADD COLUMN base_amount INTEGER;
+ALTER TABLE bookings ADD COLUMN tax_amount INTEGER NOT NULL DEFAULT 0;
+UPDATE bookings SET base_amount = total_amount;
+ALTER TABLE bookings ALTER COLUMN base_amount SET NOT NULL;
+
+-- Create booking tax lines table
+CREATE TABLE booking_tax_lines (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    tax_rule_id UUID REFERENCES tax_rules(id) ON DELETE SET NULL,
+    name VARCHAR(100) NOT NULL,
+    rate_basis_points INTEGER NOT NULL,
+    mode VARCHAR(20) NOT NULL,
+    amount INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX idx_tax_rules_category ON tax_rules(category_id);
+CREATE INDEX idx_booking_tax_lines_booking ON booking_tax_lines(booking_id);
+
+CREATE TRIGGER update_tax_rules_updated_at BEFORE UPDATE ON tax_rules FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
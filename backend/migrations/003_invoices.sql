@@ -0,0 +1,22 @@
+-- Traveller names printed on vouchers
+ALTER TABLE bookings ADD COLUMN traveller_names TEXT[] DEFAULT '{}';
+
+-- Create invoice sequences table (one counter per financial year)
+CREATE TABLE invoice_sequences (
+    financial_year VARCHAR(7) PRIMARY KEY,
+    last_number INTEGER NOT NULL DEFAULT 0
+);
+
+-- Create invoices table
+CREATE TABLE invoices (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    booking_id UUID UNIQUE NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    invoice_number VARCHAR(30) UNIQUE NOT NULL,
+    financial_year VARCHAR(7) NOT NULL,
+    sequence_number INTEGER NOT NULL,
+    base_amount INTEGER NOT NULL,
+    tax_amount INTEGER NOT NULL,
+    total_amount INTEGER NOT NULL,
+    issued_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (financial_year, sequence_number)
+);
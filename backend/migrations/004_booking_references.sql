@@ -0,0 +1,24 @@
+-- Human-friendly booking references, e.g. WMT-7K3Q9P
+ALTER TABLE bookings ADD COLUMN reference VARCHAR(12);
+
+-- Backfill existing bookings using the same unambiguous alphabet as the application
+DO $$
+DECLARE
+    alphabet CONSTANT TEXT := '23456789ABCDEFGHJKMNPQRSTUVWXYZ';
+    booking RECORD;
+    candidate TEXT;
+BEGIN
+    FOR booking IN SELECT id FROM bookings WHERE reference IS NULL LOOP
+        LOOP
+            candidate := 'WMT-';
+            FOR i IN 1..6 LOOP
+                candidate := candidate || substr(alphabet, 1 + floor(random() * length(alphabet))::INTEGER, 1);
+            END LOOP;
+            EXIT WHEN NOT EXISTS (SELECT 1 FROM bookings WHERE reference = candidate);
+        END LOOP;
+        UPDATE bookings SET reference = candidate WHERE id = booking.id;
+    END LOOP;
+END $$;
+
+ALTER TABLE bookings ALTER COLUMN reference SET NOT NULL;
+CREATE UNIQUE INDEX idx_bookings_reference ON bookings(reference);
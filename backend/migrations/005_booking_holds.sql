@@ -0,0 +1,6 @@
+-- Time-limited seat holds for unpaid bookings. Bookings created before this
+-- migration keep a NULL hold and are never expired automatically.
+ALTER TABLE bookings ADD COLUMN hold_expires_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_bookings_hold_expires ON bookings(hold_expires_at) WHERE status = 'pending';
+CREATE INDEX idx_bookings_departure ON bookings(package_id, booking_date);
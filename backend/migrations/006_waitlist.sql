@@ -0,0 +1,33 @@
+-- Create notifications table (outbox consumed by the mail/SMS sender)
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    kind VARCHAR(50) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'queued',
+    sent_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- Create waitlist entries table (FIFO per package departure)
+CREATE TABLE waitlist_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    booking_date DATE NOT NULL,
+    party_size INTEGER NOT NULL CHECK (party_size > 0),
+    status VARCHAR(20) NOT NULL DEFAULT 'waiting',
+    offer_booking_id UUID REFERENCES bookings(id) ON DELETE SET NULL,
+    offer_expires_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX idx_notifications_status ON notifications(status) WHERE status = 'queued';
+CREATE INDEX idx_waitlist_departure ON waitlist_entries(package_id, booking_date, created_at) WHERE status = 'waiting';
+CREATE INDEX idx_waitlist_offer_booking ON waitlist_entries(offer_booking_id);
+CREATE UNIQUE INDEX idx_waitlist_active_user ON waitlist_entries(package_id, booking_date, user_id) WHERE status IN ('waiting', 'offered');
+
+CREATE TRIGGER update_waitlist_entries_updated_at BEFORE UPDATE ON waitlist_entries FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,52 @@
+-- Create payment schedule templates table (per package deposit/balance split)
+CREATE TABLE payment_schedule_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    label VARCHAR(100) NOT NULL,
+    percent_basis_points INTEGER NOT NULL CHECK (percent_basis_points > 0 AND percent_basis_points <= 10000),
+    due_days_before_departure INTEGER CHECK (due_days_before_departure >= 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (package_id, position)
+);
+
+-- Create booking installments table
+CREATE TABLE booking_installments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    label VARCHAR(100) NOT NULL,
+    amount INTEGER NOT NULL,
+    due_date DATE NOT NULL,
+    paid_amount INTEGER NOT NULL DEFAULT 0,
+    reminder_sent_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (booking_id, position)
+);
+
+-- Create booking payments table
+CREATE TABLE booking_payments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL CHECK (amount > 0),
+    method VARCHAR(50) NOT NULL,
+    transaction_reference VARCHAR(100),
+    recorded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+ALTER TABLE bookings ADD COLUMN amount_paid INTEGER NOT NULL DEFAULT 0;
+
+-- Existing bookings get a single installment due by departure; confirmed
+-- bookings were settled outside the system and are treated as paid
+UPDATE bookings SET amount_paid = total_amount WHERE status = 'confirmed';
+
+INSERT INTO booking_installments (booking_id, position, label, amount, due_date, paid_amount)
+SELECT id, 1, 'Full payment', total_amount, booking_date, amount_paid FROM bookings;
+
+CREATE INDEX idx_payment_schedule_templates_package ON payment_schedule_templates(package_id);
+CREATE INDEX idx_booking_installments_due ON booking_installments(due_date);
+CREATE INDEX idx_booking_payments_booking ON booking_payments(booking_id);
+
+CREATE TRIGGER update_booking_installments_updated_at BEFORE UPDATE ON booking_installments FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
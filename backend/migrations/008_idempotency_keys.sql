@@ -0,0 +1,14 @@
+-- Create idempotency keys table (stored responses for retried POST requests)
+CREATE TABLE idempotency_keys (
+    key VARCHAR(255) NOT NULL,
+    scope VARCHAR(64) NOT NULL,
+    fingerprint VARCHAR(64) NOT NULL,
+    status_code SMALLINT,
+    content_type VARCHAR(255),
+    response_body BYTEA,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (key, scope)
+);
+
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
@@ -0,0 +1,17 @@
+-- Create booking messages table (customer/staff thread per booking)
+CREATE TABLE booking_messages (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    author_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    author_role VARCHAR(20) NOT NULL CHECK (author_role IN ('customer', 'staff')),
+    body TEXT NOT NULL,
+    attachments JSONB NOT NULL DEFAULT '[]',
+    is_internal BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- Track when each side last read the thread, for unread counters
+ALTER TABLE bookings ADD COLUMN customer_last_read_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE bookings ADD COLUMN staff_last_read_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_booking_messages_booking_id ON booking_messages(booking_id, created_at);
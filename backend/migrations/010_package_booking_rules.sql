@@ -0,0 +1,12 @@
+-- Create package booking rules table (which departure dates a package accepts)
+CREATE TABLE package_booking_rules (
+    package_id UUID PRIMARY KEY REFERENCES packages(id) ON DELETE CASCADE,
+    min_lead_days INTEGER NOT NULL DEFAULT 0 CHECK (min_lead_days >= 0),
+    max_advance_days INTEGER CHECK (max_advance_days > 0),
+    departure_weekdays INTEGER[] NOT NULL DEFAULT '{}',
+    fixed_departure_dates DATE[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TRIGGER update_package_booking_rules_updated_at BEFORE UPDATE ON package_booking_rules FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
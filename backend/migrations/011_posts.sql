@@ -0,0 +1,23 @@
+-- Create posts table (blog/CMS)
+CREATE TABLE posts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    slug VARCHAR(255) NOT NULL UNIQUE,
+    title VARCHAR(255) NOT NULL,
+    excerpt TEXT,
+    body_markdown TEXT NOT NULL,
+    body_html TEXT NOT NULL,
+    cover_image_url VARCHAR(500),
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    author_id UUID NOT NULL REFERENCES users(id),
+    status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'published', 'scheduled')),
+    published_at TIMESTAMP WITH TIME ZONE,
+    meta_title VARCHAR(255),
+    meta_description VARCHAR(500),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX idx_posts_published_at ON posts(published_at DESC) WHERE status IN ('published', 'scheduled');
+CREATE INDEX idx_posts_tags ON posts USING GIN(tags);
+
+CREATE TRIGGER update_posts_updated_at BEFORE UPDATE ON posts FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,23 @@
+-- Create enquiries table (contact form leads)
+CREATE TABLE enquiries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(200) NOT NULL,
+    email VARCHAR(255) NOT NULL,
+    phone VARCHAR(20),
+    subject VARCHAR(255),
+    message TEXT NOT NULL,
+    package_id UUID REFERENCES packages(id) ON DELETE SET NULL,
+    travel_date DATE,
+    number_of_people INTEGER CHECK (number_of_people > 0),
+    status VARCHAR(20) NOT NULL DEFAULT 'new' CHECK (status IN ('new', 'contacted', 'quoted', 'won', 'lost')),
+    assigned_to UUID REFERENCES users(id) ON DELETE SET NULL,
+    booking_id UUID REFERENCES bookings(id) ON DELETE SET NULL,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX idx_enquiries_status ON enquiries(status, created_at);
+CREATE INDEX idx_enquiries_ip_address ON enquiries(ip_address, created_at);
+
+CREATE TRIGGER update_enquiries_updated_at BEFORE UPDATE ON enquiries FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,69 @@
+-- Create RFQs table (MICE / group requests for quotation)
+CREATE TABLE rfqs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    company_name VARCHAR(255) NOT NULL,
+    contact_name VARCHAR(200) NOT NULL,
+    contact_email VARCHAR(255) NOT NULL,
+    contact_phone VARCHAR(20),
+    city VARCHAR(100) NOT NULL,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    headcount INTEGER NOT NULL CHECK (headcount > 0),
+    budget_amount INTEGER CHECK (budget_amount > 0),
+    rooms_required INTEGER CHECK (rooms_required >= 0),
+    venue_requirements TEXT,
+    notes TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'submitted' CHECK (status IN ('submitted', 'quoted', 'accepted', 'declined', 'cancelled')),
+    booking_id UUID REFERENCES bookings(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (end_date >= start_date)
+);
+
+-- Create RFQ quotes table (one row per quote version)
+CREATE TABLE rfq_quotes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    rfq_id UUID NOT NULL REFERENCES rfqs(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'sent', 'superseded', 'accepted')),
+    cost_total INTEGER NOT NULL,
+    margin_total INTEGER NOT NULL,
+    total_amount INTEGER NOT NULL,
+    deposit_basis_points INTEGER NOT NULL DEFAULT 0 CHECK (deposit_basis_points >= 0 AND deposit_basis_points <= 10000),
+    balance_due_days_before INTEGER NOT NULL DEFAULT 0 CHECK (balance_due_days_before >= 0),
+    valid_until DATE,
+    notes TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    sent_at TIMESTAMP WITH TIME ZONE,
+    accepted_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (rfq_id, version)
+);
+
+-- Create RFQ quote line items table
+CREATE TABLE rfq_quote_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    quote_id UUID NOT NULL REFERENCES rfq_quotes(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    category VARCHAR(50) NOT NULL,
+    description VARCHAR(500) NOT NULL,
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    unit_cost INTEGER NOT NULL CHECK (unit_cost >= 0),
+    margin_basis_points INTEGER NOT NULL DEFAULT 0 CHECK (margin_basis_points >= 0),
+    cost_amount INTEGER NOT NULL,
+    sell_amount INTEGER NOT NULL,
+    UNIQUE (quote_id, position)
+);
+
+-- Bookings made from an accepted quote have no package
+ALTER TABLE bookings ADD COLUMN rfq_id UUID REFERENCES rfqs(id);
+ALTER TABLE bookings ADD CONSTRAINT bookings_package_or_rfq CHECK (package_id IS NOT NULL OR rfq_id IS NOT NULL);
+
+CREATE INDEX idx_rfqs_user ON rfqs(user_id);
+CREATE INDEX idx_rfqs_status ON rfqs(status, created_at);
+CREATE INDEX idx_bookings_rfq ON bookings(rfq_id);
+
+CREATE TRIGGER update_rfqs_updated_at BEFORE UPDATE ON rfqs FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
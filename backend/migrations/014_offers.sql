@@ -0,0 +1,32 @@
+-- Create offers table (time-boxed promotions)
+CREATE TABLE offers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    badge_label VARCHAR(50),
+    banner_image_url TEXT,
+    discount_type VARCHAR(20) NOT NULL CHECK (discount_type IN ('percentage', 'fixed_price')),
+    discount_value INTEGER NOT NULL CHECK (discount_value > 0),
+    starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (ends_at > starts_at),
+    CHECK (discount_type <> 'percentage' OR discount_value < 10000)
+);
+
+-- Create offer packages table (packages each offer applies to)
+CREATE TABLE offer_packages (
+    offer_id UUID NOT NULL REFERENCES offers(id) ON DELETE CASCADE,
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    PRIMARY KEY (offer_id, package_id)
+);
+
+-- Offer whose price a booking was made at
+ALTER TABLE bookings ADD COLUMN offer_id UUID REFERENCES offers(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_offers_live ON offers(starts_at, ends_at) WHERE is_active = TRUE;
+CREATE INDEX idx_offer_packages_package ON offer_packages(package_id);
+
+CREATE TRIGGER update_offers_updated_at BEFORE UPDATE ON offers FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
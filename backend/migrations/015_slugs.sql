@@ -0,0 +1,72 @@
+-- Add URL slugs to packages and categories
+ALTER TABLE packages ADD COLUMN slug VARCHAR(255);
+ALTER TABLE categories ADD COLUMN slug VARCHAR(255);
+
+-- Backfill from titles; characters outside a-z and 0-9 are dropped here, the
+-- API transliterates them for new rows
+UPDATE packages SET slug = TRIM(BOTH '-' FROM REGEXP_REPLACE(LOWER(title), '[^a-z0-9]+', '-', 'g'));
+UPDATE packages SET slug = 'package' WHERE slug = '';
+
+UPDATE categories SET slug = TRIM(BOTH '-' FROM REGEXP_REPLACE(LOWER(name), '[^a-z0-9]+', '-', 'g'));
+UPDATE categories SET slug = 'category' WHERE slug = '';
+
+-- The oldest row keeps a shared slug; later ones get the first free -2, -3, ...
+-- suffix, checked against every slug so "Kerala" twice and "Kerala 2" cannot collide
+DO $$
+DECLARE
+    table_name TEXT;
+    duplicate RECORD;
+    candidate TEXT;
+    n INTEGER;
+    taken BOOLEAN;
+BEGIN
+    FOREACH table_name IN ARRAY ARRAY['packages', 'categories'] LOOP
+        FOR duplicate IN EXECUTE format(
+            'SELECT id, slug FROM (
+                 SELECT id, slug, ROW_NUMBER() OVER (PARTITION BY slug ORDER BY created_at, id) AS position FROM %I
+             ) ranked
+             WHERE position > 1
+             ORDER BY slug, position',
+            table_name
+        ) LOOP
+            n := 1;
+            LOOP
+                n := n + 1;
+                candidate := LEFT(duplicate.slug, 254 - LENGTH(n::TEXT)) || '-' || n;
+                EXECUTE format('SELECT EXISTS (SELECT 1 FROM %I WHERE slug = $1)', table_name) INTO taken USING candidate;
+                EXIT WHEN NOT taken;
+            END LOOP;
+            EXECUTE format('UPDATE %I SET slug = $1 WHERE id = $2', table_name) USING candidate, duplicate.id;
+        END LOOP;
+    END LOOP;
+END $$;
+
+ALTER TABLE packages ALTER COLUMN slug SET NOT NULL;
+ALTER TABLE packages ADD CONSTRAINT packages_slug_key UNIQUE (slug);
+ALTER TABLE categories ALTER COLUMN slug SET NOT NULL;
+ALTER TABLE categories ADD CONSTRAINT categories_slug_key UNIQUE (slug);
+
+-- Create slug redirects table (old slugs that still resolve)
+CREATE TABLE slug_redirects (
+    entity_type VARCHAR(20) NOT NULL CHECK (entity_type IN ('package', 'category', 'post')),
+    old_slug VARCHAR(255) NOT NULL,
+    target_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (entity_type, old_slug)
+);
+
+-- Create slug redirect trigger function; the entity type is the trigger argument
+CREATE OR REPLACE FUNCTION record_slug_redirect()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM slug_redirects WHERE entity_type = TG_ARGV[0] AND old_slug = NEW.slug;
+    INSERT INTO slug_redirects (entity_type, old_slug, target_id, created_at)
+    VALUES (TG_ARGV[0], OLD.slug, NEW.id, NOW())
+    ON CONFLICT (entity_type, old_slug) DO UPDATE SET target_id = EXCLUDED.target_id, created_at = EXCLUDED.created_at;
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER record_packages_slug_redirect AFTER UPDATE OF slug ON packages FOR EACH ROW WHEN (OLD.slug IS DISTINCT FROM NEW.slug) EXECUTE FUNCTION record_slug_redirect('package');
+CREATE TRIGGER record_categories_slug_redirect AFTER UPDATE OF slug ON categories FOR EACH ROW WHEN (OLD.slug IS DISTINCT FROM NEW.slug) EXECUTE FUNCTION record_slug_redirect('category');
+CREATE TRIGGER record_posts_slug_redirect AFTER UPDATE OF slug ON posts FOR EACH ROW WHEN (OLD.slug IS DISTINCT FROM NEW.slug) EXECUTE FUNCTION record_slug_redirect('post');
@@ -0,0 +1,29 @@
+-- Create package translations table (per-locale text; NULL fields fall back to the default language)
+CREATE TABLE package_translations (
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    locale VARCHAR(10) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    highlights TEXT[],
+    itinerary JSONB,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (package_id, locale)
+);
+
+-- Create category translations table
+CREATE TABLE category_translations (
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    locale VARCHAR(10) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (category_id, locale)
+);
+
+CREATE INDEX idx_package_translations_locale ON package_translations(locale);
+CREATE INDEX idx_category_translations_locale ON category_translations(locale);
+
+CREATE TRIGGER update_package_translations_updated_at BEFORE UPDATE ON package_translations FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+CREATE TRIGGER update_category_translations_updated_at BEFORE UPDATE ON category_translations FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
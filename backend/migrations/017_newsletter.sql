@@ -0,0 +1,23 @@
+-- Create newsletter subscribers table (double opt-in mailing list with segment tags)
+CREATE TABLE newsletter_subscribers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    email VARCHAR(255) NOT NULL,
+    first_name VARCHAR(100),
+    user_id UUID UNIQUE REFERENCES users(id) ON DELETE SET NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'subscribed', 'unsubscribed')),
+    interests TEXT[] NOT NULL DEFAULT '{}',
+    destinations TEXT[] NOT NULL DEFAULT '{}',
+    travel_scope VARCHAR(20) NOT NULL DEFAULT 'any' CHECK (travel_scope IN ('any', 'domestic', 'international')),
+    source VARCHAR(50),
+    confirmed_at TIMESTAMP WITH TIME ZONE,
+    unsubscribed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_newsletter_subscribers_email ON newsletter_subscribers(LOWER(email));
+CREATE INDEX idx_newsletter_subscribers_status ON newsletter_subscribers(status, created_at);
+CREATE INDEX idx_newsletter_subscribers_interests ON newsletter_subscribers USING GIN (interests);
+CREATE INDEX idx_newsletter_subscribers_destinations ON newsletter_subscribers USING GIN (destinations);
+
+CREATE TRIGGER update_newsletter_subscribers_updated_at BEFORE UPDATE ON newsletter_subscribers FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,43 @@
+-- Create content templates table (FAQ entries and policy sections shared across packages)
+CREATE TABLE content_templates (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('faq', 'policy')),
+    section_type VARCHAR(30) CHECK (section_type IN ('visa', 'terms', 'things_to_carry', 'cancellation', 'health_safety', 'other')),
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK ((kind = 'policy') = (section_type IS NOT NULL))
+);
+
+-- Create package FAQs table (ordered; text comes from the template when one is linked)
+CREATE TABLE package_faqs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    template_id UUID REFERENCES content_templates(id) ON DELETE RESTRICT,
+    question TEXT,
+    answer TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (package_id, position),
+    CHECK (template_id IS NOT NULL OR (question IS NOT NULL AND answer IS NOT NULL))
+);
+
+-- Create package policies table (typed sections such as visa notes, terms and things to carry)
+CREATE TABLE package_policies (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    template_id UUID REFERENCES content_templates(id) ON DELETE RESTRICT,
+    section_type VARCHAR(30) CHECK (section_type IN ('visa', 'terms', 'things_to_carry', 'cancellation', 'health_safety', 'other')),
+    title TEXT,
+    body TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (package_id, position),
+    CHECK (template_id IS NOT NULL OR (section_type IS NOT NULL AND title IS NOT NULL AND body IS NOT NULL))
+);
+
+CREATE INDEX idx_package_faqs_template ON package_faqs(template_id) WHERE template_id IS NOT NULL;
+CREATE INDEX idx_package_policies_template ON package_policies(template_id) WHERE template_id IS NOT NULL;
+
+CREATE TRIGGER update_content_templates_updated_at BEFORE UPDATE ON content_templates FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
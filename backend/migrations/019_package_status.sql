@@ -0,0 +1,11 @@
+-- Package publishing workflow; status and the publish window replace is_active
+ALTER TABLE packages ADD COLUMN status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'in_review', 'scheduled', 'published', 'archived'));
+ALTER TABLE packages ADD COLUMN publish_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE packages ADD COLUMN unpublish_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE packages ADD CONSTRAINT packages_scheduled_publish_at CHECK (status <> 'scheduled' OR publish_at IS NOT NULL);
+ALTER TABLE packages ADD CONSTRAINT packages_publish_window CHECK (publish_at IS NULL OR unpublish_at IS NULL OR unpublish_at > publish_at);
+
+UPDATE packages SET status = CASE WHEN is_active THEN 'published' ELSE 'archived' END;
+ALTER TABLE packages DROP COLUMN is_active;
+
+CREATE INDEX idx_packages_status ON packages(status, publish_at);
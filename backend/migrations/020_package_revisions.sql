@@ -0,0 +1,10 @@
+-- Create package revisions table (the package as it was before each edit)
+CREATE TABLE package_revisions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    revision_number INTEGER NOT NULL,
+    snapshot JSONB NOT NULL,
+    edited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (package_id, revision_number)
+);
@@ -0,0 +1,18 @@
+-- Add row versions for optimistic concurrency on admin edits
+ALTER TABLE packages ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE categories ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE bookings ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+-- Create version trigger function
+CREATE OR REPLACE FUNCTION increment_version_column()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.version = OLD.version + 1;
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+-- Create triggers for version
+CREATE TRIGGER increment_packages_version BEFORE UPDATE ON packages FOR EACH ROW EXECUTE FUNCTION increment_version_column();
+CREATE TRIGGER increment_categories_version BEFORE UPDATE ON categories FOR EACH ROW EXECUTE FUNCTION increment_version_column();
+CREATE TRIGGER increment_bookings_version BEFORE UPDATE ON bookings FOR EACH ROW EXECUTE FUNCTION increment_version_column();
@@ -0,0 +1,3 @@
+-- Add external IDs so packages can be matched to rows in content spreadsheets
+ALTER TABLE packages ADD COLUMN external_id VARCHAR(100);
+ALTER TABLE packages ADD CONSTRAINT packages_external_id_key UNIQUE (external_id);
@@ -0,0 +1,4 @@
+-- Link cloned packages back to the package they were copied from
+ALTER TABLE packages ADD COLUMN cloned_from UUID REFERENCES packages(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_packages_cloned_from ON packages(cloned_from) WHERE cloned_from IS NOT NULL;
@@ -0,0 +1,24 @@
+-- Create package variants table (tiers such as standard, deluxe and luxury sold on the same itinerary)
+CREATE TABLE package_variants (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    package_id UUID NOT NULL REFERENCES packages(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,
+    price INTEGER NOT NULL CHECK (price > 0),
+    added_inclusions TEXT[] NOT NULL DEFAULT '{}',
+    removed_inclusions TEXT[] NOT NULL DEFAULT '{}',
+    max_people INTEGER NOT NULL CHECK (max_people > 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (package_id, name)
+);
+
+CREATE INDEX idx_package_variants_package ON package_variants(package_id, position);
+
+-- Bookings keep the variant they were sold at; variants with bookings can only be deactivated
+ALTER TABLE bookings ADD COLUMN variant_id UUID REFERENCES package_variants(id) ON DELETE RESTRICT;
+
+CREATE INDEX idx_bookings_variant_date ON bookings(variant_id, booking_date) WHERE variant_id IS NOT NULL;
+
+CREATE TRIGGER update_package_variants_updated_at BEFORE UPDATE ON package_variants FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,6 @@
+-- Stop deletes of packages and users from cascading into bookings; archive them instead
+ALTER TABLE bookings DROP CONSTRAINT bookings_package_id_fkey;
+ALTER TABLE bookings ADD CONSTRAINT bookings_package_id_fkey FOREIGN KEY (package_id) REFERENCES packages(id) ON DELETE RESTRICT;
+
+ALTER TABLE bookings DROP CONSTRAINT bookings_user_id_fkey;
+ALTER TABLE bookings ADD CONSTRAINT bookings_user_id_fkey FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE RESTRICT;
@@ -0,0 +1,7 @@
+-- Track delivery attempts so the notification dispatcher can retry failed sends
+ALTER TABLE notifications ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE notifications ADD COLUMN last_error TEXT;
+ALTER TABLE notifications ADD COLUMN next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW();
+
+DROP INDEX idx_notifications_status;
+CREATE INDEX idx_notifications_queued ON notifications(next_attempt_at, created_at) WHERE status = 'queued';
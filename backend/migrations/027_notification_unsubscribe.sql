@@ -0,0 +1,2 @@
+-- Unsubscribe link sent as a List-Unsubscribe header on mailing list notifications
+ALTER TABLE notifications ADD COLUMN list_unsubscribe_url TEXT;
@@ -0,0 +1,2 @@
+-- Variant a waitlisted customer is waiting for; offers are made at that variant
+ALTER TABLE waitlist_entries ADD COLUMN variant_id UUID REFERENCES package_variants(id) ON DELETE CASCADE;
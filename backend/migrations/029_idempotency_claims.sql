@@ -0,0 +1,2 @@
+-- When a key was last claimed; an unfinished claim older than the lease can be taken over
+ALTER TABLE idempotency_keys ADD COLUMN claimed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW();
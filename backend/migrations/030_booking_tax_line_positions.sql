@@ -0,0 +1,13 @@
+-- Order of a booking's tax lines as computed: inclusive lines first, then exclusive
+ALTER TABLE booking_tax_lines ADD COLUMN position INTEGER;
+
+UPDATE booking_tax_lines t
+SET position = ordered.position
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY booking_id ORDER BY mode = 'exclusive', created_at, id) as position
+    FROM booking_tax_lines
+) ordered
+WHERE t.id = ordered.id;
+
+ALTER TABLE booking_tax_lines ALTER COLUMN position SET NOT NULL;
+ALTER TABLE booking_tax_lines ADD CONSTRAINT booking_tax_lines_position_key UNIQUE (booking_id, position);
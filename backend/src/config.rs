@@ -1,4 +1,5 @@
 use std::env;
+use std::net::IpAddr;
 
 #[allow(dead_code)]
 #[derive(Clone)]
@@ -6,11 +7,45 @@ pub struct Config {
     pub database_url: String,
     pub jwt_secret: String,
     pub server_port: u16,
+    pub booking_hold_minutes: i64,
+    pub hold_sweep_interval_secs: u64,
+    pub waitlist_offer_hours: i64,
+    pub payment_reminder_days: i64,
+    pub payment_grace_days: i64,
+    pub payment_sweep_interval_secs: u64,
+    pub idempotency_key_ttl_hours: i64,
+    pub idempotency_lease_secs: i64,
+    pub enquiry_rate_limit_per_hour: i64,
+    pub enquiry_min_submit_secs: i64,
+    pub trusted_proxies: Vec<IpAddr>,
+    pub site_url: String,
+    pub default_locale: String,
+    pub supported_locales: Vec<String>,
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub smtp_starttls: bool,
+    pub mail_from: String,
+    pub notification_sweep_interval_secs: u64,
 }
 
-#[allow(dead_code)]
 impl Config {
     pub fn from_env() -> Self {
+        let default_locale = env::var("DEFAULT_LOCALE")
+            .unwrap_or_else(|_| "en".to_string())
+            .trim()
+            .to_lowercase();
+        let mut supported_locales: Vec<String> = env::var("SUPPORTED_LOCALES")
+            .unwrap_or_else(|_| "en,hi,ar".to_string())
+            .split(',')
+            .map(|locale| locale.trim().to_lowercase())
+            .filter(|locale| !locale.is_empty())
+            .collect();
+        if !supported_locales.contains(&default_locale) {
+            supported_locales.push(default_locale.clone());
+        }
+
         Self {
             database_url: env::var("DATABASE_URL")
                 .expect("DATABASE_URL must be set"),
@@ -20,6 +55,76 @@ impl Config {
                 .unwrap_or_else(|_| "8080".to_string())
                 .parse()
                 .expect("PORT must be a valid number"),
+            booking_hold_minutes: env::var("BOOKING_HOLD_MINUTES")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("BOOKING_HOLD_MINUTES must be a valid number"),
+            hold_sweep_interval_secs: sweep_interval_secs("HOLD_SWEEP_INTERVAL_SECS", "60"),
+            waitlist_offer_hours: env::var("WAITLIST_OFFER_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .expect("WAITLIST_OFFER_HOURS must be a valid number"),
+            payment_reminder_days: env::var("PAYMENT_REMINDER_DAYS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .expect("PAYMENT_REMINDER_DAYS must be a valid number"),
+            payment_grace_days: env::var("PAYMENT_GRACE_DAYS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .expect("PAYMENT_GRACE_DAYS must be a valid number"),
+            payment_sweep_interval_secs: sweep_interval_secs("PAYMENT_SWEEP_INTERVAL_SECS", "3600"),
+            idempotency_key_ttl_hours: env::var("IDEMPOTENCY_KEY_TTL_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .expect("IDEMPOTENCY_KEY_TTL_HOURS must be a valid number"),
+            idempotency_lease_secs: env::var("IDEMPOTENCY_LEASE_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .expect("IDEMPOTENCY_LEASE_SECS must be a valid number"),
+            enquiry_rate_limit_per_hour: env::var("ENQUIRY_RATE_LIMIT_PER_HOUR")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .expect("ENQUIRY_RATE_LIMIT_PER_HOUR must be a valid number"),
+            enquiry_min_submit_secs: env::var("ENQUIRY_MIN_SUBMIT_SECS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .expect("ENQUIRY_MIN_SUBMIT_SECS must be a valid number"),
+            trusted_proxies: env::var("TRUSTED_PROXIES")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|proxy| !proxy.is_empty())
+                .map(|proxy| proxy.parse().expect("TRUSTED_PROXIES must be a comma-separated list of IP addresses"))
+                .collect(),
+            site_url: env::var("SITE_URL")
+                .unwrap_or_else(|_| "http://localhost:3000".to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            default_locale,
+            supported_locales,
+            smtp_host: env::var("SMTP_HOST").ok().filter(|host| !host.trim().is_empty()),
+            smtp_port: env::var("SMTP_PORT")
+                .unwrap_or_else(|_| "587".to_string())
+                .parse()
+                .expect("SMTP_PORT must be a valid number"),
+            smtp_username: env::var("SMTP_USERNAME").ok(),
+            smtp_password: env::var("SMTP_PASSWORD").ok(),
+            smtp_starttls: env::var("SMTP_STARTTLS")
+                .map(|value| value != "false")
+                .unwrap_or(true),
+            mail_from: env::var("MAIL_FROM")
+                .unwrap_or_else(|_| "Webmeen Travel <no-reply@localhost>".to_string()),
+            notification_sweep_interval_secs: sweep_interval_secs("NOTIFICATION_SWEEP_INTERVAL_SECS", "30"),
         }
     }
 }
+
+/// Reads a job's sweep interval; `tokio::time::interval` panics on zero.
+fn sweep_interval_secs(name: &str, default: &str) -> u64 {
+    let secs: u64 = env::var(name)
+        .unwrap_or_else(|_| default.to_string())
+        .parse()
+        .unwrap_or_else(|_| panic!("{} must be a valid number", name));
+    assert!(secs >= 1, "{} must be at least 1", name);
+    secs
+}
@@ -1,6 +1,7 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 use crate::models::*;
+use crate::utils::publishing::VISIBLE_PACKAGE_SQL;
 
 #[allow(dead_code)]
 pub struct Database {
@@ -60,9 +61,10 @@ impl Database {
         let limit = limit.unwrap_or(20);
         let offset = offset.unwrap_or(0);
 
-        sqlx::query_as::<_, Package>(
-            "SELECT * FROM packages WHERE is_active = true ORDER BY created_at DESC LIMIT $1 OFFSET $2"
-        )
+        sqlx::query_as::<_, Package>(&format!(
+            "SELECT p.* FROM packages p WHERE {} ORDER BY p.created_at DESC LIMIT $1 OFFSET $2",
+            VISIBLE_PACKAGE_SQL
+        ))
         .bind(limit)
         .bind(offset)
         .fetch_all(&self.pool)
@@ -70,17 +72,19 @@ impl Database {
     }
 
     pub async fn get_featured_packages(&self) -> Result<Vec<Package>, sqlx::Error> {
-        sqlx::query_as::<_, Package>(
-            "SELECT * FROM packages WHERE is_active = true AND is_featured = true ORDER BY created_at DESC"
-        )
+        sqlx::query_as::<_, Package>(&format!(
+            "SELECT p.* FROM packages p WHERE {} AND p.is_featured = true ORDER BY p.created_at DESC",
+            VISIBLE_PACKAGE_SQL
+        ))
         .fetch_all(&self.pool)
         .await
     }
 
     pub async fn get_package_by_id(&self, package_id: Uuid) -> Result<Option<Package>, sqlx::Error> {
-        sqlx::query_as::<_, Package>(
-            "SELECT * FROM packages WHERE id = $1 AND is_active = true"
-        )
+        sqlx::query_as::<_, Package>(&format!(
+            "SELECT p.* FROM packages p WHERE p.id = $1 AND {}",
+            VISIBLE_PACKAGE_SQL
+        ))
         .bind(package_id)
         .fetch_optional(&self.pool)
         .await
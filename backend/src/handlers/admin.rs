@@ -1,27 +1,256 @@
-use actix_web::{web, HttpResponse, Result, Scope};
+use actix_web::{web, HttpRequest, HttpResponse, Result, Scope};
 use sqlx::PgPool;
 use uuid::Uuid;
 use validator::Validate;
-use chrono::Utc;
+use chrono::{Duration, Utc};
 
-use crate::models::{Package, CreatePackageRequest, User, Category, CreateCategoryRequest};
+use crate::config::Config;
+use crate::models::{Package, CreatePackageRequest, UpdatePackageStatusRequest, User, Category, CreateCategoryRequest, TaxRule, CreateTaxRuleRequest, WaitlistEntryResponse,
+    PaymentScheduleTemplate, UpdatePaymentScheduleRequest, RecordPaymentRequest, CreateMessageRequest,
+    UnansweredBookingResponse, PackageBookingRules, UpdateBookingRulesRequest, AdminBookingResponse,
+    BookingSearchQuery, Post, CreatePostRequest, Enquiry, EnquiryResponse, AssignEnquiryRequest,
+    UpdateEnquiryStatusRequest, ConvertEnquiryRequest, Booking, CreateBookingRequest, Rfq, RfqQuote, RfqQuoteItem,
+    RfqQuoteResponse, RfqDetailResponse, CreateQuoteRequest, UpdateRfqStatusRequest, Offer, CreateOfferRequest,
+    PackageTranslation, UpsertPackageTranslationRequest, CategoryTranslation, UpsertCategoryTranslationRequest, MissingTranslations, SubscriberQuery,
+    ContentTemplate, CreateContentTemplateRequest, UpdateContentTemplateRequest, UpdatePackageFaqsRequest, UpdatePackagePoliciesRequest,
+    PackageRevision, PackageRevisionSummary, RevisionDiffResponse, PatchPackageRequest, PatchCategoryRequest,
+    ClonePackageRequest, PackageVariant, PackageVariantRequest, ArchivedPackage};
 use crate::middleware::auth::AdminOnly;
+use crate::middleware::locale::Locale;
+use crate::handlers::bookings::{insert_booking_in, quote_package, BookingInsert, PackageQuote};
+use crate::handlers::packages::package_detail;
+use crate::handlers::posts::prepare_post;
+use crate::jobs::waitlist::{close_offers, spawn_offer_freed_seats, spawn_offer_freed_seats_for_package};
+use crate::utils::booking_export::{stream_bookings_export, ExportFormat};
+use crate::utils::booking_rules::{check_booking_date, package_booking_rules};
+use crate::utils::booking_search::{count_bookings, select_bookings, validate_search};
+use crate::utils::concurrency::{etag, expected_version, precondition_failed, precondition_required, VersionedWrite};
+use crate::utils::inventory::{seats_available, variant_seats_available};
+use crate::utils::messages::{post_message, read_thread, ThreadSide, STAFF_UNREAD_SQL};
+use crate::utils::newsletter::{encode_subscribers_csv, find_subscribers};
+use crate::utils::notifications::queue_notification;
+use crate::utils::offers::attach_offer_packages;
+use crate::utils::package_transfer::{decode_packages_csv, encode_packages_csv, export_packages, import_packages};
+use crate::utils::package_clone::copy_package;
+use crate::utils::package_content::{package_faqs, package_policies, templates_exist};
+use crate::utils::payments::{payment_summary, record_payment, PaymentOutcome};
+use crate::utils::publishing::check_publish_window;
+use crate::utils::quotes::price_quote;
+use crate::utils::reference::booking_lookup_key;
+use crate::utils::revisions::{diff_snapshots, record_package_revision, restore_package_revision};
+use crate::utils::slug::{unique_slug, SlugEntity};
+use crate::utils::variants::variant_selection_error;
 
 #[derive(serde::Deserialize)]
 struct UpdateBookingStatusRequest {
     status: String,
+    version: Option<i32>,
+}
+
+#[derive(sqlx::FromRow)]
+struct UpdatedBooking {
+    id: Uuid,
+    package_id: Option<Uuid>,
+    booking_date: chrono::NaiveDate,
+    version: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct LockedBooking {
+    id: Uuid,
+    package_id: Option<Uuid>,
+    variant_id: Option<Uuid>,
+    booking_date: chrono::NaiveDate,
+    number_of_people: i32,
+    status: String,
+    version: i32,
+}
+
+enum BookingStatusChange {
+    Updated(UpdatedBooking),
+    NotFound,
+    Stale(i32),
+    NotAllowed(String),
+    SoldOut(i64),
+}
+
+enum EnquiryConversion {
+    Converted(Box<Booking>),
+    NotFound,
+    AlreadyConverted(Uuid),
+    NotWon,
+    SoldOut(i64),
+}
+
+const BOOKING_STATUSES: [&str; 5] = ["pending", "confirmed", "completed", "cancelled", "expired"];
+
+/// Whether staff may move a booking from one status to another. Cancelled and
+/// expired bookings can be reinstated, subject to capacity; completed is final.
+fn booking_transition_allowed(from: &str, to: &str) -> bool {
+    from == to
+        || matches!(
+            (from, to),
+            ("pending", "confirmed" | "cancelled" | "expired")
+                | ("confirmed", "pending" | "completed" | "cancelled")
+                | ("cancelled" | "expired", "pending" | "confirmed")
+        )
+}
+
+/// Statuses whose bookings count against a departure's seats.
+fn holds_seats(status: &str) -> bool {
+    matches!(status, "pending" | "confirmed")
+}
+
+#[derive(serde::Deserialize)]
+struct ExportQuery {
+    format: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct PostAdminQuery {
+    status: Option<String>,
+    tag: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+#[derive(serde::Deserialize)]
+struct EnquiryQuery {
+    status: Option<String>,
+    assigned_to: Option<Uuid>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+#[derive(serde::Deserialize)]
+struct RfqQuery {
+    status: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+#[derive(serde::Deserialize)]
+struct PackageAdminQuery {
+    status: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+#[derive(serde::Deserialize)]
+struct PackageTransferQuery {
+    format: Option<String>,
+    dry_run: Option<bool>,
+}
+
+#[derive(serde::Deserialize)]
+struct RevisionDiffQuery {
+    from: i32,
+    to: Option<i32>,
+}
+
+#[derive(serde::Deserialize)]
+struct ContentTemplateQuery {
+    kind: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct MissingTranslationsQuery {
+    locale: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct WaitlistQuery {
+    package_id: Option<Uuid>,
+    booking_date: Option<chrono::NaiveDate>,
+    status: Option<String>,
 }
 
 pub fn admin_routes() -> Scope {
     web::scope("/admin")
         .route("/users", web::get().to(get_all_users))
+        .route("/packages", web::get().to(get_all_packages))
         .route("/packages", web::post().to(create_package))
+        .route("/packages/export", web::get().to(export_packages_file))
+        .route("/packages/archive", web::get().to(get_archived_packages))
+        .service(
+            web::resource("/packages/import")
+                .app_data(web::PayloadConfig::new(IMPORT_PAYLOAD_LIMIT))
+                .route(web::post().to(import_packages_file))
+        )
         .route("/packages/{id}", web::put().to(update_package))
+        .route("/packages/{id}", web::patch().to(patch_package))
         .route("/packages/{id}", web::delete().to(delete_package))
+        .route("/packages/{id}/restore", web::post().to(restore_package))
+        .route("/packages/{id}/permanent", web::delete().to(delete_package_permanently))
+        .route("/packages/{id}/status", web::put().to(update_package_status))
+        .route("/packages/{id}/preview", web::get().to(preview_package))
+        .route("/packages/{id}/clone", web::post().to(clone_package))
+        .route("/packages/{id}/variants", web::get().to(get_package_variants))
+        .route("/packages/{id}/variants", web::post().to(create_package_variant))
+        .route("/packages/{id}/variants/{variant_id}", web::put().to(update_package_variant))
+        .route("/packages/{id}/variants/{variant_id}", web::delete().to(delete_package_variant))
+        .route("/packages/{id}/revisions", web::get().to(get_package_revisions))
+        .route("/packages/{id}/revisions/diff", web::get().to(diff_package_revisions))
+        .route("/packages/{id}/revisions/{revision}", web::get().to(get_package_revision))
+        .route("/packages/{id}/revisions/{revision}/rollback", web::post().to(rollback_package_revision))
+        .route("/packages/{id}/payment-schedule", web::get().to(get_payment_schedule))
+        .route("/packages/{id}/payment-schedule", web::put().to(update_payment_schedule))
+        .route("/packages/{id}/booking-rules", web::get().to(get_booking_rules))
+        .route("/packages/{id}/booking-rules", web::put().to(update_booking_rules))
+        .route("/packages/{id}/booking-rules", web::delete().to(delete_booking_rules))
+        .route("/packages/{id}/faqs", web::get().to(get_package_faqs))
+        .route("/packages/{id}/faqs", web::put().to(update_package_faqs))
+        .route("/packages/{id}/policies", web::get().to(get_package_policies))
+        .route("/packages/{id}/policies", web::put().to(update_package_policies))
+        .route("/packages/{id}/translations", web::get().to(get_package_translations))
+        .route("/packages/{id}/translations/{locale}", web::put().to(upsert_package_translation))
+        .route("/packages/{id}/translations/{locale}", web::delete().to(delete_package_translation))
+        .route("/content-templates", web::get().to(get_content_templates))
+        .route("/content-templates", web::post().to(create_content_template))
+        .route("/content-templates/{id}", web::put().to(update_content_template))
+        .route("/content-templates/{id}", web::delete().to(delete_content_template))
         .route("/categories", web::get().to(get_categories))
         .route("/categories", web::post().to(create_category))
+        .route("/categories/{id}", web::patch().to(patch_category))
+        .route("/categories/{id}/translations", web::get().to(get_category_translations))
+        .route("/categories/{id}/translations/{locale}", web::put().to(upsert_category_translation))
+        .route("/categories/{id}/translations/{locale}", web::delete().to(delete_category_translation))
+        .route("/translations/missing", web::get().to(get_missing_translations))
+        .route("/tax-rules", web::get().to(get_tax_rules))
+        .route("/tax-rules", web::post().to(create_tax_rule))
+        .route("/tax-rules/{id}", web::put().to(update_tax_rule))
+        .route("/tax-rules/{id}", web::delete().to(delete_tax_rule))
         .route("/bookings", web::get().to(get_all_bookings))
+        .route("/bookings/unanswered", web::get().to(get_unanswered_bookings))
+        .route("/bookings/export", web::get().to(export_bookings))
+        .route("/bookings/{id}", web::get().to(get_booking))
         .route("/bookings/{id}/status", web::put().to(update_booking_status))
+        .route("/bookings/{id}/payments", web::get().to(get_booking_payments))
+        .route("/bookings/{id}/payments", web::post().to(record_booking_payment))
+        .route("/bookings/{id}/messages", web::get().to(get_booking_messages))
+        .route("/bookings/{id}/messages", web::post().to(post_booking_message))
+        .route("/waitlist", web::get().to(get_waitlist))
+        .route("/enquiries", web::get().to(get_enquiries))
+        .route("/enquiries/{id}", web::get().to(get_enquiry))
+        .route("/enquiries/{id}/assign", web::put().to(assign_enquiry))
+        .route("/enquiries/{id}/status", web::put().to(update_enquiry_status))
+        .route("/enquiries/{id}/convert", web::post().to(convert_enquiry))
+        .route("/newsletter/subscribers", web::get().to(get_newsletter_subscribers))
+        .route("/newsletter/subscribers/export", web::get().to(export_newsletter_subscribers))
+        .route("/offers", web::get().to(get_offers))
+        .route("/offers", web::post().to(create_offer))
+        .route("/offers/{id}", web::put().to(update_offer))
+        .route("/offers/{id}", web::delete().to(delete_offer))
+        .route("/rfqs", web::get().to(get_rfqs))
+        .route("/rfqs/{id}", web::get().to(get_rfq))
+        .route("/rfqs/{id}/status", web::put().to(update_rfq_status))
+        .route("/rfqs/{id}/quotes", web::post().to(create_rfq_quote))
+        .route("/rfqs/{id}/quotes/{quote_id}/send", web::post().to(send_rfq_quote))
+        .route("/posts", web::get().to(get_all_posts))
+        .route("/posts", web::post().to(create_post))
+        .route("/posts/{id}", web::get().to(get_post))
+        .route("/posts/{id}", web::put().to(update_post))
+        .route("/posts/{id}", web::delete().to(delete_post))
 }
 
 async fn get_all_users(
@@ -70,17 +299,30 @@ async fn create_package(
         })));
     }
 
+    let status = req.status.as_deref().unwrap_or("draft");
+    if let Err(error) = check_publish_window(status, req.publish_at, req.unpublish_at) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })));
+    }
+
+    let slug = match new_slug(pool.get_ref(), SlugEntity::Package, req.slug.as_deref(), &req.title).await {
+        Ok(slug) => slug,
+        Err(response) => return Ok(response),
+    };
+
     let package_id = Uuid::new_v4();
     let now = Utc::now();
 
     let result = sqlx::query_as::<_, Package>(
         r#"
-        INSERT INTO packages (id, title, description, price, duration_days, max_people, category_id, image_url, highlights, inclusions, exclusions, itinerary, is_featured, is_active, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, true, $14, $15)
+        INSERT INTO packages (id, slug, title, description, price, duration_days, max_people, category_id, image_url, highlights, inclusions, exclusions, itinerary, is_featured, is_international, status, publish_at, unpublish_at, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
         RETURNING *
         "#
     )
     .bind(package_id)
+    .bind(&slug)
     .bind(&req.title)
     .bind(&req.description)
     .bind(req.price)
@@ -93,6 +335,10 @@ async fn create_package(
     .bind(&req.exclusions)
     .bind(&req.itinerary)
     .bind(req.is_featured.unwrap_or(false))
+    .bind(req.is_international.unwrap_or(false))
+    .bind(status)
+    .bind(req.publish_at)
+    .bind(req.unpublish_at)
     .bind(now)
     .bind(now)
     .fetch_one(pool.get_ref())
@@ -102,7 +348,14 @@ async fn create_package(
         Ok(package) => {
             Ok(HttpResponse::Created().json(serde_json::json!({
                 "message": "Package created successfully",
-                "package_id": package.id
+                "package_id": package.id,
+                "slug": package.slug,
+                "status": package.status
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A package with this slug already exists"
             })))
         }
         Err(e) => {
@@ -116,7 +369,9 @@ async fn create_package(
 
 async fn update_package(
     pool: web::Data<PgPool>,
-    _admin: AdminOnly,
+    config: web::Data<Config>,
+    admin: AdminOnly,
+    http_req: HttpRequest,
     path: web::Path<Uuid>,
     req: web::Json<CreatePackageRequest>,
 ) -> Result<HttpResponse> {
@@ -129,42 +384,40 @@ async fn update_package(
         })));
     }
 
-    let result = sqlx::query(
-        r#"
-        UPDATE packages 
-        SET title = $2, description = $3, price = $4, duration_days = $5, max_people = $6, 
-            category_id = $7, image_url = $8, highlights = $9, inclusions = $10, 
-            exclusions = $11, itinerary = $12, is_featured = $13, updated_at = NOW()
-        WHERE id = $1
-        "#
-    )
-    .bind(package_id)
-    .bind(&req.title)
-    .bind(&req.description)
-    .bind(req.price)
-    .bind(req.duration_days)
-    .bind(req.max_people)
-    .bind(req.category_id)
-    .bind(&req.image_url)
-    .bind(&req.highlights)
-    .bind(&req.inclusions)
-    .bind(&req.exclusions)
-    .bind(&req.itinerary)
-    .bind(req.is_featured.unwrap_or(false))
-    .execute(pool.get_ref())
-    .await;
+    let expected = match expected_version(&http_req, req.version) {
+        Ok(expected) => expected,
+        Err(response) => return Ok(response),
+    };
+
+    let result = write_package_edit(pool.get_ref(), admin.user_id, package_id, PackageEdit::Replace(&req), expected).await;
 
     match result {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
-                Ok(HttpResponse::Ok().json(serde_json::json!({
-                    "message": "Package updated successfully"
-                })))
-            } else {
-                Ok(HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "Package not found"
+        Ok(VersionedWrite::Updated(package)) => {
+            // A capacity increase may free seats for waitlisted customers
+            spawn_offer_freed_seats_for_package(pool.get_ref().clone(), config.waitlist_offer_hours, package_id);
+
+            Ok(HttpResponse::Ok()
+                .insert_header(("ETag", etag(package.version)))
+                .json(serde_json::json!({
+                    "message": "Package updated successfully",
+                    "version": package.version
                 })))
-            }
+        }
+        Ok(VersionedWrite::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Ok(VersionedWrite::Stale(current)) => Ok(precondition_failed(Some(current))),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A package with this slug already exists"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Category not found"
+            })))
         }
         Err(e) => {
             log::error!("Failed to update package: {}", e);
@@ -175,194 +428,4093 @@ async fn update_package(
     }
 }
 
-async fn delete_package(
+/// Updates only the fields given. The version being edited must be sent, as
+/// `If-Match` or `version`, so concurrent edits cannot silently overwrite
+/// each other.
+async fn patch_package(
     pool: web::Data<PgPool>,
-    _admin: AdminOnly,
+    config: web::Data<Config>,
+    admin: AdminOnly,
+    http_req: HttpRequest,
     path: web::Path<Uuid>,
+    req: web::Json<PatchPackageRequest>,
 ) -> Result<HttpResponse> {
     let package_id = path.into_inner();
 
-    let result = sqlx::query(
-        "UPDATE packages SET is_active = false, updated_at = NOW() WHERE id = $1"
-    )
-    .bind(package_id)
-    .execute(pool.get_ref())
-    .await;
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let expected = match expected_version(&http_req, req.version) {
+        Ok(Some(expected)) => Some(expected),
+        Ok(None) if http_req.headers().contains_key("If-Match") => None,
+        Ok(None) => return Ok(precondition_required()),
+        Err(response) => return Ok(response),
+    };
+
+    let result = write_package_edit(pool.get_ref(), admin.user_id, package_id, PackageEdit::Patch(&req), expected).await;
 
     match result {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
-                Ok(HttpResponse::Ok().json(serde_json::json!({
-                    "message": "Package deleted successfully"
-                })))
-            } else {
-                Ok(HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "Package not found"
-                })))
+        Ok(VersionedWrite::Updated(package)) => {
+            if req.max_people.is_some() {
+                spawn_offer_freed_seats_for_package(pool.get_ref().clone(), config.waitlist_offer_hours, package_id);
             }
+
+            Ok(HttpResponse::Ok()
+                .insert_header(("ETag", etag(package.version)))
+                .json(package))
+        }
+        Ok(VersionedWrite::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Ok(VersionedWrite::Stale(current)) => Ok(precondition_failed(Some(current))),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A package with this slug already exists"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Category not found"
+            })))
         }
         Err(e) => {
-            log::error!("Failed to delete package: {}", e);
+            log::error!("Failed to update package: {}", e);
             Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to delete package"
+                "error": "Failed to update package"
             })))
         }
     }
 }
 
-async fn get_categories(
+/// A package edit: PUT replaces every field, PATCH only the fields given.
+enum PackageEdit<'a> {
+    Replace(&'a CreatePackageRequest),
+    Patch(&'a PatchPackageRequest),
+}
+
+/// Saves the current package as a revision and applies the edit, unless the
+/// package has moved past the expected version.
+async fn write_package_edit(
+    pool: &PgPool,
+    editor_id: Uuid,
+    package_id: Uuid,
+    edit: PackageEdit<'_>,
+    expected: Option<i32>,
+) -> Result<VersionedWrite<Package>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    if record_package_revision(&mut tx, package_id, editor_id).await?.is_none() {
+        return Ok(VersionedWrite::NotFound);
+    }
+
+    let current = sqlx::query_scalar::<_, i32>("SELECT version FROM packages WHERE id = $1")
+        .bind(package_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if expected.is_some_and(|expected| expected != current) {
+        return Ok(VersionedWrite::Stale(current));
+    }
+
+    let package = match edit {
+        PackageEdit::Replace(req) => {
+            sqlx::query_as::<_, Package>(
+                r#"
+                UPDATE packages
+                SET title = $2, description = $3, price = $4, duration_days = $5, max_people = $6,
+                    category_id = $7, image_url = $8, highlights = $9, inclusions = $10,
+                    exclusions = $11, itinerary = $12, is_featured = $13, is_international = $14,
+                    slug = COALESCE($15, slug), updated_at = NOW()
+                WHERE id = $1
+                RETURNING *
+                "#
+            )
+            .bind(package_id)
+            .bind(&req.title)
+            .bind(&req.description)
+            .bind(req.price)
+            .bind(req.duration_days)
+            .bind(req.max_people)
+            .bind(req.category_id)
+            .bind(&req.image_url)
+            .bind(&req.highlights)
+            .bind(&req.inclusions)
+            .bind(&req.exclusions)
+            .bind(&req.itinerary)
+            .bind(req.is_featured.unwrap_or(false))
+            .bind(req.is_international.unwrap_or(false))
+            .bind(&req.slug)
+            .fetch_one(&mut *tx)
+            .await?
+        }
+        PackageEdit::Patch(patch) => {
+            sqlx::query_as::<_, Package>(
+                r#"
+                UPDATE packages
+                SET title = COALESCE($2, title), description = COALESCE($3, description), price = COALESCE($4, price),
+                    duration_days = COALESCE($5, duration_days), max_people = COALESCE($6, max_people),
+                    category_id = COALESCE($7, category_id),
+                    image_url = CASE WHEN $8::TEXT IS NULL THEN image_url ELSE NULLIF($8, '') END,
+                    highlights = COALESCE($9, highlights), inclusions = COALESCE($10, inclusions),
+                    exclusions = COALESCE($11, exclusions), itinerary = COALESCE($12, itinerary),
+                    is_featured = COALESCE($13, is_featured), is_international = COALESCE($14, is_international),
+                    slug = COALESCE($15, slug), updated_at = NOW()
+                WHERE id = $1
+                RETURNING *
+                "#
+            )
+            .bind(package_id)
+            .bind(&patch.title)
+            .bind(&patch.description)
+            .bind(patch.price)
+            .bind(patch.duration_days)
+            .bind(patch.max_people)
+            .bind(patch.category_id)
+            .bind(&patch.image_url)
+            .bind(&patch.highlights)
+            .bind(&patch.inclusions)
+            .bind(&patch.exclusions)
+            .bind(&patch.itinerary)
+            .bind(patch.is_featured)
+            .bind(patch.is_international)
+            .bind(&patch.slug)
+            .fetch_one(&mut *tx)
+            .await?
+        }
+    };
+
+    tx.commit().await?;
+    Ok(VersionedWrite::Updated(package))
+}
+
+/// Starts a new draft from an existing package; see `copy_package`.
+async fn clone_package(
     pool: web::Data<PgPool>,
     _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<ClonePackageRequest>,
 ) -> Result<HttpResponse> {
-    let categories = sqlx::query_as::<_, Category>(
-        "SELECT * FROM categories WHERE is_active = true ORDER BY name"
-    )
-    .fetch_all(pool.get_ref())
+    let source_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let result: Result<Option<Package>, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+        let package = copy_package(&mut tx, source_id, &req).await?;
+        tx.commit().await?;
+        Ok(package)
+    }
     .await;
 
-    match categories {
-        Ok(categories) => {
-            Ok(HttpResponse::Ok().json(categories))
+    match result {
+        Ok(Some(package)) => {
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "message": "Package cloned successfully",
+                "package_id": package.id,
+                "slug": package.slug,
+                "status": package.status,
+                "cloned_from": package.cloned_from
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A package with this slug already exists"
+            })))
         }
         Err(e) => {
-            log::error!("Failed to fetch categories: {}", e);
+            log::error!("Failed to clone package: {}", e);
             Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch categories"
+                "error": "Failed to clone package"
             })))
         }
     }
 }
 
-async fn create_category(
+/// Largest package import accepted, in bytes.
+pub(crate) const IMPORT_PAYLOAD_LIMIT: usize = 10 * 1024 * 1024;
+
+/// Every package as a `json` (default) or `csv` file for editing offline.
+async fn export_packages_file(
     pool: web::Data<PgPool>,
     _admin: AdminOnly,
-    req: web::Json<CreateCategoryRequest>,
+    query: web::Query<PackageTransferQuery>,
 ) -> Result<HttpResponse> {
-    if let Err(errors) = req.validate() {
+    let format = query.format.as_deref().unwrap_or("json");
+    if format != "json" && format != "csv" {
         return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Validation failed",
-            "details": errors
+            "error": "format must be json or csv"
         })));
     }
 
-    let category_id = Uuid::new_v4();
-    let now = Utc::now();
+    let packages = match export_packages(pool.get_ref()).await {
+        Ok(packages) => packages,
+        Err(e) => {
+            log::error!("Failed to fetch packages for export: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to export packages"
+            })));
+        }
+    };
 
-    let result = sqlx::query_as::<_, Category>(
-        r#"
-        INSERT INTO categories (id, name, description, icon, is_active, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, true, $5, $6)
-        RETURNING *
-        "#
-    )
-    .bind(category_id)
-    .bind(&req.name)
-    .bind(&req.description)
-    .bind(&req.icon)
-    .bind(now)
-    .bind(now)
-    .fetch_one(pool.get_ref())
-    .await;
+    let file_name = format!("packages-{}.{}", Utc::now().format("%Y%m%d-%H%M%S"), format);
+    let body = match format {
+        "csv" => encode_packages_csv(&packages),
+        _ => serde_json::to_vec_pretty(&packages).map_err(Into::into),
+    };
 
-    match result {
-        Ok(category) => {
-            Ok(HttpResponse::Created().json(serde_json::json!({
-                "message": "Category created successfully",
-                "category_id": category.id
+    match body {
+        Ok(body) => {
+            Ok(HttpResponse::Ok()
+                .content_type(if format == "csv" { "text/csv; charset=utf-8" } else { "application/json" })
+                .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", file_name)))
+                .body(body))
+        }
+        Err(e) => {
+            log::error!("Failed to encode packages: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to export packages"
             })))
         }
+    }
+}
+
+/// Creates and updates packages from an export-shaped file: a JSON array, or
+/// CSV when `format=csv`. With `dry_run=true` it only reports what would
+/// happen. Otherwise all rows are saved together, or none if any row fails.
+async fn import_packages_file(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    admin: AdminOnly,
+    query: web::Query<PackageTransferQuery>,
+    body: web::Bytes,
+) -> Result<HttpResponse> {
+    let rows = match query.format.as_deref().unwrap_or("json") {
+        "json" => serde_json::from_slice::<Vec<serde_json::Value>>(&body).map_err(|e| e.to_string()),
+        "csv" => decode_packages_csv(&body).map_err(|e| e.to_string()),
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "format must be json or csv"
+            })));
+        }
+    };
+    let rows = match rows {
+        Ok(rows) => rows,
         Err(e) => {
-            log::error!("Failed to create category: {}", e);
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("Could not read the file: {}", e)
+            })));
+        }
+    };
+
+    let dry_run = query.dry_run.unwrap_or(false);
+    match import_packages(pool.get_ref(), admin.user_id, rows, dry_run).await {
+        Ok((report, updated_ids)) => {
+            for package_id in updated_ids.into_iter().filter(|_| report.applied) {
+                spawn_offer_freed_seats_for_package(pool.get_ref().clone(), config.waitlist_offer_hours, package_id);
+            }
+
+            if report.errors > 0 && !dry_run {
+                Ok(HttpResponse::UnprocessableEntity().json(report))
+            } else {
+                Ok(HttpResponse::Ok().json(report))
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to import packages: {}", e);
             Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to create category"
+                "error": "Failed to import packages"
             })))
         }
     }
 }
 
-async fn get_all_bookings(
+async fn get_package_variants(
     pool: web::Data<PgPool>,
     _admin: AdminOnly,
+    path: web::Path<Uuid>,
 ) -> Result<HttpResponse> {
-    let bookings = sqlx::query(
-        r#"
-        SELECT b.id, b.booking_date, b.number_of_people, b.total_amount, b.status, 
-               b.special_requests, b.created_at, p.title as package_title, 
-               u.first_name, u.last_name, u.email
-        FROM bookings b
-        JOIN packages p ON b.package_id = p.id
-        JOIN users u ON b.user_id = u.id
-        ORDER BY b.created_at DESC
-        "#
+    let package_id = path.into_inner();
+
+    let variants = sqlx::query_as::<_, PackageVariant>(
+        "SELECT * FROM package_variants WHERE package_id = $1 ORDER BY position, price"
     )
+    .bind(package_id)
     .fetch_all(pool.get_ref())
     .await;
 
-    match bookings {
-        Ok(bookings) => {
-            let booking_responses: Vec<_> = bookings.into_iter().map(|b| {
-                use sqlx::Row;
-                serde_json::json!({
-                    "id": b.get::<Uuid, _>("id"),
-                    "package_title": b.get::<String, _>("package_title"),
-                    "user_name": format!("{} {}", 
-                        b.get::<String, _>("first_name"), 
-                        b.get::<String, _>("last_name")
-                    ),
-                    "user_email": b.get::<String, _>("email"),
-                    "booking_date": b.get::<chrono::NaiveDate, _>("booking_date"),
-                    "number_of_people": b.get::<i32, _>("number_of_people"),
-                    "total_amount": b.get::<i32, _>("total_amount"),
-                    "status": b.get::<String, _>("status"),
-                    "special_requests": b.get::<Option<String>, _>("special_requests"),
-                    "created_at": b.get::<chrono::DateTime<chrono::Utc>, _>("created_at")
-                })
-            }).collect();
-
-            Ok(HttpResponse::Ok().json(booking_responses))
-        }
+    match variants {
+        Ok(variants) => Ok(HttpResponse::Ok().json(variants)),
         Err(e) => {
-            log::error!("Failed to fetch bookings: {}", e);
+            log::error!("Failed to fetch package variants: {}", e);
             Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch bookings"
+                "error": "Failed to fetch package variants"
             })))
         }
     }
 }
 
-async fn update_booking_status(
+async fn create_package_variant(
     pool: web::Data<PgPool>,
     _admin: AdminOnly,
     path: web::Path<Uuid>,
-    req: web::Json<UpdateBookingStatusRequest>,
+    req: web::Json<PackageVariantRequest>,
 ) -> Result<HttpResponse> {
-    let booking_id = path.into_inner();
+    let package_id = path.into_inner();
 
-    let result = sqlx::query(
-        "UPDATE bookings SET status = $2, updated_at = NOW() WHERE id = $1"
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let result = sqlx::query_as::<_, PackageVariant>(
+        r#"
+        INSERT INTO package_variants (id, package_id, name, position, price, added_inclusions, removed_inclusions, max_people, is_active, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), NOW())
+        RETURNING *
+        "#
     )
-    .bind(booking_id)
-    .bind(&req.status)
-    .execute(pool.get_ref())
+    .bind(Uuid::new_v4())
+    .bind(package_id)
+    .bind(&req.name)
+    .bind(req.position.unwrap_or(0))
+    .bind(req.price)
+    .bind(req.added_inclusions.clone().unwrap_or_default())
+    .bind(req.removed_inclusions.clone().unwrap_or_default())
+    .bind(req.max_people)
+    .bind(req.is_active.unwrap_or(true))
+    .fetch_one(pool.get_ref())
     .await;
 
     match result {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
+        Ok(variant) => Ok(HttpResponse::Created().json(variant)),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "This package already has a variant with this name"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to create package variant: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create package variant"
+            })))
+        }
+    }
+}
+
+async fn update_package_variant(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, Uuid)>,
+    req: web::Json<PackageVariantRequest>,
+) -> Result<HttpResponse> {
+    let (package_id, variant_id) = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let result = sqlx::query_as::<_, PackageVariant>(
+        r#"
+        UPDATE package_variants
+        SET name = $3, position = COALESCE($4, position), price = $5, added_inclusions = $6, removed_inclusions = $7,
+            max_people = $8, is_active = COALESCE($9, is_active), updated_at = NOW()
+        WHERE id = $1 AND package_id = $2
+        RETURNING *
+        "#
+    )
+    .bind(variant_id)
+    .bind(package_id)
+    .bind(&req.name)
+    .bind(req.position)
+    .bind(req.price)
+    .bind(req.added_inclusions.clone().unwrap_or_default())
+    .bind(req.removed_inclusions.clone().unwrap_or_default())
+    .bind(req.max_people)
+    .bind(req.is_active)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(Some(variant)) => {
+            // A capacity increase may free seats for customers waiting on this variant
+            spawn_offer_freed_seats_for_package(pool.get_ref().clone(), config.waitlist_offer_hours, package_id);
+            Ok(HttpResponse::Ok().json(variant))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Variant not found"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "This package already has a variant with this name"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update package variant: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update package variant"
+            })))
+        }
+    }
+}
+
+async fn delete_package_variant(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse> {
+    let (package_id, variant_id) = path.into_inner();
+
+    let result = sqlx::query("DELETE FROM package_variants WHERE id = $1 AND package_id = $2")
+        .bind(variant_id)
+        .bind(package_id)
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) if result.rows_affected() > 0 => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Variant deleted successfully"
+            })))
+        }
+        Ok(_) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Variant not found"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "This variant has bookings; deactivate it instead"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to delete package variant: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete package variant"
+            })))
+        }
+    }
+}
+
+async fn get_package_revisions(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let revisions = sqlx::query_as::<_, PackageRevisionSummary>(
+        r#"
+        SELECT r.id, r.revision_number, r.edited_by, u.first_name || ' ' || u.last_name as editor_name, r.created_at
+        FROM package_revisions r
+        LEFT JOIN users u ON u.id = r.edited_by
+        WHERE r.package_id = $1
+        ORDER BY r.revision_number DESC
+        "#
+    )
+    .bind(package_id)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match revisions {
+        Ok(revisions) => Ok(HttpResponse::Ok().json(revisions)),
+        Err(e) => {
+            log::error!("Failed to fetch package revisions: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch package revisions"
+            })))
+        }
+    }
+}
+
+async fn get_package_revision(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, i32)>,
+) -> Result<HttpResponse> {
+    let (package_id, revision_number) = path.into_inner();
+
+    let revision = sqlx::query_as::<_, PackageRevision>(
+        "SELECT * FROM package_revisions WHERE package_id = $1 AND revision_number = $2"
+    )
+    .bind(package_id)
+    .bind(revision_number)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match revision {
+        Ok(Some(revision)) => Ok(HttpResponse::Ok().json(revision)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Revision not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package revision: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch package revision"
+            })))
+        }
+    }
+}
+
+/// Field-level changes between two revisions, or between a revision and the
+/// current package when `to` is omitted.
+async fn diff_package_revisions(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    query: web::Query<RevisionDiffQuery>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let snapshot = |revision_number: i32| {
+        sqlx::query_scalar::<_, serde_json::Value>(
+            "SELECT snapshot FROM package_revisions WHERE package_id = $1 AND revision_number = $2"
+        )
+        .bind(package_id)
+        .bind(revision_number)
+        .fetch_optional(pool.get_ref())
+    };
+
+    let result: Result<Option<(serde_json::Value, serde_json::Value)>, sqlx::Error> = async {
+        let Some(from) = snapshot(query.from).await? else {
+            return Ok(None);
+        };
+        let to = match query.to {
+            Some(to) => snapshot(to).await?,
+            None => {
+                sqlx::query_scalar::<_, serde_json::Value>("SELECT to_jsonb(p) FROM packages p WHERE p.id = $1")
+                    .bind(package_id)
+                    .fetch_optional(pool.get_ref())
+                    .await?
+            }
+        };
+        Ok(to.map(|to| (from, to)))
+    }
+    .await;
+
+    match result {
+        Ok(Some((from, to))) => {
+            Ok(HttpResponse::Ok().json(RevisionDiffResponse {
+                from_revision: query.from,
+                to_revision: query.to,
+                changes: diff_snapshots(&from, &to),
+            }))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Revision not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to diff package revisions: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to diff package revisions"
+            })))
+        }
+    }
+}
+
+/// Restores a package's content from a revision. The state being replaced is
+/// itself saved as a new revision, so a rollback can be undone.
+async fn rollback_package_revision(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    admin: AdminOnly,
+    path: web::Path<(Uuid, i32)>,
+) -> Result<HttpResponse> {
+    let (package_id, revision_number) = path.into_inner();
+
+    let result: Result<Option<i32>, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let Some(saved_as) = record_package_revision(&mut tx, package_id, admin.user_id).await? else {
+            return Ok(None);
+        };
+        if !restore_package_revision(&mut tx, package_id, revision_number).await? {
+            return Ok(None);
+        }
+
+        tx.commit().await?;
+        Ok(Some(saved_as))
+    }
+    .await;
+
+    match result {
+        Ok(Some(saved_as)) => {
+            spawn_offer_freed_seats_for_package(pool.get_ref().clone(), config.waitlist_offer_hours, package_id);
+
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": format!("Package rolled back to revision {}", revision_number),
+                "previous_state_revision": saved_as
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Revision not found"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Another package now uses this revision's slug"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "This revision's category no longer exists"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to roll back package: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to roll back package"
+            })))
+        }
+    }
+}
+
+/// Archives a package: it leaves the site but keeps its bookings and history.
+async fn delete_package(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let result = sqlx::query(
+        "UPDATE packages SET status = 'archived', updated_at = NOW() WHERE id = $1"
+    )
+    .bind(package_id)
+    .execute(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(result) => {
+            if result.rows_affected() > 0 {
+                Ok(HttpResponse::Ok().json(serde_json::json!({
+                    "message": "Package archived successfully"
+                })))
+            } else {
+                Ok(HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Package not found"
+                })))
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to delete package: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete package"
+            })))
+        }
+    }
+}
+
+async fn get_archived_packages(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+) -> Result<HttpResponse> {
+    let packages = sqlx::query_as::<_, ArchivedPackage>(
+        r#"
+        SELECT p.id, p.slug, p.title, p.external_id, p.updated_at,
+               (SELECT COUNT(*) FROM bookings b WHERE b.package_id = p.id) as booking_count
+        FROM packages p
+        WHERE p.status = 'archived'
+        ORDER BY p.updated_at DESC
+        "#
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match packages {
+        Ok(packages) => Ok(HttpResponse::Ok().json(packages)),
+        Err(e) => {
+            log::error!("Failed to fetch archived packages: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch archived packages"
+            })))
+        }
+    }
+}
+
+/// Brings an archived package back as a draft, to go through review and
+/// publishing again.
+async fn restore_package(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let result: Result<Option<String>, sqlx::Error> = async {
+        let restored = sqlx::query_scalar::<_, String>(
+            r#"
+            UPDATE packages SET status = 'draft', updated_at = NOW()
+            WHERE id = $1 AND status = 'archived'
+            RETURNING status
+            "#
+        )
+        .bind(package_id)
+        .fetch_optional(pool.get_ref())
+        .await?;
+
+        match restored {
+            Some(status) => Ok(Some(status)),
+            None => {
+                sqlx::query_scalar::<_, String>("SELECT status FROM packages WHERE id = $1")
+                    .bind(package_id)
+                    .fetch_optional(pool.get_ref())
+                    .await
+            }
+        }
+    }
+    .await;
+
+    match result {
+        Ok(Some(status)) if status == "draft" => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Package restored as a draft",
+                "status": status
+            })))
+        }
+        Ok(Some(_)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Only archived packages can be restored"
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to restore package: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to restore package"
+            })))
+        }
+    }
+}
+
+enum PermanentDelete {
+    Deleted,
+    NotFound,
+    NotArchived,
+    HasBookings(i64),
+}
+
+/// Deletes an archived package for good, with its content, variants and
+/// revisions. Packages that any booking refers to can only stay archived.
+async fn delete_package_permanently(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let result: Result<PermanentDelete, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        // Bookings lock the package row too, so none can slip in meanwhile
+        let status = sqlx::query_scalar::<_, String>("SELECT status FROM packages WHERE id = $1 FOR UPDATE")
+            .bind(package_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        match status.as_deref() {
+            None => return Ok(PermanentDelete::NotFound),
+            Some("archived") => {}
+            Some(_) => return Ok(PermanentDelete::NotArchived),
+        }
+
+        let bookings = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM bookings WHERE package_id = $1")
+            .bind(package_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if bookings > 0 {
+            return Ok(PermanentDelete::HasBookings(bookings));
+        }
+
+        sqlx::query("DELETE FROM slug_redirects WHERE entity_type = 'package' AND target_id = $1")
+            .bind(package_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM packages WHERE id = $1")
+            .bind(package_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(PermanentDelete::Deleted)
+    }
+    .await;
+
+    match result {
+        Ok(PermanentDelete::Deleted) => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Package deleted permanently"
+            })))
+        }
+        Ok(PermanentDelete::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Ok(PermanentDelete::NotArchived) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Archive the package before deleting it permanently"
+            })))
+        }
+        Ok(PermanentDelete::HasBookings(bookings)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "This package has bookings and can only be archived",
+                "booking_count": bookings
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "This package has bookings and can only be archived"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to delete package permanently: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete package"
+            })))
+        }
+    }
+}
+
+/// Every package in any workflow state, for the editorial dashboard.
+async fn get_all_packages(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<PackageAdminQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let packages = sqlx::query_as::<_, Package>(
+        r#"
+        SELECT * FROM packages
+        WHERE ($1::TEXT IS NULL OR status = $1)
+        ORDER BY updated_at DESC
+        LIMIT $2 OFFSET $3
+        "#
+    )
+    .bind(&query.status)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match packages {
+        Ok(packages) => Ok(HttpResponse::Ok().json(packages)),
+        Err(e) => {
+            log::error!("Failed to fetch packages: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch packages"
+            })))
+        }
+    }
+}
+
+/// Moves a package through the publishing workflow. Scheduled and published
+/// packages go public once `publish_at` has passed (immediately if unset)
+/// and come down at `unpublish_at`.
+async fn update_package_status(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdatePackageStatusRequest>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    if let Err(error) = check_publish_window(&req.status, req.publish_at, req.unpublish_at) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })));
+    }
+
+    let package = sqlx::query_as::<_, Package>(
+        r#"
+        UPDATE packages
+        SET status = $2, publish_at = $3, unpublish_at = $4, updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#
+    )
+    .bind(package_id)
+    .bind(&req.status)
+    .bind(req.publish_at)
+    .bind(req.unpublish_at)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match package {
+        Ok(Some(package)) => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Package status updated",
+                "status": package.status,
+                "publish_at": package.publish_at,
+                "unpublish_at": package.unpublish_at
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update package status: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update package status"
+            })))
+        }
+    }
+}
+
+/// Shows a package in any state exactly as the public detail page would.
+async fn preview_package(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    locale: Locale,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package = sqlx::query_as::<_, Package>("SELECT * FROM packages WHERE id = $1")
+        .bind(path.into_inner())
+        .fetch_optional(pool.get_ref())
+        .await;
+
+    let package = match package {
+        Ok(Some(package)) => package,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch package"
+            })));
+        }
+    };
+
+    match package_detail(pool.get_ref(), package, &locale).await {
+        Ok(detail) => Ok(HttpResponse::Ok().json(detail)),
+        Err(e) => {
+            log::error!("Failed to fetch package details: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch package"
+            })))
+        }
+    }
+}
+
+async fn get_payment_schedule(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let templates = sqlx::query_as::<_, PaymentScheduleTemplate>(
+        "SELECT * FROM payment_schedule_templates WHERE package_id = $1 ORDER BY position"
+    )
+    .bind(package_id)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match templates {
+        Ok(templates) => {
+            Ok(HttpResponse::Ok().json(templates))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch payment schedule: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch payment schedule"
+            })))
+        }
+    }
+}
+
+async fn update_payment_schedule(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdatePaymentScheduleRequest>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    // An empty schedule means the full amount is due at booking
+    let total_share: i32 = req.installments.iter().map(|i| i.percent_basis_points).sum();
+    if !req.installments.is_empty() && total_share != 10000 {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Installment shares must add up to 10000 basis points (100%)"
+        })));
+    }
+
+    let result: Result<Vec<PaymentScheduleTemplate>, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM payment_schedule_templates WHERE package_id = $1")
+            .bind(package_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut templates = Vec::with_capacity(req.installments.len());
+        for (index, item) in req.installments.iter().enumerate() {
+            let template = sqlx::query_as::<_, PaymentScheduleTemplate>(
+                r#"
+                INSERT INTO payment_schedule_templates (id, package_id, position, label, percent_basis_points, due_days_before_departure, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, NOW())
+                RETURNING *
+                "#
+            )
+            .bind(Uuid::new_v4())
+            .bind(package_id)
+            .bind(index as i32 + 1)
+            .bind(&item.label)
+            .bind(item.percent_basis_points)
+            .bind(item.due_days_before_departure)
+            .fetch_one(&mut *tx)
+            .await?;
+            templates.push(template);
+        }
+
+        tx.commit().await?;
+        Ok(templates)
+    }
+    .await;
+
+    match result {
+        Ok(templates) => {
+            Ok(HttpResponse::Ok().json(templates))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update payment schedule: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update payment schedule"
+            })))
+        }
+    }
+}
+
+async fn get_booking_rules(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    match package_booking_rules(pool.get_ref(), package_id).await {
+        Ok(Some(rules)) => Ok(HttpResponse::Ok().json(rules)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "No booking rules set for this package"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking rules: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking rules"
+            })))
+        }
+    }
+}
+
+async fn update_booking_rules(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdateBookingRulesRequest>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    if req.max_advance_days.is_some_and(|max_advance_days| max_advance_days < req.min_lead_days) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "max_advance_days cannot be less than min_lead_days"
+        })));
+    }
+
+    let mut departure_weekdays = req.departure_weekdays.clone();
+    departure_weekdays.sort();
+    departure_weekdays.dedup();
+    let mut fixed_departure_dates = req.fixed_departure_dates.clone();
+    fixed_departure_dates.sort();
+    fixed_departure_dates.dedup();
+
+    let rules = sqlx::query_as::<_, PackageBookingRules>(
+        r#"
+        INSERT INTO package_booking_rules (package_id, min_lead_days, max_advance_days, departure_weekdays, fixed_departure_dates, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, NOW(), NOW())
+        ON CONFLICT (package_id) DO UPDATE
+        SET min_lead_days = EXCLUDED.min_lead_days,
+            max_advance_days = EXCLUDED.max_advance_days,
+            departure_weekdays = EXCLUDED.departure_weekdays,
+            fixed_departure_dates = EXCLUDED.fixed_departure_dates
+        RETURNING *
+        "#
+    )
+    .bind(package_id)
+    .bind(req.min_lead_days)
+    .bind(req.max_advance_days)
+    .bind(&departure_weekdays)
+    .bind(&fixed_departure_dates)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match rules {
+        Ok(rules) => {
+            Ok(HttpResponse::Ok().json(rules))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update booking rules: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update booking rules"
+            })))
+        }
+    }
+}
+
+async fn delete_booking_rules(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let result = sqlx::query("DELETE FROM package_booking_rules WHERE package_id = $1")
+        .bind(package_id)
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) if result.rows_affected() > 0 => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Booking rules removed"
+            })))
+        }
+        Ok(_) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "No booking rules set for this package"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to delete booking rules: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete booking rules"
+            })))
+        }
+    }
+}
+
+async fn get_package_faqs(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    match package_faqs(pool.get_ref(), path.into_inner()).await {
+        Ok(faqs) => Ok(HttpResponse::Ok().json(faqs)),
+        Err(e) => {
+            log::error!("Failed to fetch package FAQs: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch FAQs"
+            })))
+        }
+    }
+}
+
+/// Replaces a package's FAQs with the given list, in order. Entries with a
+/// `template_id` show that template's current text.
+async fn update_package_faqs(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdatePackageFaqsRequest>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    if req.faqs.iter().any(|faq| faq.template_id.is_none() && (faq.question.is_none() || faq.answer.is_none())) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Each FAQ needs a template_id, or a question and an answer"
+        })));
+    }
+
+    let template_ids: Vec<Uuid> = req.faqs.iter().filter_map(|faq| faq.template_id).collect();
+    match templates_exist(pool.get_ref(), "faq", &template_ids).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "FAQ template not found"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to check FAQ templates: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update FAQs"
+            })));
+        }
+    }
+
+    let result: Result<(), sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM package_faqs WHERE package_id = $1")
+            .bind(package_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (index, faq) in req.faqs.iter().enumerate() {
+            let (question, answer) = match faq.template_id {
+                Some(_) => (None, None),
+                None => (faq.question.as_deref(), faq.answer.as_deref()),
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO package_faqs (id, package_id, position, template_id, question, answer, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, NOW())
+                "#
+            )
+            .bind(Uuid::new_v4())
+            .bind(package_id)
+            .bind(index as i32 + 1)
+            .bind(faq.template_id)
+            .bind(question)
+            .bind(answer)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+    .await;
+
+    let result = match result {
+        Ok(()) => package_faqs(pool.get_ref(), package_id).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(faqs) => Ok(HttpResponse::Ok().json(faqs)),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update package FAQs: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update FAQs"
+            })))
+        }
+    }
+}
+
+async fn get_package_policies(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    match package_policies(pool.get_ref(), path.into_inner()).await {
+        Ok(policies) => Ok(HttpResponse::Ok().json(policies)),
+        Err(e) => {
+            log::error!("Failed to fetch package policies: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch policies"
+            })))
+        }
+    }
+}
+
+/// Replaces a package's policy sections with the given list, in order.
+/// Entries with a `template_id` show that template's current text.
+async fn update_package_policies(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdatePackagePoliciesRequest>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    if req.policies.iter().any(|policy| {
+        policy.template_id.is_none() && (policy.section_type.is_none() || policy.title.is_none() || policy.body.is_none())
+    }) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Each policy section needs a template_id, or a section_type, title and body"
+        })));
+    }
+
+    let template_ids: Vec<Uuid> = req.policies.iter().filter_map(|policy| policy.template_id).collect();
+    match templates_exist(pool.get_ref(), "policy", &template_ids).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Policy template not found"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to check policy templates: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update policies"
+            })));
+        }
+    }
+
+    let result: Result<(), sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM package_policies WHERE package_id = $1")
+            .bind(package_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (index, policy) in req.policies.iter().enumerate() {
+            let (section_type, title, body) = match policy.template_id {
+                Some(_) => (None, None, None),
+                None => (policy.section_type.as_deref(), policy.title.as_deref(), policy.body.as_deref()),
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO package_policies (id, package_id, position, template_id, section_type, title, body, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+                "#
+            )
+            .bind(Uuid::new_v4())
+            .bind(package_id)
+            .bind(index as i32 + 1)
+            .bind(policy.template_id)
+            .bind(section_type)
+            .bind(title)
+            .bind(body)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+    .await;
+
+    let result = match result {
+        Ok(()) => package_policies(pool.get_ref(), package_id).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(policies) => Ok(HttpResponse::Ok().json(policies)),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update package policies: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update policies"
+            })))
+        }
+    }
+}
+
+async fn get_content_templates(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<ContentTemplateQuery>,
+) -> Result<HttpResponse> {
+    let templates = sqlx::query_as::<_, ContentTemplate>(
+        r#"
+        SELECT * FROM content_templates
+        WHERE ($1::TEXT IS NULL OR kind = $1)
+        ORDER BY kind, section_type, title
+        "#
+    )
+    .bind(&query.kind)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match templates {
+        Ok(templates) => Ok(HttpResponse::Ok().json(templates)),
+        Err(e) => {
+            log::error!("Failed to fetch content templates: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch templates"
+            })))
+        }
+    }
+}
+
+async fn create_content_template(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    req: web::Json<CreateContentTemplateRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    if req.kind == "policy" && req.section_type.is_none() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Policy templates need a section_type"
+        })));
+    }
+    let section_type = if req.kind == "policy" { req.section_type.as_deref() } else { None };
+
+    let template = sqlx::query_as::<_, ContentTemplate>(
+        r#"
+        INSERT INTO content_templates (id, kind, section_type, title, body, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, NOW(), NOW())
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(&req.kind)
+    .bind(section_type)
+    .bind(&req.title)
+    .bind(&req.body)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match template {
+        Ok(template) => Ok(HttpResponse::Created().json(template)),
+        Err(e) => {
+            log::error!("Failed to create content template: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create template"
+            })))
+        }
+    }
+}
+
+/// Updates a template's text; every package using it shows the change.
+async fn update_content_template(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdateContentTemplateRequest>,
+) -> Result<HttpResponse> {
+    let template_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let template = sqlx::query_as::<_, ContentTemplate>(
+        r#"
+        UPDATE content_templates
+        SET section_type = CASE WHEN kind = 'policy' THEN COALESCE($2, section_type) END,
+            title = $3,
+            body = $4
+        WHERE id = $1
+        RETURNING *
+        "#
+    )
+    .bind(template_id)
+    .bind(&req.section_type)
+    .bind(&req.title)
+    .bind(&req.body)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match template {
+        Ok(Some(template)) => Ok(HttpResponse::Ok().json(template)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Template not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update content template: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update template"
+            })))
+        }
+    }
+}
+
+async fn delete_content_template(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let result = sqlx::query("DELETE FROM content_templates WHERE id = $1")
+        .bind(path.into_inner())
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) if result.rows_affected() > 0 => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Template deleted"
+            })))
+        }
+        Ok(_) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Template not found"
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Template is used by one or more packages"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to delete content template: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete template"
+            })))
+        }
+    }
+}
+
+async fn get_categories(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+) -> Result<HttpResponse> {
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT * FROM categories WHERE is_active = true ORDER BY name"
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match categories {
+        Ok(categories) => {
+            Ok(HttpResponse::Ok().json(categories))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch categories: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch categories"
+            })))
+        }
+    }
+}
+
+async fn create_category(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    req: web::Json<CreateCategoryRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let slug = match new_slug(pool.get_ref(), SlugEntity::Category, req.slug.as_deref(), &req.name).await {
+        Ok(slug) => slug,
+        Err(response) => return Ok(response),
+    };
+
+    let category_id = Uuid::new_v4();
+    let now = Utc::now();
+
+    let result = sqlx::query_as::<_, Category>(
+        r#"
+        INSERT INTO categories (id, slug, name, description, icon, is_active, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, true, $6, $7)
+        RETURNING *
+        "#
+    )
+    .bind(category_id)
+    .bind(&slug)
+    .bind(&req.name)
+    .bind(&req.description)
+    .bind(&req.icon)
+    .bind(now)
+    .bind(now)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(category) => {
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "message": "Category created successfully",
+                "category_id": category.id,
+                "slug": category.slug
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A category with this name or slug already exists"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to create category: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create category"
+            })))
+        }
+    }
+}
+
+/// Updates only the fields given; an empty `description` or `icon` clears it.
+/// Like package patches, the edited version must be sent.
+async fn patch_category(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    http_req: HttpRequest,
+    path: web::Path<Uuid>,
+    req: web::Json<PatchCategoryRequest>,
+) -> Result<HttpResponse> {
+    let category_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let expected = match expected_version(&http_req, req.version) {
+        Ok(Some(expected)) => Some(expected),
+        Ok(None) if http_req.headers().contains_key("If-Match") => None,
+        Ok(None) => return Ok(precondition_required()),
+        Err(response) => return Ok(response),
+    };
+
+    let result: Result<VersionedWrite<Category>, sqlx::Error> = async {
+        let category = sqlx::query_as::<_, Category>(
+            r#"
+            UPDATE categories
+            SET name = COALESCE($2, name), slug = COALESCE($3, slug),
+                description = CASE WHEN $4::TEXT IS NULL THEN description ELSE NULLIF($4, '') END,
+                icon = CASE WHEN $5::TEXT IS NULL THEN icon ELSE NULLIF($5, '') END,
+                updated_at = NOW()
+            WHERE id = $1 AND ($6::INTEGER IS NULL OR version = $6)
+            RETURNING *
+            "#
+        )
+        .bind(category_id)
+        .bind(&req.name)
+        .bind(&req.slug)
+        .bind(&req.description)
+        .bind(&req.icon)
+        .bind(expected)
+        .fetch_optional(pool.get_ref())
+        .await?;
+
+        if let Some(category) = category {
+            return Ok(VersionedWrite::Updated(category));
+        }
+
+        let current = sqlx::query_scalar::<_, i32>("SELECT version FROM categories WHERE id = $1")
+            .bind(category_id)
+            .fetch_optional(pool.get_ref())
+            .await?;
+        Ok(current.map_or(VersionedWrite::NotFound, VersionedWrite::Stale))
+    }
+    .await;
+
+    match result {
+        Ok(VersionedWrite::Updated(category)) => {
+            Ok(HttpResponse::Ok()
+                .insert_header(("ETag", etag(category.version)))
+                .json(category))
+        }
+        Ok(VersionedWrite::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Category not found"
+            })))
+        }
+        Ok(VersionedWrite::Stale(current)) => Ok(precondition_failed(Some(current))),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A category with this name or slug already exists"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update category: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update category"
+            })))
+        }
+    }
+}
+
+/// The slug for a new record: the one given, or a free one generated from the title.
+async fn new_slug(pool: &PgPool, entity: SlugEntity, slug: Option<&str>, title: &str) -> Result<String, HttpResponse> {
+    if let Some(slug) = slug {
+        return Ok(slug.to_string());
+    }
+
+    match unique_slug(pool, entity, title).await {
+        Ok(Some(slug)) => Ok(slug),
+        Ok(None) => Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Could not generate a slug from the title; please provide one"
+        }))),
+        Err(e) => {
+            log::error!("Failed to generate slug: {}", e);
+            Err(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to generate slug"
+            })))
+        }
+    }
+}
+
+async fn get_package_translations(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    let translations = sqlx::query_as::<_, PackageTranslation>(
+        "SELECT * FROM package_translations WHERE package_id = $1 ORDER BY locale"
+    )
+    .bind(package_id)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match translations {
+        Ok(translations) => Ok(HttpResponse::Ok().json(translations)),
+        Err(e) => {
+            log::error!("Failed to fetch package translations: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch translations"
+            })))
+        }
+    }
+}
+
+async fn upsert_package_translation(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, String)>,
+    req: web::Json<UpsertPackageTranslationRequest>,
+) -> Result<HttpResponse> {
+    let (package_id, locale) = path.into_inner();
+
+    if let Some(response) = invalid_translation_locale(&config, &locale) {
+        return Ok(response);
+    }
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let translation = sqlx::query_as::<_, PackageTranslation>(
+        r#"
+        INSERT INTO package_translations (package_id, locale, title, description, highlights, itinerary, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW())
+        ON CONFLICT (package_id, locale) DO UPDATE
+        SET title = EXCLUDED.title,
+            description = EXCLUDED.description,
+            highlights = EXCLUDED.highlights,
+            itinerary = EXCLUDED.itinerary
+        RETURNING *
+        "#
+    )
+    .bind(package_id)
+    .bind(&locale)
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(&req.highlights)
+    .bind(&req.itinerary)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match translation {
+        Ok(translation) => Ok(HttpResponse::Ok().json(translation)),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to save package translation: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to save translation"
+            })))
+        }
+    }
+}
+
+async fn delete_package_translation(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse> {
+    let (package_id, locale) = path.into_inner();
+
+    let result = sqlx::query("DELETE FROM package_translations WHERE package_id = $1 AND locale = $2")
+        .bind(package_id)
+        .bind(&locale)
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) if result.rows_affected() > 0 => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Translation removed"
+            })))
+        }
+        Ok(_) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Translation not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to delete package translation: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete translation"
+            })))
+        }
+    }
+}
+
+async fn get_category_translations(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let category_id = path.into_inner();
+
+    let translations = sqlx::query_as::<_, CategoryTranslation>(
+        "SELECT * FROM category_translations WHERE category_id = $1 ORDER BY locale"
+    )
+    .bind(category_id)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match translations {
+        Ok(translations) => Ok(HttpResponse::Ok().json(translations)),
+        Err(e) => {
+            log::error!("Failed to fetch category translations: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch translations"
+            })))
+        }
+    }
+}
+
+async fn upsert_category_translation(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, String)>,
+    req: web::Json<UpsertCategoryTranslationRequest>,
+) -> Result<HttpResponse> {
+    let (category_id, locale) = path.into_inner();
+
+    if let Some(response) = invalid_translation_locale(&config, &locale) {
+        return Ok(response);
+    }
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let translation = sqlx::query_as::<_, CategoryTranslation>(
+        r#"
+        INSERT INTO category_translations (category_id, locale, name, description, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, NOW(), NOW())
+        ON CONFLICT (category_id, locale) DO UPDATE
+        SET name = EXCLUDED.name,
+            description = EXCLUDED.description
+        RETURNING *
+        "#
+    )
+    .bind(category_id)
+    .bind(&locale)
+    .bind(&req.name)
+    .bind(&req.description)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match translation {
+        Ok(translation) => Ok(HttpResponse::Ok().json(translation)),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Category not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to save category translation: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to save translation"
+            })))
+        }
+    }
+}
+
+async fn delete_category_translation(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse> {
+    let (category_id, locale) = path.into_inner();
+
+    let result = sqlx::query("DELETE FROM category_translations WHERE category_id = $1 AND locale = $2")
+        .bind(category_id)
+        .bind(&locale)
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) if result.rows_affected() > 0 => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Translation removed"
+            })))
+        }
+        Ok(_) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Translation not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to delete category translation: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete translation"
+            })))
+        }
+    }
+}
+
+/// Unarchived packages without a translation into every supported locale, or
+/// into `?locale=` when given.
+async fn get_missing_translations(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    _admin: AdminOnly,
+    query: web::Query<MissingTranslationsQuery>,
+) -> Result<HttpResponse> {
+    let locales: Vec<String> = match &query.locale {
+        Some(locale) => {
+            if let Some(response) = invalid_translation_locale(&config, locale) {
+                return Ok(response);
+            }
+            vec![locale.clone()]
+        }
+        None => config
+            .supported_locales
+            .iter()
+            .filter(|locale| **locale != config.default_locale)
+            .cloned()
+            .collect(),
+    };
+
+    let packages = sqlx::query_as::<_, MissingTranslations>(
+        r#"
+        SELECT p.id, p.slug, p.title, ARRAY_AGG(l.locale ORDER BY l.locale) as missing_locales
+        FROM packages p
+        CROSS JOIN UNNEST($1::TEXT[]) AS l(locale)
+        WHERE p.status <> 'archived'
+          AND NOT EXISTS (
+              SELECT 1 FROM package_translations pt
+              WHERE pt.package_id = p.id AND pt.locale = l.locale
+          )
+        GROUP BY p.id
+        ORDER BY p.title
+        "#
+    )
+    .bind(&locales)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match packages {
+        Ok(packages) => Ok(HttpResponse::Ok().json(packages)),
+        Err(e) => {
+            log::error!("Failed to fetch missing translations: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch missing translations"
+            })))
+        }
+    }
+}
+
+/// Translations are kept for the supported locales other than the default,
+/// whose text lives on the record itself.
+fn invalid_translation_locale(config: &Config, locale: &str) -> Option<HttpResponse> {
+    let error = if locale == config.default_locale {
+        "The default locale is edited on the record itself"
+    } else if !config.supported_locales.iter().any(|supported| supported == locale) {
+        "Unsupported locale"
+    } else {
+        return None;
+    };
+
+    Some(HttpResponse::BadRequest().json(serde_json::json!({
+        "error": error,
+        "supported_locales": config.supported_locales
+    })))
+}
+
+async fn get_tax_rules(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+) -> Result<HttpResponse> {
+    let rules = sqlx::query_as::<_, TaxRule>(
+        "SELECT * FROM tax_rules ORDER BY created_at"
+    )
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match rules {
+        Ok(rules) => {
+            Ok(HttpResponse::Ok().json(rules))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch tax rules: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch tax rules"
+            })))
+        }
+    }
+}
+
+async fn create_tax_rule(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    req: web::Json<CreateTaxRuleRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let result = sqlx::query_as::<_, TaxRule>(
+        r#"
+        INSERT INTO tax_rules (id, name, category_id, region, rate_basis_points, mode, is_active, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, NOW(), NOW())
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(&req.name)
+    .bind(req.category_id)
+    .bind(&req.region)
+    .bind(req.rate_basis_points)
+    .bind(&req.mode)
+    .bind(req.is_active.unwrap_or(true))
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(rule) => {
+            Ok(HttpResponse::Created().json(rule))
+        }
+        Err(e) => {
+            log::error!("Failed to create tax rule: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create tax rule"
+            })))
+        }
+    }
+}
+
+async fn update_tax_rule(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<CreateTaxRuleRequest>,
+) -> Result<HttpResponse> {
+    let rule_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let result = sqlx::query_as::<_, TaxRule>(
+        r#"
+        UPDATE tax_rules
+        SET name = $2, category_id = $3, region = $4, rate_basis_points = $5, mode = $6,
+            is_active = $7, updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#
+    )
+    .bind(rule_id)
+    .bind(&req.name)
+    .bind(req.category_id)
+    .bind(&req.region)
+    .bind(req.rate_basis_points)
+    .bind(&req.mode)
+    .bind(req.is_active.unwrap_or(true))
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(Some(rule)) => {
+            Ok(HttpResponse::Ok().json(rule))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Tax rule not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update tax rule: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update tax rule"
+            })))
+        }
+    }
+}
+
+async fn delete_tax_rule(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let rule_id = path.into_inner();
+
+    // Rules are deactivated rather than deleted so existing tax lines keep their reference
+    let result = sqlx::query(
+        "UPDATE tax_rules SET is_active = false, updated_at = NOW() WHERE id = $1"
+    )
+    .bind(rule_id)
+    .execute(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(result) => {
+            if result.rows_affected() > 0 {
+                Ok(HttpResponse::Ok().json(serde_json::json!({
+                    "message": "Tax rule deleted successfully"
+                })))
+            } else {
+                Ok(HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Tax rule not found"
+                })))
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to delete tax rule: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete tax rule"
+            })))
+        }
+    }
+}
+
+async fn get_all_bookings(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<BookingSearchQuery>,
+) -> Result<HttpResponse> {
+    if let Err(error) = validate_search(&query) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })));
+    }
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let total = count_bookings(&query)
+        .build_query_scalar::<i64>()
+        .fetch_one(pool.get_ref())
+        .await;
+
+    let mut builder = select_bookings(&query);
+    builder.push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+    let bookings = builder
+        .build_query_as::<AdminBookingResponse>()
+        .fetch_all(pool.get_ref())
+        .await;
+
+    match (total, bookings) {
+        (Ok(total), Ok(bookings)) => {
+            Ok(HttpResponse::Ok()
+                .insert_header(("X-Total-Count", total.to_string()))
+                .json(bookings))
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            log::error!("Failed to fetch bookings: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch bookings"
+            })))
+        }
+    }
+}
+
+async fn export_bookings(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<BookingSearchQuery>,
+    export: web::Query<ExportQuery>,
+) -> Result<HttpResponse> {
+    if let Err(error) = validate_search(&query) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": error
+        })));
+    }
+
+    let Some(format) = ExportFormat::parse(export.format.as_deref()) else {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "format must be csv or xlsx"
+        })));
+    };
+
+    let file_name = format!("bookings-{}.{}", Utc::now().format("%Y%m%d-%H%M%S"), format.extension());
+    let body = stream_bookings_export(pool.get_ref().clone(), query.into_inner(), format);
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", file_name)))
+        .streaming(body))
+}
+
+async fn get_booking(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let query = format!(
+        r#"
+        SELECT b.id, b.reference, b.booking_date, b.number_of_people, b.base_amount, b.tax_amount,
+               b.total_amount, b.amount_paid, b.status, b.special_requests, b.traveller_names, b.hold_expires_at,
+               b.created_at, b.version, COALESCE(p.title, r.title) as package_title, u.first_name, u.last_name, u.email,
+               {} as unread_messages
+        FROM bookings b
+        LEFT JOIN packages p ON b.package_id = p.id
+        LEFT JOIN rfqs r ON b.rfq_id = r.id
+        JOIN users u ON b.user_id = u.id
+        WHERE b.id = $1 OR b.reference = $2
+        "#,
+        STAFF_UNREAD_SQL
+    );
+    let booking = sqlx::query(&query)
+    .bind(booking_id)
+    .bind(&reference)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match booking {
+        Ok(Some(b)) => {
+            use sqlx::Row;
+            let version = b.get::<i32, _>("version");
+            Ok(HttpResponse::Ok()
+                .insert_header(("ETag", etag(version)))
+                .json(serde_json::json!({
+                    "id": b.get::<Uuid, _>("id"),
+                    "reference": b.get::<String, _>("reference"),
+                    "package_title": b.get::<String, _>("package_title"),
+                    "user_name": format!("{} {}",
+                        b.get::<String, _>("first_name"),
+                        b.get::<String, _>("last_name")
+                    ),
+                    "user_email": b.get::<String, _>("email"),
+                    "booking_date": b.get::<chrono::NaiveDate, _>("booking_date"),
+                    "number_of_people": b.get::<i32, _>("number_of_people"),
+                    "traveller_names": b.get::<Vec<String>, _>("traveller_names"),
+                    "base_amount": b.get::<i32, _>("base_amount"),
+                    "tax_amount": b.get::<i32, _>("tax_amount"),
+                    "total_amount": b.get::<i32, _>("total_amount"),
+                    "amount_paid": b.get::<i32, _>("amount_paid"),
+                    "status": b.get::<String, _>("status"),
+                    "special_requests": b.get::<Option<String>, _>("special_requests"),
+                    "hold_expires_at": b.get::<Option<chrono::DateTime<chrono::Utc>>, _>("hold_expires_at"),
+                    "unread_messages": b.get::<i64, _>("unread_messages"),
+                    "created_at": b.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+                    "version": version
+                })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking"
+            })))
+        }
+    }
+}
+
+async fn update_booking_status(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    _admin: AdminOnly,
+    http_req: HttpRequest,
+    path: web::Path<String>,
+    req: web::Json<UpdateBookingStatusRequest>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let expected = match expected_version(&http_req, req.version) {
+        Ok(expected) => expected,
+        Err(response) => return Ok(response),
+    };
+
+    if !BOOKING_STATUSES.contains(&req.status.as_str()) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Status must be one of: {}", BOOKING_STATUSES.join(", "))
+        })));
+    }
+
+    let result: Result<BookingStatusChange, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let booking = sqlx::query_as::<_, LockedBooking>(
+            r#"
+            SELECT id, package_id, variant_id, booking_date, number_of_people, status, version
+            FROM bookings
+            WHERE id = $1 OR reference = $2
+            FOR UPDATE
+            "#
+        )
+        .bind(booking_id)
+        .bind(&reference)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(booking) = booking else {
+            return Ok(BookingStatusChange::NotFound);
+        };
+        if expected.is_some_and(|expected| expected != booking.version) {
+            return Ok(BookingStatusChange::Stale(booking.version));
+        }
+        if !booking_transition_allowed(&booking.status, &req.status) {
+            return Ok(BookingStatusChange::NotAllowed(booking.status));
+        }
+
+        // A reinstated booking takes its seats back, so capacity is checked the way a new booking's is
+        if let (Some(package_id), false, true) = (booking.package_id, holds_seats(&booking.status), holds_seats(&req.status)) {
+            sqlx::query("SELECT id FROM packages WHERE id = $1 FOR UPDATE")
+                .bind(package_id)
+                .execute(&mut *tx)
+                .await?;
+
+            let mut available = seats_available(&mut *tx, package_id, booking.booking_date).await?;
+            if let Some(variant_id) = booking.variant_id {
+                available = available.min(variant_seats_available(&mut *tx, variant_id, booking.booking_date).await?);
+            }
+            if available < booking.number_of_people as i64 {
+                return Ok(BookingStatusChange::SoldOut(available.max(0)));
+            }
+        }
+
+        // Moving into pending starts a fresh hold so the sweeper releases the seats if it goes unpaid
+        let hold_expires_at = (req.status == "pending" && booking.status != "pending")
+            .then(|| Utc::now() + Duration::minutes(config.booking_hold_minutes));
+
+        let updated = sqlx::query_as::<_, UpdatedBooking>(
+            r#"
+            UPDATE bookings
+            SET status = $2,
+                hold_expires_at = CASE WHEN $2 <> 'pending' THEN NULL ELSE COALESCE($3, hold_expires_at) END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, package_id, booking_date, version
+            "#
+        )
+        .bind(booking.id)
+        .bind(&req.status)
+        .bind(hold_expires_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(BookingStatusChange::Updated(updated))
+    }
+    .await;
+
+    match result {
+        Ok(BookingStatusChange::Updated(UpdatedBooking { id: booking_id, package_id, booking_date, version })) => {
+            // Settle any waitlist offer this booking came from and hand freed seats to the queue
+            let offer_status = match req.status.as_str() {
+                "confirmed" => Some("converted"),
+                "cancelled" => Some("declined"),
+                "expired" => Some("expired"),
+                _ => None,
+            };
+            if let Some(offer_status) = offer_status {
+                if let Err(e) = close_offers(pool.get_ref(), &[booking_id], offer_status).await {
+                    log::error!("Failed to close waitlist offer for booking {}: {}", booking_id, e);
+                }
+            }
+            if let (Some(package_id), "cancelled" | "expired") = (package_id, req.status.as_str()) {
+                spawn_offer_freed_seats(pool.get_ref().clone(), config.waitlist_offer_hours, package_id, booking_date);
+            }
+
+            Ok(HttpResponse::Ok()
+                .insert_header(("ETag", etag(version)))
+                .json(serde_json::json!({
+                    "message": "Booking status updated successfully",
+                    "version": version
+                })))
+        }
+        Ok(BookingStatusChange::Stale(current)) => Ok(precondition_failed(Some(current))),
+        Ok(BookingStatusChange::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Ok(BookingStatusChange::NotAllowed(current)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("A {} booking cannot be moved to {}", current, req.status)
+            })))
+        }
+        Ok(BookingStatusChange::SoldOut(seats_available)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Not enough seats left on this departure to reinstate the booking",
+                "seats_available": seats_available
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update booking status: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update booking status"
+            })))
+        }
+    }
+}
+
+async fn get_booking_payments(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let summary = match find_booking_id(pool.get_ref(), booking_id, reference.as_deref()).await {
+        Ok(Some(booking_id)) => payment_summary(pool.get_ref(), booking_id).await,
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match summary {
+        Ok(Some(summary)) => Ok(HttpResponse::Ok().json(summary)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking payments: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking payments"
+            })))
+        }
+    }
+}
+
+async fn record_booking_payment(
+    pool: web::Data<PgPool>,
+    admin: AdminOnly,
+    path: web::Path<String>,
+    req: web::Json<RecordPaymentRequest>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let outcome = match find_booking_id(pool.get_ref(), booking_id, reference.as_deref()).await {
+        Ok(Some(booking_id)) => record_payment(pool.get_ref(), booking_id, &req, admin.user_id).await,
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match outcome {
+        Ok(Some(PaymentOutcome::Recorded { payment, status })) => {
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "message": "Payment recorded successfully",
+                "payment": payment,
+                "booking_status": status
+            })))
+        }
+        Ok(Some(PaymentOutcome::Overpayment { outstanding })) => {
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Payment exceeds the outstanding amount",
+                "outstanding": outstanding
+            })))
+        }
+        Ok(Some(PaymentOutcome::NotPayable { status })) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": format!("Cannot record a payment for a {} booking", status)
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to record payment: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to record payment"
+            })))
+        }
+    }
+}
+
+async fn get_unanswered_bookings(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+) -> Result<HttpResponse> {
+    // Bookings whose latest customer-visible message came from the customer, oldest first
+    let bookings = sqlx::query_as::<_, UnansweredBookingResponse>(&format!(
+        r#"
+        SELECT b.id as booking_id, b.reference, COALESCE(p.title, r.title) as package_title, u.email as user_email,
+               m.body as last_message, m.created_at as last_message_at,
+               {} as unread_messages
+        FROM bookings b
+        LEFT JOIN packages p ON b.package_id = p.id
+        LEFT JOIN rfqs r ON b.rfq_id = r.id
+        JOIN users u ON b.user_id = u.id
+        JOIN LATERAL (
+            SELECT author_role, body, created_at FROM booking_messages
+            WHERE booking_id = b.id AND NOT is_internal
+            ORDER BY created_at DESC
+            LIMIT 1
+        ) m ON true
+        WHERE m.author_role = 'customer'
+        ORDER BY m.created_at
+        "#,
+        STAFF_UNREAD_SQL
+    ))
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match bookings {
+        Ok(bookings) => {
+            Ok(HttpResponse::Ok().json(bookings))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch unanswered bookings: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch unanswered bookings"
+            })))
+        }
+    }
+}
+
+async fn get_booking_messages(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let messages = match find_booking_id(pool.get_ref(), booking_id, reference.as_deref()).await {
+        Ok(Some(booking_id)) => read_thread(pool.get_ref(), booking_id, ThreadSide::Staff).await.map(Some),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match messages {
+        Ok(Some(messages)) => Ok(HttpResponse::Ok().json(messages)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking messages: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking messages"
+            })))
+        }
+    }
+}
+
+async fn post_booking_message(
+    pool: web::Data<PgPool>,
+    admin: AdminOnly,
+    path: web::Path<String>,
+    req: web::Json<CreateMessageRequest>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let message = match find_booking_id(pool.get_ref(), booking_id, reference.as_deref()).await {
+        Ok(Some(booking_id)) => post_message(pool.get_ref(), booking_id, admin.user_id, ThreadSide::Staff, &req).await.map(Some),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match message {
+        Ok(Some(message)) => Ok(HttpResponse::Created().json(message)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to post booking message: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to post message"
+            })))
+        }
+    }
+}
+
+async fn get_waitlist(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<WaitlistQuery>,
+) -> Result<HttpResponse> {
+    let entries = sqlx::query_as::<_, WaitlistEntryResponse>(
+        r#"
+        SELECT w.id, w.package_id, p.title as package_title, w.variant_id, v.name as variant_name, u.email as user_email,
+               w.booking_date, w.party_size, w.status, w.offer_booking_id, w.offer_expires_at,
+               w.created_at,
+               CASE WHEN w.status = 'waiting' THEN
+                   ROW_NUMBER() OVER (PARTITION BY w.package_id, w.booking_date, w.status ORDER BY w.created_at)
+               END as position
+        FROM waitlist_entries w
+        JOIN packages p ON w.package_id = p.id
+        LEFT JOIN package_variants v ON w.variant_id = v.id
+        JOIN users u ON w.user_id = u.id
+        WHERE ($1::UUID IS NULL OR w.package_id = $1)
+          AND ($2::DATE IS NULL OR w.booking_date = $2)
+          AND ($3::VARCHAR IS NULL OR w.status = $3)
+        ORDER BY w.booking_date, w.package_id, w.created_at
+        "#
+    )
+    .bind(query.package_id)
+    .bind(query.booking_date)
+    .bind(&query.status)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match entries {
+        Ok(entries) => {
+            Ok(HttpResponse::Ok().json(entries))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch waitlist: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch waitlist"
+            })))
+        }
+    }
+}
+
+async fn find_booking_id(
+    pool: &PgPool,
+    booking_id: Option<Uuid>,
+    reference: Option<&str>,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar("SELECT id FROM bookings WHERE id = $1 OR reference = $2")
+        .bind(booking_id)
+        .bind(reference)
+        .fetch_optional(pool)
+        .await
+}
+
+const ENQUIRY_SELECT: &str = r#"
+    SELECT e.id, e.name, e.email, e.phone, e.subject, e.message, e.package_id, p.title as package_title,
+           e.travel_date, e.number_of_people, e.status, e.assigned_to, s.email as assigned_to_email,
+           e.booking_id, e.created_at, e.updated_at
+    FROM enquiries e
+    LEFT JOIN packages p ON e.package_id = p.id
+    LEFT JOIN users s ON e.assigned_to = s.id
+"#;
+
+async fn get_enquiries(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<EnquiryQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let enquiries = sqlx::query_as::<_, EnquiryResponse>(&format!(
+        r#"
+        {}
+        WHERE ($1::TEXT IS NULL OR e.status = $1)
+          AND ($2::UUID IS NULL OR e.assigned_to = $2)
+        ORDER BY e.created_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+        ENQUIRY_SELECT
+    ))
+    .bind(&query.status)
+    .bind(query.assigned_to)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match enquiries {
+        Ok(enquiries) => {
+            Ok(HttpResponse::Ok().json(enquiries))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch enquiries: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch enquiries"
+            })))
+        }
+    }
+}
+
+async fn get_enquiry(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let enquiry_id = path.into_inner();
+
+    match fetch_enquiry_response(pool.get_ref(), enquiry_id).await {
+        Ok(Some(enquiry)) => Ok(HttpResponse::Ok().json(enquiry)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Enquiry not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch enquiry: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch enquiry"
+            })))
+        }
+    }
+}
+
+async fn assign_enquiry(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<AssignEnquiryRequest>,
+) -> Result<HttpResponse> {
+    let enquiry_id = path.into_inner();
+
+    if let Some(user_id) = req.user_id {
+        let is_staff = sqlx::query_scalar::<_, bool>(
+            "SELECT COALESCE(is_admin, false) FROM users WHERE id = $1 AND is_active = true"
+        )
+        .bind(user_id)
+        .fetch_optional(pool.get_ref())
+        .await;
+
+        match is_staff {
+            Ok(Some(true)) => {}
+            Ok(_) => {
+                return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "Enquiries can only be assigned to active staff members"
+                })));
+            }
+            Err(e) => {
+                log::error!("Failed to fetch staff member: {}", e);
+                return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to assign enquiry"
+                })));
+            }
+        }
+    }
+
+    let result = sqlx::query("UPDATE enquiries SET assigned_to = $2 WHERE id = $1")
+        .bind(enquiry_id)
+        .bind(req.user_id)
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) if result.rows_affected() > 0 => enquiry_updated(pool.get_ref(), enquiry_id).await,
+        Ok(_) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Enquiry not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to assign enquiry: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to assign enquiry"
+            })))
+        }
+    }
+}
+
+async fn update_enquiry_status(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdateEnquiryStatusRequest>,
+) -> Result<HttpResponse> {
+    let enquiry_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let result = sqlx::query_scalar::<_, Option<Uuid>>(
+        r#"
+        UPDATE enquiries SET status = $2
+        WHERE id = $1 AND (booking_id IS NULL OR $2 = 'won')
+        RETURNING booking_id
+        "#
+    )
+    .bind(enquiry_id)
+    .bind(&req.status)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(Some(_)) => enquiry_updated(pool.get_ref(), enquiry_id).await,
+        Ok(None) => {
+            // Either missing, or converted to a booking and so locked as won
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Enquiry not found or already converted to a booking"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update enquiry status: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update enquiry status"
+            })))
+        }
+    }
+}
+
+/// Creates a pending booking from a won enquiry, held like any customer booking.
+///
+/// Package, date and party size come from the enquiry unless overridden, and
+/// the booking belongs to the customer account with the enquiry's email
+/// unless `user_id` is given.
+async fn convert_enquiry(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<ConvertEnquiryRequest>,
+) -> Result<HttpResponse> {
+    let enquiry_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let enquiry = match sqlx::query_as::<_, Enquiry>("SELECT * FROM enquiries WHERE id = $1")
+        .bind(enquiry_id)
+        .fetch_optional(pool.get_ref())
+        .await
+    {
+        Ok(Some(enquiry)) => enquiry,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Enquiry not found"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to fetch enquiry: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to convert enquiry"
+            })));
+        }
+    };
+
+    if let Some(booking_id) = enquiry.booking_id {
+        return Ok(HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Enquiry has already been converted",
+            "booking_id": booking_id
+        })));
+    }
+    if enquiry.status != "won" {
+        return Ok(HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Only won enquiries can be converted to a booking"
+        })));
+    }
+
+    let (Some(package_id), Some(booking_date), Some(number_of_people)) = (
+        req.package_id.or(enquiry.package_id),
+        req.booking_date.or(enquiry.travel_date),
+        req.number_of_people.or(enquiry.number_of_people),
+    ) else {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "package_id, booking_date and number_of_people are required when the enquiry does not include them"
+        })));
+    };
+
+    let user_id = match req.user_id {
+        Some(user_id) => Ok(Some(user_id)),
+        None => {
+            sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE LOWER(email) = LOWER($1)")
+                .bind(&enquiry.email)
+                .fetch_optional(pool.get_ref())
+                .await
+        }
+    };
+    let user_id = match user_id {
+        Ok(Some(user_id)) => user_id,
+        Ok(None) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "No customer account uses this enquiry's email; ask the customer to register or pass user_id"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to fetch customer account: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to convert enquiry"
+            })));
+        }
+    };
+
+    match package_booking_rules(pool.get_ref(), package_id).await {
+        Ok(rules) => {
+            if let Err(violation) = check_booking_date(rules.as_ref(), booking_date, Utc::now().date_naive()) {
+                return Ok(violation.to_response());
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package booking rules: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to convert enquiry"
+            })));
+        }
+    }
+
+    match variant_selection_error(pool.get_ref(), package_id, req.variant_id).await {
+        Ok(Some(error)) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            })));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            log::error!("Failed to fetch package variants: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to convert enquiry"
+            })));
+        }
+    }
+
+    let quote = match quote_package(pool.get_ref(), package_id, req.variant_id, number_of_people).await {
+        Ok(PackageQuote::Quoted(quote)) => quote,
+        Ok(PackageQuote::NotFound) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })));
+        }
+        Ok(PackageQuote::TooLarge) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "The total for this party is too large to book online"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to convert enquiry"
+            })));
+        }
+    };
+
+    let booking_req = CreateBookingRequest {
+        package_id,
+        variant_id: req.variant_id,
+        booking_date,
+        number_of_people,
+        special_requests: Some(enquiry.message.clone()),
+        traveller_names: None,
+    };
+    let hold_expires_at = Utc::now() + Duration::minutes(config.booking_hold_minutes);
+
+    // The enquiry row lock makes concurrent conversions queue, and the booking
+    // only commits together with the link to it
+    let result: Result<EnquiryConversion, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let enquiry = sqlx::query_as::<_, (Option<Uuid>, String)>(
+            "SELECT booking_id, status FROM enquiries WHERE id = $1 FOR UPDATE"
+        )
+        .bind(enquiry_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some((linked_booking_id, status)) = enquiry else {
+            return Ok(EnquiryConversion::NotFound);
+        };
+
+        if let Some(booking_id) = linked_booking_id {
+            return Ok(EnquiryConversion::AlreadyConverted(booking_id));
+        }
+        if status != "won" {
+            return Ok(EnquiryConversion::NotWon);
+        }
+
+        let booking = match insert_booking_in(&mut tx, user_id, &booking_req, &quote, hold_expires_at).await? {
+            BookingInsert::Created(booking) => booking,
+            BookingInsert::SoldOut { seats_available } => {
+                return Ok(EnquiryConversion::SoldOut(seats_available));
+            }
+        };
+
+        sqlx::query("UPDATE enquiries SET booking_id = $2 WHERE id = $1")
+            .bind(enquiry_id)
+            .bind(booking.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(EnquiryConversion::Converted(booking))
+    }
+    .await;
+
+    match result {
+        Ok(EnquiryConversion::Converted(booking)) => {
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "message": "Enquiry converted to booking",
+                "booking_id": booking.id,
+                "reference": booking.reference,
+                "total_amount": booking.total_amount,
+                "status": booking.status,
+                "hold_expires_at": booking.hold_expires_at
+            })))
+        }
+        Ok(EnquiryConversion::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Enquiry not found"
+            })))
+        }
+        Ok(EnquiryConversion::AlreadyConverted(booking_id)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Enquiry has already been converted",
+                "booking_id": booking_id
+            })))
+        }
+        Ok(EnquiryConversion::NotWon) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Only won enquiries can be converted to a booking"
+            })))
+        }
+        Ok(EnquiryConversion::SoldOut(seats_available)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Not enough seats available on this departure",
+                "seats_available": seats_available
+            })))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Customer account not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to convert enquiry {}: {}", enquiry_id, e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to convert enquiry"
+            })))
+        }
+    }
+}
+
+async fn fetch_enquiry_response(pool: &PgPool, enquiry_id: Uuid) -> Result<Option<EnquiryResponse>, sqlx::Error> {
+    sqlx::query_as::<_, EnquiryResponse>(&format!("{} WHERE e.id = $1", ENQUIRY_SELECT))
+        .bind(enquiry_id)
+        .fetch_optional(pool)
+        .await
+}
+
+async fn enquiry_updated(pool: &PgPool, enquiry_id: Uuid) -> Result<HttpResponse> {
+    match fetch_enquiry_response(pool, enquiry_id).await {
+        Ok(Some(enquiry)) => Ok(HttpResponse::Ok().json(enquiry)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Enquiry not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch enquiry: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch enquiry"
+            })))
+        }
+    }
+}
+
+async fn get_all_posts(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<PostAdminQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let tag = query.tag.as_deref().map(|tag| tag.trim().to_lowercase());
+
+    let posts = sqlx::query_as::<_, Post>(
+        r#"
+        SELECT * FROM posts
+        WHERE ($1::TEXT IS NULL OR status = $1)
+          AND ($2::TEXT IS NULL OR $2 = ANY(tags))
+        ORDER BY COALESCE(published_at, created_at) DESC
+        LIMIT $3 OFFSET $4
+        "#
+    )
+    .bind(&query.status)
+    .bind(&tag)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match posts {
+        Ok(posts) => {
+            Ok(HttpResponse::Ok().json(posts))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch posts: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch posts"
+            })))
+        }
+    }
+}
+
+async fn get_post(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let post_id = path.into_inner();
+
+    let post = sqlx::query_as::<_, Post>("SELECT * FROM posts WHERE id = $1")
+        .bind(post_id)
+        .fetch_optional(pool.get_ref())
+        .await;
+
+    match post {
+        Ok(Some(post)) => Ok(HttpResponse::Ok().json(post)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Post not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch post: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch post"
+            })))
+        }
+    }
+}
+
+async fn create_post(
+    pool: web::Data<PgPool>,
+    admin: AdminOnly,
+    req: web::Json<CreatePostRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let prepared = match prepare_post(&req) {
+        Ok(prepared) => prepared,
+        Err(error) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            })));
+        }
+    };
+
+    let result = sqlx::query_as::<_, Post>(
+        r#"
+        INSERT INTO posts (id, slug, title, excerpt, body_markdown, body_html, cover_image_url, tags, author_id, status, published_at, meta_title, meta_description, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, NOW(), NOW())
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(&prepared.slug)
+    .bind(&req.title)
+    .bind(&req.excerpt)
+    .bind(&req.body_markdown)
+    .bind(&prepared.body_html)
+    .bind(&req.cover_image_url)
+    .bind(&prepared.tags)
+    .bind(req.author_id.unwrap_or(admin.user_id))
+    .bind(&req.status)
+    .bind(prepared.published_at)
+    .bind(&req.meta_title)
+    .bind(&req.meta_description)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(post) => {
+            Ok(HttpResponse::Created().json(post))
+        }
+        Err(e) => Ok(post_write_error(e, "create")),
+    }
+}
+
+async fn update_post(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<CreatePostRequest>,
+) -> Result<HttpResponse> {
+    let post_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let prepared = match prepare_post(&req) {
+        Ok(prepared) => prepared,
+        Err(error) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            })));
+        }
+    };
+
+    // Republishing without a new date keeps the original publish time
+    let result = sqlx::query_as::<_, Post>(
+        r#"
+        UPDATE posts
+        SET slug = $2, title = $3, excerpt = $4, body_markdown = $5, body_html = $6, cover_image_url = $7,
+            tags = $8, author_id = COALESCE($9, author_id), status = $10,
+            published_at = CASE
+                WHEN $10 = 'published' AND $14 AND published_at IS NOT NULL THEN published_at
+                ELSE $11
+            END,
+            meta_title = $12, meta_description = $13
+        WHERE id = $1
+        RETURNING *
+        "#
+    )
+    .bind(post_id)
+    .bind(&prepared.slug)
+    .bind(&req.title)
+    .bind(&req.excerpt)
+    .bind(&req.body_markdown)
+    .bind(&prepared.body_html)
+    .bind(&req.cover_image_url)
+    .bind(&prepared.tags)
+    .bind(req.author_id)
+    .bind(&req.status)
+    .bind(prepared.published_at)
+    .bind(&req.meta_title)
+    .bind(&req.meta_description)
+    .bind(req.published_at.is_none())
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(Some(post)) => Ok(HttpResponse::Ok().json(post)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Post not found"
+            })))
+        }
+        Err(e) => Ok(post_write_error(e, "update")),
+    }
+}
+
+async fn delete_post(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let post_id = path.into_inner();
+
+    let result = sqlx::query("DELETE FROM posts WHERE id = $1")
+        .bind(post_id)
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) => {
+            if result.rows_affected() > 0 {
+                Ok(HttpResponse::Ok().json(serde_json::json!({
+                    "message": "Post deleted successfully"
+                })))
+            } else {
+                Ok(HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Post not found"
+                })))
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to delete post: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to delete post"
+            })))
+        }
+    }
+}
+
+fn post_write_error(error: sqlx::Error, action: &str) -> HttpResponse {
+    match error {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            HttpResponse::Conflict().json(serde_json::json!({
+                "error": "A post with this slug already exists"
+            }))
+        }
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Author not found"
+            }))
+        }
+        e => {
+            log::error!("Failed to {} post: {}", action, e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to {} post", action)
+            }))
+        }
+    }
+}
+
+async fn get_rfqs(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<RfqQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let rfqs = sqlx::query_as::<_, Rfq>(
+        r#"
+        SELECT * FROM rfqs
+        WHERE ($1::TEXT IS NULL OR status = $1)
+        ORDER BY created_at DESC
+        LIMIT $2 OFFSET $3
+        "#
+    )
+    .bind(&query.status)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match rfqs {
+        Ok(rfqs) => {
+            Ok(HttpResponse::Ok().json(rfqs))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch RFQs: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch RFQs"
+            })))
+        }
+    }
+}
+
+async fn get_rfq(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let rfq_id = path.into_inner();
+
+    let rfq = sqlx::query_as::<_, Rfq>("SELECT * FROM rfqs WHERE id = $1")
+        .bind(rfq_id)
+        .fetch_optional(pool.get_ref())
+        .await;
+
+    let detail = match rfq {
+        Ok(Some(rfq)) => fetch_rfq_quotes(pool.get_ref(), rfq_id)
+            .await
+            .map(|quotes| Some(RfqDetailResponse { rfq, quotes })),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match detail {
+        Ok(Some(detail)) => Ok(HttpResponse::Ok().json(detail)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "RFQ not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch RFQ: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch RFQ"
+            })))
+        }
+    }
+}
+
+/// Every quote version for an RFQ, newest first, with cost and margin per line.
+async fn fetch_rfq_quotes(pool: &PgPool, rfq_id: Uuid) -> Result<Vec<RfqQuoteResponse>, sqlx::Error> {
+    let quotes = sqlx::query_as::<_, RfqQuote>(
+        "SELECT * FROM rfq_quotes WHERE rfq_id = $1 ORDER BY version DESC"
+    )
+    .bind(rfq_id)
+    .fetch_all(pool)
+    .await?;
+
+    let quote_ids: Vec<Uuid> = quotes.iter().map(|quote| quote.id).collect();
+    let items = sqlx::query_as::<_, RfqQuoteItem>(
+        "SELECT * FROM rfq_quote_items WHERE quote_id = ANY($1) ORDER BY position"
+    )
+    .bind(&quote_ids)
+    .fetch_all(pool)
+    .await?;
+
+    let mut responses: Vec<RfqQuoteResponse> = quotes
+        .into_iter()
+        .map(|quote| RfqQuoteResponse { quote, items: Vec::new() })
+        .collect();
+    for item in items {
+        if let Some(response) = responses.iter_mut().find(|response| response.quote.id == item.quote_id) {
+            response.items.push(item);
+        }
+    }
+
+    Ok(responses)
+}
+
+/// Adds a new draft version of the quote. Earlier versions stay visible to the
+/// customer until this one is sent.
+async fn create_rfq_quote(
+    pool: web::Data<PgPool>,
+    admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<CreateQuoteRequest>,
+) -> Result<HttpResponse> {
+    let rfq_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    if req.valid_until.is_some_and(|valid_until| valid_until < Utc::now().date_naive()) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "valid_until cannot be in the past"
+        })));
+    }
+
+    let Some(priced) = price_quote(&req.items) else {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Quote total is too large"
+        })));
+    };
+
+    let result: Result<Option<Result<RfqQuoteResponse, String>>, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let status = sqlx::query_scalar::<_, String>("SELECT status FROM rfqs WHERE id = $1 FOR UPDATE")
+            .bind(rfq_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let Some(status) = status else {
+            return Ok(None);
+        };
+        if !matches!(status.as_str(), "submitted" | "quoted") {
+            return Ok(Some(Err(format!("Cannot quote a {} RFQ", status))));
+        }
+
+        let quote = sqlx::query_as::<_, RfqQuote>(
+            r#"
+            INSERT INTO rfq_quotes (id, rfq_id, version, status, cost_total, margin_total, total_amount, deposit_basis_points, balance_due_days_before, valid_until, notes, created_by, created_at)
+            VALUES ($1, $2, (SELECT COALESCE(MAX(version), 0) + 1 FROM rfq_quotes WHERE rfq_id = $2), 'draft', $3, $4, $5, $6, $7, $8, $9, $10, NOW())
+            RETURNING *
+            "#
+        )
+        .bind(Uuid::new_v4())
+        .bind(rfq_id)
+        .bind(priced.cost_total)
+        .bind(priced.margin_total)
+        .bind(priced.total_amount)
+        .bind(req.deposit_basis_points)
+        .bind(req.balance_due_days_before)
+        .bind(req.valid_until)
+        .bind(&req.notes)
+        .bind(admin.user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut items = Vec::with_capacity(priced.items.len());
+        for (index, line) in priced.items.iter().enumerate() {
+            let item = sqlx::query_as::<_, RfqQuoteItem>(
+                r#"
+                INSERT INTO rfq_quote_items (id, quote_id, position, category, description, quantity, unit_cost, margin_basis_points, cost_amount, sell_amount)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                RETURNING *
+                "#
+            )
+            .bind(Uuid::new_v4())
+            .bind(quote.id)
+            .bind(index as i32 + 1)
+            .bind(&line.item.category)
+            .bind(&line.item.description)
+            .bind(line.item.quantity)
+            .bind(line.item.unit_cost)
+            .bind(line.item.margin_basis_points)
+            .bind(line.cost_amount)
+            .bind(line.sell_amount)
+            .fetch_one(&mut *tx)
+            .await?;
+            items.push(item);
+        }
+
+        tx.commit().await?;
+        Ok(Some(Ok(RfqQuoteResponse { quote, items })))
+    }
+    .await;
+
+    match result {
+        Ok(Some(Ok(quote))) => Ok(HttpResponse::Created().json(quote)),
+        Ok(Some(Err(reason))) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": reason
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "RFQ not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to create RFQ quote: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create quote"
+            })))
+        }
+    }
+}
+
+/// Sends a draft quote to the customer, superseding every earlier version.
+async fn send_rfq_quote(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse> {
+    let (rfq_id, quote_id) = path.into_inner();
+
+    let result: Result<Option<Result<RfqQuote, String>>, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let rfq = sqlx::query_as::<_, Rfq>("SELECT * FROM rfqs WHERE id = $1 FOR UPDATE")
+            .bind(rfq_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let quote = sqlx::query_as::<_, RfqQuote>("SELECT * FROM rfq_quotes WHERE id = $1 AND rfq_id = $2")
+            .bind(quote_id)
+            .bind(rfq_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let (Some(rfq), Some(quote)) = (rfq, quote) else {
+            return Ok(None);
+        };
+        if !matches!(rfq.status.as_str(), "submitted" | "quoted") {
+            return Ok(Some(Err(format!("Cannot send a quote for a {} RFQ", rfq.status))));
+        }
+        if quote.status != "draft" {
+            return Ok(Some(Err(format!("Quote v{} has already been {}", quote.version, quote.status))));
+        }
+
+        sqlx::query("UPDATE rfq_quotes SET status = 'superseded' WHERE rfq_id = $1 AND id <> $2 AND status IN ('draft', 'sent')")
+            .bind(rfq_id)
+            .bind(quote_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let quote = sqlx::query_as::<_, RfqQuote>(
+            "UPDATE rfq_quotes SET status = 'sent', sent_at = NOW() WHERE id = $1 RETURNING *"
+        )
+        .bind(quote_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE rfqs SET status = 'quoted' WHERE id = $1")
+            .bind(rfq_id)
+            .execute(&mut *tx)
+            .await?;
+
+        queue_notification(
+            &mut *tx,
+            Some(rfq.user_id),
+            &rfq.contact_email,
+            "rfq_quote_sent",
+            &format!("Your quote for {} is ready", rfq.title),
+            &format!(
+                "Hi {}, quote v{} for {} is ready for review. You can accept it online from your account.",
+                rfq.contact_name, quote.version, rfq.title
+            ),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(Ok(quote)))
+    }
+    .await;
+
+    match result {
+        Ok(Some(Ok(quote))) => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Quote sent successfully",
+                "quote": quote
+            })))
+        }
+        Ok(Some(Err(reason))) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": reason
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Quote not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to send RFQ quote: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to send quote"
+            })))
+        }
+    }
+}
+
+async fn update_rfq_status(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<UpdateRfqStatusRequest>,
+) -> Result<HttpResponse> {
+    let rfq_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    // An accepted RFQ is settled through its booking instead
+    let result = sqlx::query_as::<_, (Uuid, String)>(
+        r#"
+        UPDATE rfqs
+        SET status = CASE WHEN status = 'accepted' THEN status ELSE $2 END
+        WHERE id = $1
+        RETURNING id, status
+        "#
+    )
+    .bind(rfq_id)
+    .bind(&req.status)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(Some((_, status))) if status == "accepted" => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "An accepted RFQ cannot be closed; cancel its booking instead"
+            })))
+        }
+        Ok(Some(_)) => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "RFQ status updated successfully"
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "RFQ not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to update RFQ status: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update RFQ status"
+            })))
+        }
+    }
+}
+
+async fn get_newsletter_subscribers(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<SubscriberQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    match find_subscribers(pool.get_ref(), &query, Some(limit), offset).await {
+        Ok(subscribers) => Ok(HttpResponse::Ok().json(subscribers)),
+        Err(e) => {
+            log::error!("Failed to fetch newsletter subscribers: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch subscribers"
+            })))
+        }
+    }
+}
+
+/// Downloads the matching subscribers as CSV. Only confirmed subscribers are
+/// exported unless another `status` is asked for.
+async fn export_newsletter_subscribers(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    query: web::Query<SubscriberQuery>,
+) -> Result<HttpResponse> {
+    let mut query = query.into_inner();
+    query.status.get_or_insert_with(|| "subscribed".to_string());
+
+    let subscribers = match find_subscribers(pool.get_ref(), &query, None, 0).await {
+        Ok(subscribers) => subscribers,
+        Err(e) => {
+            log::error!("Failed to fetch newsletter subscribers: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to export subscribers"
+            })));
+        }
+    };
+
+    match encode_subscribers_csv(&subscribers) {
+        Ok(csv) => {
+            let file_name = format!("subscribers-{}.csv", Utc::now().format("%Y%m%d-%H%M%S"));
+            Ok(HttpResponse::Ok()
+                .content_type("text/csv; charset=utf-8")
+                .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", file_name)))
+                .body(csv))
+        }
+        Err(e) => {
+            log::error!("Failed to encode newsletter subscribers: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to export subscribers"
+            })))
+        }
+    }
+}
+
+async fn get_offers(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+) -> Result<HttpResponse> {
+    let offers = sqlx::query_as::<_, Offer>("SELECT * FROM offers ORDER BY starts_at DESC")
+        .fetch_all(pool.get_ref())
+        .await;
+
+    let offers = match offers {
+        Ok(offers) => attach_offer_packages(pool.get_ref(), offers, true).await,
+        Err(e) => Err(e),
+    };
+
+    match offers {
+        Ok(offers) => {
+            Ok(HttpResponse::Ok().json(offers))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch offers: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch offers"
+            })))
+        }
+    }
+}
+
+async fn create_offer(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    req: web::Json<CreateOfferRequest>,
+) -> Result<HttpResponse> {
+    if let Some(response) = invalid_offer(&req) {
+        return Ok(response);
+    }
+
+    let result: Result<Offer, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let offer = sqlx::query_as::<_, Offer>(
+            r#"
+            INSERT INTO offers (id, title, description, badge_label, banner_image_url, discount_type, discount_value, starts_at, ends_at, is_active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW(), NOW())
+            RETURNING *
+            "#
+        )
+        .bind(Uuid::new_v4())
+        .bind(&req.title)
+        .bind(&req.description)
+        .bind(&req.badge_label)
+        .bind(&req.banner_image_url)
+        .bind(&req.discount_type)
+        .bind(req.discount_value)
+        .bind(req.starts_at)
+        .bind(req.ends_at)
+        .bind(req.is_active.unwrap_or(true))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        link_offer_packages(&mut tx, offer.id, &req.package_ids).await?;
+
+        tx.commit().await?;
+        Ok(offer)
+    }
+    .await;
+
+    match result {
+        Ok(offer) => {
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "message": "Offer created successfully",
+                "offer_id": offer.id
+            })))
+        }
+        Err(e) => Ok(offer_write_error(e, "create")),
+    }
+}
+
+async fn update_offer(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+    req: web::Json<CreateOfferRequest>,
+) -> Result<HttpResponse> {
+    let offer_id = path.into_inner();
+
+    if let Some(response) = invalid_offer(&req) {
+        return Ok(response);
+    }
+
+    let result: Result<Option<Offer>, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let offer = sqlx::query_as::<_, Offer>(
+            r#"
+            UPDATE offers
+            SET title = $2, description = $3, badge_label = $4, banner_image_url = $5, discount_type = $6,
+                discount_value = $7, starts_at = $8, ends_at = $9, is_active = $10, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#
+        )
+        .bind(offer_id)
+        .bind(&req.title)
+        .bind(&req.description)
+        .bind(&req.badge_label)
+        .bind(&req.banner_image_url)
+        .bind(&req.discount_type)
+        .bind(req.discount_value)
+        .bind(req.starts_at)
+        .bind(req.ends_at)
+        .bind(req.is_active.unwrap_or(true))
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(offer) = offer else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM offer_packages WHERE offer_id = $1")
+            .bind(offer_id)
+            .execute(&mut *tx)
+            .await?;
+        link_offer_packages(&mut tx, offer_id, &req.package_ids).await?;
+
+        tx.commit().await?;
+        Ok(Some(offer))
+    }
+    .await;
+
+    match result {
+        Ok(Some(_)) => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Offer updated successfully"
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Offer not found"
+            })))
+        }
+        Err(e) => Ok(offer_write_error(e, "update")),
+    }
+}
+
+async fn delete_offer(
+    pool: web::Data<PgPool>,
+    _admin: AdminOnly,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let offer_id = path.into_inner();
+
+    // Bookings keep their price; only the link to the offer is cleared
+    let result = sqlx::query("DELETE FROM offers WHERE id = $1")
+        .bind(offer_id)
+        .execute(pool.get_ref())
+        .await;
+
+    match result {
+        Ok(result) => {
+            if result.rows_affected() > 0 {
                 Ok(HttpResponse::Ok().json(serde_json::json!({
-                    "message": "Booking status updated successfully"
+                    "message": "Offer deleted successfully"
                 })))
             } else {
                 Ok(HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "Booking not found"
+                    "error": "Offer not found"
                 })))
             }
         }
         Err(e) => {
-            log::error!("Failed to update booking status: {}", e);
+            log::error!("Failed to delete offer: {}", e);
             Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to update booking status"
+                "error": "Failed to delete offer"
             })))
         }
     }
 }
+
+fn invalid_offer(req: &CreateOfferRequest) -> Option<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Some(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let error = if req.ends_at <= req.starts_at {
+        "ends_at must be after starts_at"
+    } else if req.discount_type == "percentage" && req.discount_value >= 10_000 {
+        "A percentage discount must be below 10000 basis points"
+    } else {
+        return None;
+    };
+
+    Some(HttpResponse::BadRequest().json(serde_json::json!({
+        "error": error
+    })))
+}
+
+async fn link_offer_packages(
+    conn: &mut sqlx::PgConnection,
+    offer_id: Uuid,
+    package_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO offer_packages (offer_id, package_id)
+        SELECT $1, package_id FROM UNNEST($2::UUID[]) AS package_id
+        ON CONFLICT DO NOTHING
+        "#
+    )
+    .bind(offer_id)
+    .bind(package_ids)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+fn offer_write_error(error: sqlx::Error, action: &str) -> HttpResponse {
+    match error {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Package not found"
+            }))
+        }
+        e => {
+            log::error!("Failed to {} offer: {}", action, e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to {} offer", action)
+            }))
+        }
+    }
+}
@@ -82,6 +82,18 @@ async fn register(
 
     match result {
         Ok(user) => {
+            // Link a newsletter subscription made before registering
+            if let Err(e) = sqlx::query(
+                "UPDATE newsletter_subscribers SET user_id = $1 WHERE LOWER(email) = LOWER($2) AND user_id IS NULL"
+            )
+            .bind(user.id)
+            .bind(&user.email)
+            .execute(pool.get_ref())
+            .await
+            {
+                log::error!("Failed to link newsletter subscription: {}", e);
+            }
+
             let token = create_jwt(user.id)?;
             let response = AuthResponse {
                 token,
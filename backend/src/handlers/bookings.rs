@@ -1,22 +1,47 @@
 use actix_web::{web, HttpResponse, Result, Scope};
-use sqlx::PgPool;
+use sqlx::{Acquire, PgConnection, PgPool, Row};
+use sqlx::postgres::PgRow;
 use uuid::Uuid;
 use validator::Validate;
-use chrono::Utc;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 
-use crate::models::{Booking, CreateBookingRequest, BookingResponse};
+use crate::models::{
+    Booking, CreateBookingRequest, BookingResponse, BookingQuoteRequest, BookingQuoteResponse,
+    BookingTaxLine, CreateMessageRequest, Invoice, TaxLine,
+};
+use crate::config::Config;
+use crate::jobs::waitlist::{close_offers, spawn_offer_freed_seats};
 use crate::middleware::auth::AuthenticatedUser;
+use crate::utils::booking_rules::{check_booking_date, package_booking_rules};
+use crate::utils::inventory::{seats_available, variant_seats_available};
+use crate::utils::messages::{post_message, read_thread, ThreadSide, CUSTOMER_UNREAD_SQL};
+use crate::utils::offers::best_offer_sql;
+use crate::utils::payments::{create_installments, payment_summary};
+use crate::utils::pdf::{render_invoice, render_voucher, InvoiceDocument, VoucherDocument};
+use crate::utils::publishing::VISIBLE_PACKAGE_SQL;
+use crate::utils::reference::{booking_lookup_key, generate_booking_reference};
+use crate::utils::tax::{applicable_tax_rules, compute_tax, insert_booking_tax_lines};
+use crate::utils::variants::variant_selection_error;
+
+pub(crate) const MAX_REFERENCE_ATTEMPTS: u32 = 5;
 
 pub fn booking_routes() -> Scope {
     web::scope("/bookings")
         .route("", web::post().to(create_booking))
         .route("", web::get().to(get_user_bookings))
+        .route("/quote", web::post().to(quote_booking))
         .route("/{id}", web::get().to(get_booking_by_id))
         .route("/{id}/cancel", web::put().to(cancel_booking))
+        .route("/{id}/payments", web::get().to(get_booking_payments))
+        .route("/{id}/messages", web::get().to(get_booking_messages))
+        .route("/{id}/messages", web::post().to(post_booking_message))
+        .route("/{id}/invoice.pdf", web::get().to(get_booking_invoice))
+        .route("/{id}/voucher.pdf", web::get().to(get_booking_voucher))
 }
 
 async fn create_booking(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     user: AuthenticatedUser,
     req: web::Json<CreateBookingRequest>,
 ) -> Result<HttpResponse> {
@@ -27,21 +52,54 @@ async fn create_booking(
         })));
     }
 
-    // Get package details to calculate total amount
-    let package = sqlx::query(
-        "SELECT price FROM packages WHERE id = $1 AND is_active = true"
-    )
-    .bind(req.package_id)
-    .fetch_optional(pool.get_ref())
-    .await;
+    if req.traveller_names.as_ref().is_some_and(|names| names.len() > req.number_of_people as usize) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "More traveller names than number of people"
+        })));
+    }
 
-    let package = match package {
-        Ok(Some(package)) => package,
-        Ok(None) => {
+    match package_booking_rules(pool.get_ref(), req.package_id).await {
+        Ok(rules) => {
+            if let Err(violation) = check_booking_date(rules.as_ref(), req.booking_date, Utc::now().date_naive()) {
+                return Ok(violation.to_response());
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package booking rules: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create booking"
+            })));
+        }
+    }
+
+    match variant_selection_error(pool.get_ref(), req.package_id, req.variant_id).await {
+        Ok(Some(error)) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            })));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            log::error!("Failed to fetch package variants: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create booking"
+            })));
+        }
+    }
+
+    // Get package details to calculate total amount
+    let quote = match quote_package(pool.get_ref(), req.package_id, req.variant_id, req.number_of_people).await {
+        Ok(PackageQuote::Quoted(quote)) => quote,
+        Ok(PackageQuote::NotFound) => {
             return Ok(HttpResponse::NotFound().json(serde_json::json!({
                 "error": "Package not found"
             })));
         }
+        Ok(PackageQuote::TooLarge) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "The total for this party is too large to book online"
+            })));
+        }
         Err(e) => {
             log::error!("Failed to fetch package: {}", e);
             return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
@@ -50,38 +108,31 @@ async fn create_booking(
         }
     };
 
-    let total_amount = {
-        use sqlx::Row;
-        package.get::<i32, _>("price") * req.number_of_people
-    };
-    let booking_id = Uuid::new_v4();
-    let now = Utc::now();
-
-    let result = sqlx::query_as::<_, Booking>(
-        r#"
-        INSERT INTO bookings (id, user_id, package_id, booking_date, number_of_people, total_amount, status, special_requests, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, 'pending', $7, $8, $9)
-        RETURNING *
-        "#
-    )
-    .bind(booking_id)
-    .bind(user.user_id)
-    .bind(req.package_id)
-    .bind(req.booking_date)
-    .bind(req.number_of_people)
-    .bind(total_amount)
-    .bind(&req.special_requests)
-    .bind(now)
-    .bind(now)
-    .fetch_one(pool.get_ref())
-    .await;
+    let hold_expires_at = Utc::now() + Duration::minutes(config.booking_hold_minutes);
+    let result = insert_booking(pool.get_ref(), user.user_id, &req, &quote, hold_expires_at).await;
 
     match result {
-        Ok(booking) => {
+        Ok(BookingInsert::SoldOut { seats_available }) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Not enough seats available on this departure",
+                "seats_available": seats_available
+            })))
+        }
+        Ok(BookingInsert::Created(booking)) => {
             Ok(HttpResponse::Created().json(serde_json::json!({
                 "message": "Booking created successfully",
                 "booking_id": booking.id,
-                "total_amount": booking.total_amount
+                "reference": booking.reference,
+                "invoice_url": format!("/api/bookings/{}/invoice.pdf", booking.id),
+                "voucher_url": format!("/api/bookings/{}/voucher.pdf", booking.id),
+                "base_amount": booking.base_amount,
+                "tax_amount": booking.tax_amount,
+                "tax_lines": quote.breakdown.tax_lines,
+                "total_amount": booking.total_amount,
+                "offer_id": booking.offer_id,
+                "variant_id": booking.variant_id,
+                "status": booking.status,
+                "hold_expires_at": booking.hold_expires_at
             })))
         }
         Err(e) => {
@@ -93,86 +144,131 @@ async fn create_booking(
     }
 }
 
+async fn quote_booking(
+    pool: web::Data<PgPool>,
+    req: web::Json<BookingQuoteRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    match variant_selection_error(pool.get_ref(), req.package_id, req.variant_id).await {
+        Ok(Some(error)) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            })));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            log::error!("Failed to fetch package variants: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to calculate quote"
+            })));
+        }
+    }
+
+    match quote_package(pool.get_ref(), req.package_id, req.variant_id, req.number_of_people).await {
+        Ok(PackageQuote::Quoted(quote)) => Ok(HttpResponse::Ok().json(quote)),
+        Ok(PackageQuote::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Ok(PackageQuote::TooLarge) => {
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "The total for this party is too large to book online"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to quote booking: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to calculate quote"
+            })))
+        }
+    }
+}
+
 async fn get_user_bookings(
     pool: web::Data<PgPool>,
     user: AuthenticatedUser,
 ) -> Result<HttpResponse> {
-    let bookings = sqlx::query(
+    let bookings = sqlx::query_as::<_, BookingResponse>(&format!(
         r#"
-        SELECT b.id, b.booking_date, b.number_of_people, b.total_amount, b.status, 
-               b.special_requests, b.created_at, p.title as package_title
+        SELECT b.id, b.reference, b.booking_date, b.number_of_people, b.base_amount, b.tax_amount,
+               b.total_amount, b.amount_paid, b.status, b.special_requests, b.traveller_names,
+               b.hold_expires_at, b.created_at, COALESCE(p.title, r.title) as package_title,
+               {} as unread_messages
         FROM bookings b
-        JOIN packages p ON b.package_id = p.id
+        LEFT JOIN packages p ON b.package_id = p.id
+        LEFT JOIN rfqs r ON b.rfq_id = r.id
         WHERE b.user_id = $1
         ORDER BY b.created_at DESC
-        "#
-    )
+        "#,
+        CUSTOMER_UNREAD_SQL
+    ))
     .bind(user.user_id)
     .fetch_all(pool.get_ref())
     .await;
 
-    match bookings {
-        Ok(bookings) => {
-            let responses: Vec<BookingResponse> = bookings.into_iter().map(|b| {
-                use sqlx::Row;
-                BookingResponse {
-                    id: b.get::<Uuid, _>("id"),
-                    package_title: b.get::<String, _>("package_title"),
-                    booking_date: b.get::<chrono::NaiveDate, _>("booking_date"),
-                    number_of_people: b.get::<i32, _>("number_of_people"),
-                    total_amount: b.get::<i32, _>("total_amount"),
-                    status: b.get::<String, _>("status"),
-                    special_requests: b.get::<Option<String>, _>("special_requests"),
-                    created_at: b.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
-                }
-            }).collect();
-
-            Ok(HttpResponse::Ok().json(responses))
-        }
+    let mut bookings = match bookings {
+        Ok(bookings) => bookings,
         Err(e) => {
             log::error!("Failed to fetch user bookings: {}", e);
-            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": "Failed to fetch bookings"
-            })))
+            })));
         }
+    };
+
+    if let Err(e) = attach_tax_lines(pool.get_ref(), &mut bookings).await {
+        log::error!("Failed to fetch booking tax lines: {}", e);
+        return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to fetch bookings"
+        })));
     }
+
+    Ok(HttpResponse::Ok().json(bookings))
 }
 
 async fn get_booking_by_id(
     pool: web::Data<PgPool>,
     user: AuthenticatedUser,
-    path: web::Path<Uuid>,
+    path: web::Path<String>,
 ) -> Result<HttpResponse> {
-    let booking_id = path.into_inner();
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
 
-    let booking = sqlx::query(
+    let booking = sqlx::query_as::<_, BookingResponse>(&format!(
         r#"
-        SELECT b.id, b.booking_date, b.number_of_people, b.total_amount, b.status, 
-               b.special_requests, b.created_at, p.title as package_title
+        SELECT b.id, b.reference, b.booking_date, b.number_of_people, b.base_amount, b.tax_amount,
+               b.total_amount, b.amount_paid, b.status, b.special_requests, b.traveller_names,
+               b.hold_expires_at, b.created_at, COALESCE(p.title, r.title) as package_title,
+               {} as unread_messages
         FROM bookings b
-        JOIN packages p ON b.package_id = p.id
-        WHERE b.id = $1 AND b.user_id = $2
-        "#
-    )
+        LEFT JOIN packages p ON b.package_id = p.id
+        LEFT JOIN rfqs r ON b.rfq_id = r.id
+        WHERE (b.id = $1 OR b.reference = $2) AND b.user_id = $3
+        "#,
+        CUSTOMER_UNREAD_SQL
+    ))
     .bind(booking_id)
+    .bind(&reference)
     .bind(user.user_id)
     .fetch_optional(pool.get_ref())
     .await;
 
     match booking {
         Ok(Some(booking)) => {
-            use sqlx::Row;
-            let response = BookingResponse {
-                id: booking.get::<Uuid, _>("id"),
-                package_title: booking.get::<String, _>("package_title"),
-                booking_date: booking.get::<chrono::NaiveDate, _>("booking_date"),
-                number_of_people: booking.get::<i32, _>("number_of_people"),
-                total_amount: booking.get::<i32, _>("total_amount"),
-                status: booking.get::<String, _>("status"),
-                special_requests: booking.get::<Option<String>, _>("special_requests"),
-                created_at: booking.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
-            };
-            Ok(HttpResponse::Ok().json(response))
+            let mut bookings = vec![booking];
+            if let Err(e) = attach_tax_lines(pool.get_ref(), &mut bookings).await {
+                log::error!("Failed to fetch booking tax lines: {}", e);
+                return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to fetch booking"
+                })));
+            }
+            Ok(HttpResponse::Ok().json(&bookings[0]))
         }
         Ok(None) => {
             Ok(HttpResponse::NotFound().json(serde_json::json!({
@@ -190,34 +286,44 @@ async fn get_booking_by_id(
 
 async fn cancel_booking(
     pool: web::Data<PgPool>,
+    config: web::Data<Config>,
     user: AuthenticatedUser,
-    path: web::Path<Uuid>,
+    path: web::Path<String>,
 ) -> Result<HttpResponse> {
-    let booking_id = path.into_inner();
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
 
-    let result = sqlx::query(
+    let result = sqlx::query_as::<_, (Uuid, Option<Uuid>, NaiveDate)>(
         r#"
         UPDATE bookings 
-        SET status = 'cancelled', updated_at = NOW()
-        WHERE id = $1 AND user_id = $2 AND status = 'pending'
+        SET status = 'cancelled', hold_expires_at = NULL, updated_at = NOW()
+        WHERE (id = $1 OR reference = $2) AND user_id = $3 AND status = 'pending'
+        RETURNING id, package_id, booking_date
         "#
     )
     .bind(booking_id)
+    .bind(&reference)
     .bind(user.user_id)
-    .execute(pool.get_ref())
+    .fetch_optional(pool.get_ref())
     .await;
 
     match result {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
-                Ok(HttpResponse::Ok().json(serde_json::json!({
-                    "message": "Booking cancelled successfully"
-                })))
-            } else {
-                Ok(HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "Booking not found or cannot be cancelled"
-                })))
+        Ok(Some((booking_id, package_id, booking_date))) => {
+            // A cancelled waitlist offer is declined; either way the seats go back to the queue
+            if let Err(e) = close_offers(pool.get_ref(), &[booking_id], "declined").await {
+                log::error!("Failed to close waitlist offer for booking {}: {}", booking_id, e);
+            }
+            if let Some(package_id) = package_id {
+                spawn_offer_freed_seats(pool.get_ref().clone(), config.waitlist_offer_hours, package_id, booking_date);
             }
+
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Booking cancelled successfully"
+            })))
+        }
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found or cannot be cancelled"
+            })))
         }
         Err(e) => {
             log::error!("Failed to cancel booking: {}", e);
@@ -227,3 +333,592 @@ async fn cancel_booking(
         }
     }
 }
+
+async fn get_booking_payments(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let summary = match find_user_booking(pool.get_ref(), booking_id, reference.as_deref(), user.user_id).await {
+        Ok(Some(booking_id)) => payment_summary(pool.get_ref(), booking_id).await,
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match summary {
+        Ok(Some(summary)) => Ok(HttpResponse::Ok().json(summary)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking payments: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking payments"
+            })))
+        }
+    }
+}
+
+async fn get_booking_messages(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let messages = match find_user_booking(pool.get_ref(), booking_id, reference.as_deref(), user.user_id).await {
+        Ok(Some(booking_id)) => read_thread(pool.get_ref(), booking_id, ThreadSide::Customer).await.map(Some),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match messages {
+        Ok(Some(messages)) => Ok(HttpResponse::Ok().json(messages)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking messages: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking messages"
+            })))
+        }
+    }
+}
+
+async fn post_booking_message(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<CreateMessageRequest>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let message = match find_user_booking(pool.get_ref(), booking_id, reference.as_deref(), user.user_id).await {
+        Ok(Some(booking_id)) => post_message(pool.get_ref(), booking_id, user.user_id, ThreadSide::Customer, &req).await.map(Some),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+
+    match message {
+        Ok(Some(message)) => Ok(HttpResponse::Created().json(message)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to post booking message: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to post message"
+            })))
+        }
+    }
+}
+
+async fn get_booking_invoice(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let booking = match fetch_document_booking(pool.get_ref(), booking_id, reference.as_deref(), user.user_id).await {
+        Ok(Some(booking)) => booking,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking"
+            })));
+        }
+    };
+
+    let invoice = match issue_invoice(pool.get_ref(), &booking).await {
+        Ok(Some(invoice)) => invoice,
+        Ok(None) => {
+            return Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "An invoice is only issued once the booking is confirmed or paid"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to issue invoice: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to issue invoice"
+            })));
+        }
+    };
+
+    let booking_id = booking.get::<Uuid, _>("id");
+    let tax_lines = sqlx::query_as::<_, TaxLine>(
+        r#"
+        SELECT tax_rule_id, name, rate_basis_points, mode, amount
+        FROM booking_tax_lines
+        WHERE booking_id = $1
+        ORDER BY position
+        "#
+    )
+    .bind(booking_id)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    let tax_lines = match tax_lines {
+        Ok(tax_lines) => tax_lines,
+        Err(e) => {
+            log::error!("Failed to fetch booking tax lines: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to issue invoice"
+            })));
+        }
+    };
+
+    let document = InvoiceDocument {
+        invoice_number: invoice.invoice_number.clone(),
+        issued_at: invoice.issued_at,
+        booking_reference: booking.get::<String, _>("reference"),
+        customer_name: customer_name(&booking),
+        customer_email: booking.get::<String, _>("email"),
+        package_title: booking.get::<String, _>("package_title"),
+        booking_date: booking.get::<NaiveDate, _>("booking_date"),
+        number_of_people: booking.get::<i32, _>("number_of_people"),
+        base_amount: invoice.base_amount,
+        tax_lines,
+        tax_amount: invoice.tax_amount,
+        total_amount: invoice.total_amount,
+    };
+
+    match render_invoice(&document) {
+        Ok(pdf) => Ok(pdf_response(pdf, &invoice.invoice_number.replace('/', "-"))),
+        Err(e) => {
+            log::error!("Failed to render invoice: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to render invoice"
+            })))
+        }
+    }
+}
+
+async fn get_booking_voucher(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let (booking_id, reference) = booking_lookup_key(&path.into_inner());
+
+    let booking = match fetch_document_booking(pool.get_ref(), booking_id, reference.as_deref(), user.user_id).await {
+        Ok(Some(booking)) => booking,
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Booking not found"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to fetch booking: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch booking"
+            })));
+        }
+    };
+
+    // Vouchers are presented at departure, so only bookings that will travel get one
+    let status = booking.get::<String, _>("status");
+    if status != "confirmed" && status != "completed" {
+        return Ok(HttpResponse::Conflict().json(serde_json::json!({
+            "error": "A voucher is only issued once the booking is confirmed"
+        })));
+    }
+
+    let mut travellers = booking.get::<Vec<String>, _>("traveller_names");
+    if travellers.is_empty() {
+        travellers.push(customer_name(&booking));
+    }
+    let booking_code = booking.get::<String, _>("reference");
+
+    let document = VoucherDocument {
+        booking_code: booking_code.clone(),
+        status,
+        package_title: booking.get::<String, _>("package_title"),
+        booking_date: booking.get::<NaiveDate, _>("booking_date"),
+        duration_days: booking.get::<i32, _>("duration_days"),
+        number_of_people: booking.get::<i32, _>("number_of_people"),
+        travellers,
+        itinerary: booking.get::<serde_json::Value, _>("itinerary"),
+        special_requests: booking.get::<Option<String>, _>("special_requests"),
+    };
+
+    match render_voucher(&document) {
+        Ok(pdf) => Ok(pdf_response(pdf, &format!("voucher-{}", booking_code))),
+        Err(e) => {
+            log::error!("Failed to render voucher: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to render voucher"
+            })))
+        }
+    }
+}
+
+pub(crate) enum PackageQuote {
+    Quoted(Box<BookingQuoteResponse>),
+    NotFound,
+    /// The party's total does not fit in an amount column.
+    TooLarge,
+}
+
+/// Prices a package for a party size using the shared tax routine, at the
+/// cheapest offer live right now. A variant's price replaces the package
+/// price; check the choice with `variant_selection_error` first.
+pub(crate) async fn quote_package(
+    pool: &PgPool,
+    package_id: Uuid,
+    variant_id: Option<Uuid>,
+    number_of_people: i32,
+) -> Result<PackageQuote, sqlx::Error> {
+    let package = sqlx::query(&format!(
+        r#"
+        SELECT p.price, p.category_id, p.is_international, lo.offer_id, lo.offer_price
+        FROM (
+            SELECT p.id, COALESCE(v.price, p.price) as price, p.price as base_price, p.category_id, p.is_international
+            FROM packages p
+            LEFT JOIN package_variants v ON v.id = $2 AND v.package_id = p.id AND v.is_active
+            WHERE p.id = $1 AND {}
+        ) p
+        LEFT JOIN LATERAL ({}) lo ON lo.offer_price < p.price
+        "#,
+        VISIBLE_PACKAGE_SQL,
+        best_offer_sql()
+    ))
+    .bind(package_id)
+    .bind(variant_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(package) = package else {
+        return Ok(PackageQuote::NotFound);
+    };
+
+    let list_price = package.get::<i32, _>("price");
+    let offer_id = package.get::<Option<Uuid>, _>("offer_id");
+    let unit_price = package.get::<Option<i32>, _>("offer_price").unwrap_or(list_price);
+    let Some(list_amount) = unit_price.checked_mul(number_of_people) else {
+        return Ok(PackageQuote::TooLarge);
+    };
+    let rules = applicable_tax_rules(
+        pool,
+        package.get::<Option<Uuid>, _>("category_id"),
+        package.get::<bool, _>("is_international"),
+    )
+    .await?;
+
+    let Some(breakdown) = compute_tax(list_amount, &rules) else {
+        return Ok(PackageQuote::TooLarge);
+    };
+
+    Ok(PackageQuote::Quoted(Box::new(BookingQuoteResponse {
+        package_id,
+        variant_id,
+        number_of_people,
+        unit_price,
+        original_unit_price: offer_id.map(|_| list_price),
+        offer_id,
+        breakdown,
+    })))
+}
+
+pub(crate) enum BookingInsert {
+    Created(Box<Booking>),
+    SoldOut { seats_available: i64 },
+}
+
+/// Places a pending booking that holds its seats until `hold_expires_at`.
+///
+/// Capacity is checked under a lock on the package row so concurrent bookings
+/// cannot oversell a departure. Retries if the generated reference collides.
+pub(crate) async fn insert_booking(
+    pool: &PgPool,
+    user_id: Uuid,
+    req: &CreateBookingRequest,
+    quote: &BookingQuoteResponse,
+    hold_expires_at: DateTime<Utc>,
+) -> Result<BookingInsert, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let result = insert_booking_in(&mut tx, user_id, req, quote, hold_expires_at).await?;
+    if let BookingInsert::Created(_) = result {
+        tx.commit().await?;
+    }
+    Ok(result)
+}
+
+/// Same as `insert_booking`, inside the caller's transaction. Each attempt runs
+/// under a savepoint so a reference collision does not abort the transaction.
+pub(crate) async fn insert_booking_in(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    req: &CreateBookingRequest,
+    quote: &BookingQuoteResponse,
+    hold_expires_at: DateTime<Utc>,
+) -> Result<BookingInsert, sqlx::Error> {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let reference = generate_booking_reference();
+        let mut savepoint = conn.begin().await?;
+        match try_insert_booking(&mut savepoint, user_id, req, quote, &reference, hold_expires_at).await {
+            Err(sqlx::Error::Database(e))
+                if attempts < MAX_REFERENCE_ATTEMPTS && e.constraint() == Some("idx_bookings_reference") =>
+            {
+                log::warn!("Booking reference {} already taken, retrying", reference);
+            }
+            Ok(result) => {
+                savepoint.commit().await?;
+                return Ok(result);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+async fn try_insert_booking(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    req: &CreateBookingRequest,
+    quote: &BookingQuoteResponse,
+    reference: &str,
+    hold_expires_at: DateTime<Utc>,
+) -> Result<BookingInsert, sqlx::Error> {
+    let breakdown = &quote.breakdown;
+    let booking_id = Uuid::new_v4();
+    let now = Utc::now();
+
+    sqlx::query("SELECT id FROM packages WHERE id = $1 FOR UPDATE")
+        .bind(req.package_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let mut seats_available = seats_available(&mut *conn, req.package_id, req.booking_date).await?;
+    if let Some(variant_id) = req.variant_id {
+        seats_available = seats_available.min(variant_seats_available(&mut *conn, variant_id, req.booking_date).await?);
+    }
+    if seats_available < req.number_of_people as i64 {
+        return Ok(BookingInsert::SoldOut { seats_available: seats_available.max(0) });
+    }
+
+    let booking = sqlx::query_as::<_, Booking>(
+        r#"
+        INSERT INTO bookings (id, reference, user_id, package_id, variant_id, booking_date, number_of_people, base_amount, tax_amount, total_amount, status, special_requests, traveller_names, hold_expires_at, offer_id, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'pending', $11, $12, $13, $14, $15, $16)
+        RETURNING *
+        "#
+    )
+    .bind(booking_id)
+    .bind(reference)
+    .bind(user_id)
+    .bind(req.package_id)
+    .bind(req.variant_id)
+    .bind(req.booking_date)
+    .bind(req.number_of_people)
+    .bind(breakdown.base_amount)
+    .bind(breakdown.tax_amount)
+    .bind(breakdown.total_amount)
+    .bind(&req.special_requests)
+    .bind(req.traveller_names.clone().unwrap_or_default())
+    .bind(hold_expires_at)
+    .bind(quote.offer_id)
+    .bind(now)
+    .bind(now)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    insert_booking_tax_lines(conn, booking.id, &breakdown.tax_lines).await?;
+
+    create_installments(
+        conn,
+        booking.id,
+        req.package_id,
+        breakdown.total_amount,
+        req.booking_date,
+        now.date_naive(),
+    )
+    .await?;
+
+    Ok(BookingInsert::Created(Box::new(booking)))
+}
+
+/// Loads the stored tax lines for each booking in one query.
+async fn attach_tax_lines(pool: &PgPool, bookings: &mut [BookingResponse]) -> Result<(), sqlx::Error> {
+    let booking_ids: Vec<Uuid> = bookings.iter().map(|b| b.id).collect();
+
+    let lines = sqlx::query_as::<_, BookingTaxLine>(
+        r#"
+        SELECT booking_id, tax_rule_id, name, rate_basis_points, mode, amount
+        FROM booking_tax_lines
+        WHERE booking_id = ANY($1)
+        ORDER BY booking_id, position
+        "#
+    )
+    .bind(&booking_ids)
+    .fetch_all(pool)
+    .await?;
+
+    for line in lines {
+        if let Some(booking) = bookings.iter_mut().find(|b| b.id == line.booking_id) {
+            booking.tax_lines.push(line.line);
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves a booking id or reference to the id of a booking owned by `user_id`.
+async fn find_user_booking(
+    pool: &PgPool,
+    booking_id: Option<Uuid>,
+    reference: Option<&str>,
+    user_id: Uuid,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar("SELECT id FROM bookings WHERE (id = $1 OR reference = $2) AND user_id = $3")
+        .bind(booking_id)
+        .bind(reference)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Loads a booking with the package and customer details printed on documents.
+/// Only returns the booking if it belongs to `user_id`.
+async fn fetch_document_booking(
+    pool: &PgPool,
+    booking_id: Option<Uuid>,
+    reference: Option<&str>,
+    user_id: Uuid,
+) -> Result<Option<PgRow>, sqlx::Error> {
+    sqlx::query(
+        r#"
+        SELECT b.id, b.reference, b.booking_date, b.number_of_people, b.base_amount, b.tax_amount,
+               b.total_amount, b.status, b.special_requests, b.traveller_names, b.hold_expires_at,
+               b.created_at, COALESCE(p.title, r.title) as package_title,
+               COALESCE(p.duration_days, r.end_date - r.start_date + 1) as duration_days,
+               COALESCE(p.itinerary, '[]'::JSONB) as itinerary,
+               u.first_name, u.last_name, u.email
+        FROM bookings b
+        LEFT JOIN packages p ON b.package_id = p.id
+        LEFT JOIN rfqs r ON b.rfq_id = r.id
+        JOIN users u ON b.user_id = u.id
+        WHERE (b.id = $1 OR b.reference = $2) AND b.user_id = $3
+        "#
+    )
+    .bind(booking_id)
+    .bind(reference)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Returns the booking's invoice, issuing the next number for the current
+/// financial year on first request. Only confirmed or completed bookings, or
+/// pending ones with a payment, are invoiced, so unpaid holds never use up a
+/// number in the series.
+async fn issue_invoice(pool: &PgPool, booking: &PgRow) -> Result<Option<Invoice>, sqlx::Error> {
+    let booking_id = booking.get::<Uuid, _>("id");
+    let mut tx = pool.begin().await?;
+
+    // Serialises concurrent first requests for the same booking and with status changes
+    let (status, amount_paid, base_amount, tax_amount, total_amount) = sqlx::query_as::<_, (String, i32, i32, i32, i32)>(
+        "SELECT status, amount_paid, base_amount, tax_amount, total_amount FROM bookings WHERE id = $1 FOR UPDATE"
+    )
+    .bind(booking_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let existing = sqlx::query_as::<_, Invoice>("SELECT * FROM invoices WHERE booking_id = $1")
+        .bind(booking_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    if let Some(invoice) = existing {
+        return Ok(Some(invoice));
+    }
+
+    let invoiceable = match status.as_str() {
+        "confirmed" | "completed" => true,
+        "pending" => amount_paid > 0,
+        _ => false,
+    };
+    if !invoiceable {
+        return Ok(None);
+    }
+
+    let now = Utc::now();
+    let financial_year = financial_year(now.date_naive());
+
+    let sequence_number: i32 = sqlx::query_scalar(
+        r#"
+        INSERT INTO invoice_sequences (financial_year, last_number)
+        VALUES ($1, 1)
+        ON CONFLICT (financial_year) DO UPDATE SET last_number = invoice_sequences.last_number + 1
+        RETURNING last_number
+        "#
+    )
+    .bind(&financial_year)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let invoice = sqlx::query_as::<_, Invoice>(
+        r#"
+        INSERT INTO invoices (id, booking_id, invoice_number, financial_year, sequence_number, base_amount, tax_amount, total_amount, issued_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(booking_id)
+    .bind(format!("INV/{}/{:05}", financial_year, sequence_number))
+    .bind(&financial_year)
+    .bind(sequence_number)
+    .bind(base_amount)
+    .bind(tax_amount)
+    .bind(total_amount)
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(invoice))
+}
+
+/// Indian financial year label (April to March), e.g. `2026-27`.
+fn financial_year(date: NaiveDate) -> String {
+    let start = if date.month() >= 4 { date.year() } else { date.year() - 1 };
+    format!("{}-{:02}", start, (start + 1) % 100)
+}
+
+fn customer_name(booking: &PgRow) -> String {
+    format!(
+        "{} {}",
+        booking.get::<String, _>("first_name"),
+        booking.get::<String, _>("last_name")
+    )
+}
+
+fn pdf_response(pdf: Vec<u8>, file_stem: &str) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(("Content-Disposition", format!("inline; filename=\"{}.pdf\"", file_stem)))
+        .body(pdf)
+}
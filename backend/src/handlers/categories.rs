@@ -0,0 +1,43 @@
+use actix_web::{web, HttpResponse, Result, Scope};
+use sqlx::PgPool;
+
+use crate::middleware::locale::Locale;
+use crate::models::CategoryResponse;
+use crate::utils::publishing::VISIBLE_PACKAGE_SQL;
+
+pub fn category_routes() -> Scope {
+    web::scope("/categories")
+        .route("", web::get().to(get_categories))
+}
+
+/// Active categories with their number of active packages, named in the
+/// requested language where a translation exists.
+async fn get_categories(pool: web::Data<PgPool>, locale: Locale) -> Result<HttpResponse> {
+    let categories = sqlx::query_as::<_, CategoryResponse>(&format!(
+        r#"
+        SELECT c.id, c.slug, COALESCE(ct.name, c.name) as name,
+               COALESCE(ct.description, c.description) as description, c.icon,
+               COUNT(p.id) as package_count
+        FROM categories c
+        LEFT JOIN category_translations ct ON ct.category_id = c.id AND ct.locale = $1
+        LEFT JOIN packages p ON p.category_id = c.id AND {}
+        WHERE c.is_active = true
+        GROUP BY c.id, ct.name, ct.description
+        ORDER BY name
+        "#,
+        VISIBLE_PACKAGE_SQL
+    ))
+    .bind(&locale.code)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match categories {
+        Ok(categories) => Ok(HttpResponse::Ok().json(categories)),
+        Err(e) => {
+            log::error!("Failed to fetch categories: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch categories"
+            })))
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use actix_web::{web, HttpRequest, HttpResponse, Result, Scope};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::config::Config;
+use crate::models::CreateEnquiryRequest;
+use crate::utils::client_ip::client_ip;
+use crate::utils::notifications::queue_notification;
+use crate::utils::{create_form_token, verify_form_token};
+
+const ENQUIRY_FORM: &str = "enquiry";
+
+pub fn enquiry_routes() -> Scope {
+    web::scope("/enquiries")
+        .route("", web::post().to(create_enquiry))
+        .route("/form-token", web::get().to(get_form_token))
+}
+
+async fn get_form_token() -> Result<HttpResponse> {
+    let token = create_form_token(ENQUIRY_FORM)?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "form_token": token
+    })))
+}
+
+/// Stores a contact form enquiry.
+///
+/// Spam checks, in order: a filled-in honeypot gets a fake success, the form
+/// token must be valid and at least `ENQUIRY_MIN_SUBMIT_SECS` old, and each
+/// client IP may submit `ENQUIRY_RATE_LIMIT_PER_HOUR` enquiries per hour. The
+/// IP is the connection's peer address, or `X-Forwarded-For` when the peer is
+/// one of `TRUSTED_PROXIES`.
+async fn create_enquiry(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    http_req: HttpRequest,
+    req: web::Json<CreateEnquiryRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let ip_address = client_ip(&http_req, &config.trusted_proxies).map(|ip| ip.to_string());
+
+    if !req.website.is_empty() {
+        log::warn!("Discarded enquiry from {:?}: honeypot filled in", ip_address);
+        return Ok(enquiry_received());
+    }
+
+    let claims = match verify_form_token(&req.form_token) {
+        Ok(claims) if claims.form == ENQUIRY_FORM => claims,
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "This form has expired, please reload the page and try again",
+                "code": "invalid_form_token"
+            })));
+        }
+    };
+
+    if Utc::now().timestamp() - (claims.iat as i64) < config.enquiry_min_submit_secs {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "The form was submitted too quickly, please try again",
+            "code": "submitted_too_fast"
+        })));
+    }
+
+    let recent = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM enquiries WHERE ip_address = $1 AND created_at > NOW() - INTERVAL '1 hour'"
+    )
+    .bind(&ip_address)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match recent {
+        Ok(recent) if recent >= config.enquiry_rate_limit_per_hour => {
+            return Ok(HttpResponse::TooManyRequests()
+                .insert_header(("Retry-After", "3600"))
+                .json(serde_json::json!({
+                    "error": "Too many enquiries, please try again later",
+                    "code": "rate_limited"
+                })));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            log::error!("Failed to check enquiry rate limit: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to submit enquiry"
+            })));
+        }
+    }
+
+    let result: Result<(), sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO enquiries (id, name, email, phone, subject, message, package_id, travel_date, number_of_people, status, ip_address, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'new', $10, NOW(), NOW())
+            "#
+        )
+        .bind(Uuid::new_v4())
+        .bind(&req.name)
+        .bind(&req.email)
+        .bind(&req.phone)
+        .bind(&req.subject)
+        .bind(&req.message)
+        .bind(req.package_id)
+        .bind(req.travel_date)
+        .bind(req.number_of_people)
+        .bind(&ip_address)
+        .execute(&mut *tx)
+        .await?;
+
+        queue_notification(
+            &mut *tx,
+            None,
+            &req.email,
+            "enquiry_received",
+            "We have received your enquiry",
+            &format!(
+                "Hi {}, thank you for getting in touch. One of our travel experts will reply to you shortly.",
+                req.name
+            ),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => Ok(enquiry_received()),
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Package not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to create enquiry: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to submit enquiry"
+            })))
+        }
+    }
+}
+
+// Identical for stored and discarded enquiries so bots cannot tell them apart
+fn enquiry_received() -> HttpResponse {
+    HttpResponse::Created().json(serde_json::json!({
+        "message": "Thank you! We will get back to you shortly."
+    }))
+}
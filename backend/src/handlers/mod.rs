@@ -1,4 +1,11 @@
 pub mod auth;
 pub mod packages;
+pub mod categories;
 pub mod bookings;
 pub mod admin;
+pub mod posts;
+pub mod enquiries;
+pub mod rfqs;
+pub mod offers;
+pub mod newsletter;
+pub mod sitemap;
@@ -0,0 +1,258 @@
+use actix_web::{web, HttpResponse, Result, Scope};
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::config::Config;
+use crate::models::{NewsletterSubscriber, SubscribeRequest};
+use crate::utils::newsletter::normalize_tags;
+use crate::utils::notifications::{queue_list_notification, queue_notification};
+use crate::utils::{create_newsletter_token, verify_newsletter_token};
+
+pub fn newsletter_routes() -> Scope {
+    web::scope("/newsletter")
+        .route("/subscribe", web::post().to(subscribe))
+        // Emailed links are fetched by link scanners, so GET only describes
+        // the link and the change itself needs a POST
+        .route("/confirm", web::get().to(describe_confirm_link))
+        .route("/confirm", web::post().to(confirm_subscription))
+        .route("/unsubscribe", web::get().to(describe_unsubscribe_link))
+        // Also serves one-click unsubscribe from mail clients (RFC 8058)
+        .route("/unsubscribe", web::post().to(unsubscribe))
+}
+
+#[derive(serde::Deserialize)]
+struct TokenQuery {
+    token: String,
+}
+
+/// Starts a double opt-in subscription: the address is stored as pending and
+/// sent a confirmation link. Signing up again updates the segment tags and
+/// resends the link; confirmed subscribers are left as they are. The reply
+/// is the same either way, so it does not reveal who is subscribed.
+async fn subscribe(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    req: web::Json<SubscribeRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    let email = req.email.trim().to_lowercase();
+    let token = create_newsletter_token(&email, "confirm").map_err(|e| {
+        log::error!("Newsletter token creation error: {}", e);
+        actix_web::error::ErrorInternalServerError("Failed to create token")
+    })?;
+
+    let result: Result<(), sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let subscriber = sqlx::query_as::<_, NewsletterSubscriber>(
+            r#"
+            INSERT INTO newsletter_subscribers (id, email, first_name, user_id, status, interests, destinations, travel_scope, source, created_at, updated_at)
+            VALUES ($1, $2, $3, (SELECT id FROM users WHERE LOWER(email) = $2 LIMIT 1), 'pending', $4, $5, $6, $7, NOW(), NOW())
+            ON CONFLICT (LOWER(email)) DO UPDATE
+            SET first_name = COALESCE(EXCLUDED.first_name, newsletter_subscribers.first_name),
+                user_id = COALESCE(newsletter_subscribers.user_id, EXCLUDED.user_id),
+                status = 'pending',
+                interests = EXCLUDED.interests,
+                destinations = EXCLUDED.destinations,
+                travel_scope = EXCLUDED.travel_scope,
+                source = COALESCE(newsletter_subscribers.source, EXCLUDED.source),
+                unsubscribed_at = NULL
+            WHERE newsletter_subscribers.status <> 'subscribed'
+            RETURNING *
+            "#
+        )
+        .bind(Uuid::new_v4())
+        .bind(&email)
+        .bind(&req.first_name)
+        .bind(normalize_tags(&req.interests))
+        .bind(normalize_tags(&req.destinations))
+        .bind(req.travel_scope.as_deref().unwrap_or("any"))
+        .bind(&req.source)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(subscriber) = subscriber {
+            queue_notification(
+                &mut *tx,
+                subscriber.user_id,
+                &subscriber.email,
+                "newsletter_confirm",
+                "Please confirm your subscription",
+                &format!(
+                    "Hi{}, please confirm that you would like to receive our travel newsletter: {}/newsletter/confirm?token={}\n\nIf you did not sign up, just ignore this email.",
+                    subscriber.first_name.as_deref().map(|name| format!(" {}", name)).unwrap_or_default(),
+                    config.site_url,
+                    token
+                ),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => {
+            Ok(HttpResponse::Accepted().json(serde_json::json!({
+                "message": "Please check your inbox to confirm your subscription"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to subscribe to newsletter: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to subscribe"
+            })))
+        }
+    }
+}
+
+/// Confirms a pending subscription from the emailed link and sends a welcome
+/// email carrying the unsubscribe link.
+async fn confirm_subscription(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    query: web::Query<TokenQuery>,
+) -> Result<HttpResponse> {
+    let email = match verify_newsletter_token(&query.token) {
+        Ok(claims) if claims.action == "confirm" => claims.email,
+        _ => return Ok(invalid_link()),
+    };
+
+    let unsubscribe_token = create_newsletter_token(&email, "unsubscribe").map_err(|e| {
+        log::error!("Newsletter token creation error: {}", e);
+        actix_web::error::ErrorInternalServerError("Failed to create token")
+    })?;
+
+    let result: Result<Option<String>, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let subscriber = sqlx::query_as::<_, NewsletterSubscriber>(
+            r#"
+            UPDATE newsletter_subscribers
+            SET status = 'subscribed', confirmed_at = NOW()
+            WHERE LOWER(email) = $1 AND status = 'pending'
+            RETURNING *
+            "#
+        )
+        .bind(&email)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(subscriber) = subscriber else {
+            let status = sqlx::query_scalar::<_, String>(
+                "SELECT status FROM newsletter_subscribers WHERE LOWER(email) = $1"
+            )
+            .bind(&email)
+            .fetch_optional(&mut *tx)
+            .await?;
+            return Ok(status);
+        };
+
+        let unsubscribe_url = format!("{}/newsletter/unsubscribe?token={}", config.site_url, unsubscribe_token);
+        queue_list_notification(
+            &mut *tx,
+            subscriber.user_id,
+            &subscriber.email,
+            "newsletter_welcome",
+            "You're subscribed to our travel newsletter",
+            &format!(
+                "Thanks for confirming! You'll now hear about our latest packages and offers.\n\nTo stop receiving these emails, unsubscribe here: {}",
+                unsubscribe_url
+            ),
+            &unsubscribe_url,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(subscriber.status))
+    }
+    .await;
+
+    match result {
+        Ok(Some(status)) if status == "subscribed" => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "Your subscription is confirmed"
+            })))
+        }
+        Ok(_) => Ok(invalid_link()),
+        Err(e) => {
+            log::error!("Failed to confirm newsletter subscription: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to confirm subscription"
+            })))
+        }
+    }
+}
+
+/// Unsubscribes the address the link was signed for. Repeating it is harmless.
+async fn unsubscribe(
+    pool: web::Data<PgPool>,
+    query: web::Query<TokenQuery>,
+) -> Result<HttpResponse> {
+    let email = match verify_newsletter_token(&query.token) {
+        Ok(claims) if claims.action == "unsubscribe" => claims.email,
+        _ => return Ok(invalid_link()),
+    };
+
+    let result = sqlx::query(
+        r#"
+        UPDATE newsletter_subscribers
+        SET status = 'unsubscribed', unsubscribed_at = NOW()
+        WHERE LOWER(email) = $1 AND status <> 'unsubscribed'
+        "#
+    )
+    .bind(&email)
+    .execute(pool.get_ref())
+    .await;
+
+    match result {
+        Ok(_) => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "message": "You have been unsubscribed"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to unsubscribe from newsletter: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to unsubscribe"
+            })))
+        }
+    }
+}
+
+async fn describe_confirm_link(query: web::Query<TokenQuery>) -> Result<HttpResponse> {
+    Ok(describe_link(&query.token, "confirm"))
+}
+
+async fn describe_unsubscribe_link(query: web::Query<TokenQuery>) -> Result<HttpResponse> {
+    Ok(describe_link(&query.token, "unsubscribe"))
+}
+
+/// Tells the page behind an emailed link which address and action it is
+/// for, without changing anything.
+fn describe_link(token: &str, action: &str) -> HttpResponse {
+    match verify_newsletter_token(token) {
+        Ok(claims) if claims.action == action => {
+            HttpResponse::Ok().json(serde_json::json!({
+                "email": claims.email,
+                "action": claims.action
+            }))
+        }
+        _ => invalid_link(),
+    }
+}
+
+fn invalid_link() -> HttpResponse {
+    HttpResponse::BadRequest().json(serde_json::json!({
+        "error": "This link is invalid or has expired"
+    }))
+}
@@ -0,0 +1,39 @@
+use actix_web::{web, HttpResponse, Result, Scope};
+use sqlx::PgPool;
+
+use crate::models::Offer;
+use crate::utils::offers::{attach_offer_packages, LIVE_OFFER_SQL};
+
+pub fn offer_routes() -> Scope {
+    web::scope("/offers")
+        .route("", web::get().to(get_live_offers))
+}
+
+/// Offers running right now that apply to at least one bookable package,
+/// ending soonest first.
+async fn get_live_offers(pool: web::Data<PgPool>) -> Result<HttpResponse> {
+    let offers = sqlx::query_as::<_, Offer>(&format!(
+        "SELECT o.* FROM offers o WHERE {} ORDER BY o.ends_at, o.created_at",
+        LIVE_OFFER_SQL
+    ))
+    .fetch_all(pool.get_ref())
+    .await;
+
+    let offers = match offers {
+        Ok(offers) => attach_offer_packages(pool.get_ref(), offers, false).await,
+        Err(e) => Err(e),
+    };
+
+    match offers {
+        Ok(mut offers) => {
+            offers.retain(|offer| !offer.packages.is_empty());
+            Ok(HttpResponse::Ok().json(offers))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch offers: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch offers"
+            })))
+        }
+    }
+}
@@ -1,32 +1,48 @@
 use actix_web::{web, HttpResponse, Result, Scope};
 use sqlx::PgPool;
 use uuid::Uuid;
+use validator::Validate;
+use chrono::Utc;
 
-use crate::models::{Package, PackageResponse};
+use crate::models::{Package, PackageResponse, PackageDetailResponse, JoinWaitlistRequest, WaitlistEntry};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::middleware::locale::Locale;
+use crate::utils::booking_rules::{check_booking_date, package_booking_rules};
+use crate::utils::inventory::{seats_available, variant_seats_available};
+use crate::utils::offers::apply_live_offers;
+use crate::utils::variants::{apply_variant_prices, package_variants, variant_selection_error};
+use crate::utils::package_content::{package_faqs, package_policies};
+use crate::utils::publishing::VISIBLE_PACKAGE_SQL;
+use crate::utils::slug::{resolve_slug_redirect, slug_lookup_key, slug_redirect, SlugEntity};
+use crate::utils::translations::apply_package_translations;
 
 pub fn package_routes() -> Scope {
     web::scope("/packages")
         .route("", web::get().to(get_packages))
         .route("/featured", web::get().to(get_featured_packages))
+        .route("/by-slug/{slug}", web::get().to(get_package_by_slug))
         .route("/{id}", web::get().to(get_package_by_id))
-        .route("/category/{category_id}", web::get().to(get_packages_by_category))
+        .route("/{id}/waitlist", web::post().to(join_waitlist))
+        .route("/category/{category}", web::get().to(get_packages_by_category))
 }
 
 async fn get_packages(
     pool: web::Data<PgPool>,
+    locale: Locale,
     query: web::Query<PaginationQuery>,
 ) -> Result<HttpResponse> {
     let limit = query.limit.unwrap_or(20).min(100);
     let offset = query.offset.unwrap_or(0);
 
-    let packages = sqlx::query_as::<_, Package>(
+    let packages = sqlx::query_as::<_, Package>(&format!(
         r#"
         SELECT p.* FROM packages p
-        WHERE p.is_active = true
+        WHERE {}
         ORDER BY p.created_at DESC
         LIMIT $1 OFFSET $2
-        "#
-    )
+        "#,
+        VISIBLE_PACKAGE_SQL
+    ))
     .bind(limit as i64)
     .bind(offset as i64)
     .fetch_all(pool.get_ref())
@@ -34,22 +50,15 @@ async fn get_packages(
 
     match packages {
         Ok(packages) => {
-            let responses: Vec<PackageResponse> = packages.into_iter().map(|p| PackageResponse {
-                id: p.id,
-                title: p.title,
-                description: p.description,
-                price: p.price,
-                duration_days: p.duration_days,
-                max_people: p.max_people,
-                category: None, // Will be populated with JOIN in production
-                image_url: p.image_url,
-                highlights: p.highlights,
-                inclusions: p.inclusions,
-                exclusions: p.exclusions,
-                itinerary: p.itinerary,
-                is_featured: p.is_featured,
-                created_at: p.created_at,
-            }).collect();
+            let responses = match package_responses(pool.get_ref(), packages, &locale).await {
+                Ok(responses) => responses,
+                Err(e) => {
+                    log::error!("Failed to fetch package offers or translations: {}", e);
+                    return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to fetch packages"
+                    })));
+                }
+            };
 
             Ok(HttpResponse::Ok().json(serde_json::json!({
                 "packages": responses,
@@ -65,36 +74,30 @@ async fn get_packages(
     }
 }
 
-async fn get_featured_packages(pool: web::Data<PgPool>) -> Result<HttpResponse> {
-    let packages = sqlx::query_as::<_, Package>(
+async fn get_featured_packages(pool: web::Data<PgPool>, locale: Locale) -> Result<HttpResponse> {
+    let packages = sqlx::query_as::<_, Package>(&format!(
         r#"
         SELECT p.* FROM packages p
-        WHERE p.is_active = true AND p.is_featured = true
+        WHERE {} AND p.is_featured = true
         ORDER BY p.created_at DESC
         LIMIT 6
-        "#
-    )
+        "#,
+        VISIBLE_PACKAGE_SQL
+    ))
     .fetch_all(pool.get_ref())
     .await;
 
     match packages {
         Ok(packages) => {
-            let responses: Vec<PackageResponse> = packages.into_iter().map(|p| PackageResponse {
-                id: p.id,
-                title: p.title,
-                description: p.description,
-                price: p.price,
-                duration_days: p.duration_days,
-                max_people: p.max_people,
-                category: None,
-                image_url: p.image_url,
-                highlights: p.highlights,
-                inclusions: p.inclusions,
-                exclusions: p.exclusions,
-                itinerary: p.itinerary,
-                is_featured: p.is_featured,
-                created_at: p.created_at,
-            }).collect();
+            let responses = match package_responses(pool.get_ref(), packages, &locale).await {
+                Ok(responses) => responses,
+                Err(e) => {
+                    log::error!("Failed to fetch package offers or translations: {}", e);
+                    return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to fetch featured packages"
+                    })));
+                }
+            };
 
             Ok(HttpResponse::Ok().json(responses))
         }
@@ -109,36 +112,30 @@ async fn get_featured_packages(pool: web::Data<PgPool>) -> Result<HttpResponse>
 
 async fn get_package_by_id(
     pool: web::Data<PgPool>,
+    locale: Locale,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse> {
     let package_id = path.into_inner();
 
-    let package = sqlx::query_as::<_, Package>(
-        "SELECT * FROM packages WHERE id = $1 AND is_active = true"
-    )
+    let package = sqlx::query_as::<_, Package>(&format!(
+        "SELECT p.* FROM packages p WHERE p.id = $1 AND {}",
+        VISIBLE_PACKAGE_SQL
+    ))
     .bind(package_id)
     .fetch_optional(pool.get_ref())
     .await;
 
     match package {
         Ok(Some(package)) => {
-            let response = PackageResponse {
-                id: package.id,
-                title: package.title,
-                description: package.description,
-                price: package.price,
-                duration_days: package.duration_days,
-                max_people: package.max_people,
-                category: None,
-                image_url: package.image_url,
-                highlights: package.highlights,
-                inclusions: package.inclusions,
-                exclusions: package.exclusions,
-                itinerary: package.itinerary,
-                is_featured: package.is_featured,
-                created_at: package.created_at,
-            };
-            Ok(HttpResponse::Ok().json(response))
+            match package_detail(pool.get_ref(), package, &locale).await {
+                Ok(detail) => Ok(HttpResponse::Ok().json(detail)),
+                Err(e) => {
+                    log::error!("Failed to fetch package details: {}", e);
+                    Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to fetch package"
+                    })))
+                }
+            }
         }
         Ok(None) => {
             Ok(HttpResponse::NotFound().json(serde_json::json!({
@@ -154,24 +151,83 @@ async fn get_package_by_id(
     }
 }
 
+/// Looks a package up by its slug. Old slugs answer with a 301 pointing at
+/// the current one.
+async fn get_package_by_slug(
+    pool: web::Data<PgPool>,
+    locale: Locale,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let slug = path.into_inner();
+
+    let package = sqlx::query_as::<_, Package>(&format!(
+        "SELECT p.* FROM packages p WHERE p.slug = $1 AND {}",
+        VISIBLE_PACKAGE_SQL
+    ))
+    .bind(&slug)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    let package = match package {
+        Ok(Some(package)) => package,
+        Ok(None) => {
+            return match resolve_slug_redirect(pool.get_ref(), SlugEntity::Package, &slug).await {
+                Ok(Some(current)) => Ok(slug_redirect(&format!("/api/packages/by-slug/{}", current), &current)),
+                Ok(None) => {
+                    Ok(HttpResponse::NotFound().json(serde_json::json!({
+                        "error": "Package not found"
+                    })))
+                }
+                Err(e) => {
+                    log::error!("Failed to resolve package slug redirect: {}", e);
+                    Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to fetch package"
+                    })))
+                }
+            };
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch package"
+            })));
+        }
+    };
+
+    match package_detail(pool.get_ref(), package, &locale).await {
+        Ok(detail) => Ok(HttpResponse::Ok().json(detail)),
+        Err(e) => {
+            log::error!("Failed to fetch package details: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch package"
+            })))
+        }
+    }
+}
+
+/// Lists a category's packages; the category may be given by id or slug.
 async fn get_packages_by_category(
     pool: web::Data<PgPool>,
-    path: web::Path<Uuid>,
+    locale: Locale,
+    path: web::Path<String>,
     query: web::Query<PaginationQuery>,
 ) -> Result<HttpResponse> {
-    let category_id = path.into_inner();
+    let (category_id, category_slug) = slug_lookup_key(&path.into_inner());
     let limit = query.limit.unwrap_or(20).min(100);
     let offset = query.offset.unwrap_or(0);
 
-    let packages = sqlx::query_as::<_, Package>(
+    let packages = sqlx::query_as::<_, Package>(&format!(
         r#"
         SELECT p.* FROM packages p
-        WHERE p.category_id = $1 AND p.is_active = true
+        JOIN categories c ON p.category_id = c.id
+        WHERE (c.id = $1 OR c.slug = $2) AND {}
         ORDER BY p.created_at DESC
-        LIMIT $2 OFFSET $3
-        "#
-    )
+        LIMIT $3 OFFSET $4
+        "#,
+        VISIBLE_PACKAGE_SQL
+    ))
     .bind(category_id)
+    .bind(&category_slug)
     .bind(limit as i64)
     .bind(offset as i64)
     .fetch_all(pool.get_ref())
@@ -179,22 +235,15 @@ async fn get_packages_by_category(
 
     match packages {
         Ok(packages) => {
-            let responses: Vec<PackageResponse> = packages.into_iter().map(|p| PackageResponse {
-                id: p.id,
-                title: p.title,
-                description: p.description,
-                price: p.price,
-                duration_days: p.duration_days,
-                max_people: p.max_people,
-                category: None,
-                image_url: p.image_url,
-                highlights: p.highlights,
-                inclusions: p.inclusions,
-                exclusions: p.exclusions,
-                itinerary: p.itinerary,
-                is_featured: p.is_featured,
-                created_at: p.created_at,
-            }).collect();
+            let responses = match package_responses(pool.get_ref(), packages, &locale).await {
+                Ok(responses) => responses,
+                Err(e) => {
+                    log::error!("Failed to fetch package offers or translations: {}", e);
+                    return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to fetch packages"
+                    })));
+                }
+            };
 
             Ok(HttpResponse::Ok().json(responses))
         }
@@ -207,6 +256,188 @@ async fn get_packages_by_category(
     }
 }
 
+async fn join_waitlist(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+    req: web::Json<JoinWaitlistRequest>,
+) -> Result<HttpResponse> {
+    let package_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    match package_booking_rules(pool.get_ref(), package_id).await {
+        Ok(rules) => {
+            if let Err(violation) = check_booking_date(rules.as_ref(), req.booking_date, Utc::now().date_naive()) {
+                return Ok(violation.to_response());
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package booking rules: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to join waitlist"
+            })));
+        }
+    }
+
+    match variant_selection_error(pool.get_ref(), package_id, req.variant_id).await {
+        Ok(Some(error)) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": error
+            })));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            log::error!("Failed to check package variant: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to join waitlist"
+            })));
+        }
+    }
+
+    let max_people = sqlx::query_scalar::<_, i32>(&format!(
+        r#"
+        SELECT LEAST(p.max_people, v.max_people)
+        FROM packages p
+        LEFT JOIN package_variants v ON v.id = $2 AND v.package_id = p.id
+        WHERE p.id = $1 AND {}
+        "#,
+        VISIBLE_PACKAGE_SQL
+    ))
+    .bind(package_id)
+    .bind(req.variant_id)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match max_people {
+        Ok(Some(max_people)) if req.party_size > max_people => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Party size exceeds the package capacity"
+            })));
+        }
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Package not found"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to fetch package: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to join waitlist"
+            })));
+        }
+    }
+
+    let available = match seats_available(pool.get_ref(), package_id, req.booking_date).await {
+        Ok(available) => match req.variant_id {
+            Some(variant_id) => variant_seats_available(pool.get_ref(), variant_id, req.booking_date)
+                .await
+                .map(|variant_available| available.min(variant_available)),
+            None => Ok(available),
+        },
+        Err(e) => Err(e),
+    };
+
+    match available {
+        Ok(available) if available >= req.party_size as i64 => {
+            return Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Seats are available on this departure, please book directly",
+                "seats_available": available
+            })));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            log::error!("Failed to check availability: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to join waitlist"
+            })));
+        }
+    }
+
+    let result = sqlx::query_as::<_, WaitlistEntry>(
+        r#"
+        INSERT INTO waitlist_entries (id, package_id, variant_id, user_id, booking_date, party_size, status, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, 'waiting', NOW(), NOW())
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(package_id)
+    .bind(req.variant_id)
+    .bind(user.user_id)
+    .bind(req.booking_date)
+    .bind(req.party_size)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    let entry = match result {
+        Ok(entry) => entry,
+        Err(sqlx::Error::Database(e)) if e.constraint() == Some("idx_waitlist_active_user") => {
+            return Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": "You are already on the waitlist for this departure"
+            })));
+        }
+        Err(e) => {
+            log::error!("Failed to join waitlist: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to join waitlist"
+            })));
+        }
+    };
+
+    let position = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*) FROM waitlist_entries
+        WHERE package_id = $1 AND booking_date = $2 AND status = 'waiting' AND created_at <= $3
+        "#
+    )
+    .bind(package_id)
+    .bind(entry.booking_date)
+    .bind(entry.created_at)
+    .fetch_one(pool.get_ref())
+    .await
+    .unwrap_or_else(|e| {
+        log::error!("Failed to fetch waitlist position: {}", e);
+        0
+    });
+
+    Ok(HttpResponse::Created().json(serde_json::json!({
+        "message": "Added to waitlist",
+        "entry": entry,
+        "position": position
+    })))
+}
+
+/// Public view of packages: variant "from" prices, live offer prices and text
+/// in the requested language.
+async fn package_responses(pool: &PgPool, packages: Vec<Package>, locale: &Locale) -> Result<Vec<PackageResponse>, sqlx::Error> {
+    let mut responses: Vec<PackageResponse> = packages.into_iter().map(PackageResponse::from).collect();
+    apply_variant_prices(pool, &mut responses).await?;
+    apply_live_offers(pool, &mut responses).await?;
+    apply_package_translations(pool, &mut responses, locale).await?;
+    Ok(responses)
+}
+
+/// Public view of one package, with its variants, FAQs and policy sections.
+pub(crate) async fn package_detail(pool: &PgPool, package: Package, locale: &Locale) -> Result<PackageDetailResponse, sqlx::Error> {
+    let package_id = package.id;
+    let mut responses = package_responses(pool, vec![package], locale).await?;
+    let package = responses.remove(0);
+
+    Ok(PackageDetailResponse {
+        variants: package_variants(pool, package_id, &package.inclusions).await?,
+        package,
+        faqs: package_faqs(pool, package_id).await?,
+        policies: package_policies(pool, package_id).await?,
+    })
+}
+
 #[derive(serde::Deserialize)]
 struct PaginationQuery {
     limit: Option<i32>,
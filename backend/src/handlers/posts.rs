@@ -0,0 +1,169 @@
+use actix_web::{web, HttpResponse, Result, Scope};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::{CreatePostRequest, PostResponse, PostSummary};
+use crate::utils::markdown::render_markdown;
+use crate::utils::slug::{resolve_slug_redirect, slug_redirect, slugify, SlugEntity};
+
+/// Posts visible on the public blog, for a query aliasing posts as `po`.
+/// Scheduled posts appear once their publish time has passed.
+pub(crate) const VISIBLE_POST_SQL: &str = "po.status IN ('published', 'scheduled') AND po.published_at <= NOW()";
+
+pub fn post_routes() -> Scope {
+    web::scope("/posts")
+        .route("", web::get().to(get_posts))
+        .route("/{slug}", web::get().to(get_post_by_slug))
+}
+
+#[derive(serde::Deserialize)]
+struct PostListQuery {
+    tag: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+async fn get_posts(
+    pool: web::Data<PgPool>,
+    query: web::Query<PostListQuery>,
+) -> Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(10).clamp(1, 50);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let tag = query.tag.as_deref().map(|tag| tag.trim().to_lowercase());
+
+    let posts = sqlx::query_as::<_, PostSummary>(&format!(
+        r#"
+        SELECT po.id, po.slug, po.title, po.excerpt, po.cover_image_url, po.tags, po.published_at,
+               u.first_name || ' ' || u.last_name as author_name
+        FROM posts po
+        JOIN users u ON po.author_id = u.id
+        WHERE {} AND ($1::TEXT IS NULL OR $1 = ANY(po.tags))
+        ORDER BY po.published_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        VISIBLE_POST_SQL
+    ))
+    .bind(&tag)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM posts po WHERE {} AND ($1::TEXT IS NULL OR $1 = ANY(po.tags))",
+        VISIBLE_POST_SQL
+    ))
+    .bind(&tag)
+    .fetch_one(pool.get_ref())
+    .await;
+
+    match (posts, total) {
+        (Ok(posts), Ok(total)) => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "posts": posts,
+                "total": total
+            })))
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            log::error!("Failed to fetch posts: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch posts"
+            })))
+        }
+    }
+}
+
+async fn get_post_by_slug(
+    pool: web::Data<PgPool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    let slug = path.into_inner();
+
+    let post = sqlx::query_as::<_, PostResponse>(&format!(
+        r#"
+        SELECT po.id, po.slug, po.title, po.excerpt, po.body_html, po.cover_image_url, po.tags,
+               po.published_at, po.meta_title, po.meta_description, po.updated_at,
+               u.first_name || ' ' || u.last_name as author_name
+        FROM posts po
+        JOIN users u ON po.author_id = u.id
+        WHERE po.slug = $1 AND {}
+        "#,
+        VISIBLE_POST_SQL
+    ))
+    .bind(&slug)
+    .fetch_optional(pool.get_ref())
+    .await;
+
+    match post {
+        Ok(Some(post)) => Ok(HttpResponse::Ok().json(post)),
+        Ok(None) => {
+            match resolve_slug_redirect(pool.get_ref(), SlugEntity::Post, &slug).await {
+                Ok(Some(current)) => Ok(slug_redirect(&format!("/api/posts/{}", current), &current)),
+                Ok(None) => {
+                    Ok(HttpResponse::NotFound().json(serde_json::json!({
+                        "error": "Post not found"
+                    })))
+                }
+                Err(e) => {
+                    log::error!("Failed to resolve post slug redirect: {}", e);
+                    Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to fetch post"
+                    })))
+                }
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to fetch post: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch post"
+            })))
+        }
+    }
+}
+
+/// Derived values stored alongside an admin's post request.
+pub(crate) struct PreparedPost {
+    pub slug: String,
+    pub body_html: String,
+    pub tags: Vec<String>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Resolves the slug, renders the body and works out when the post goes live.
+///
+/// Drafts have no publish time, published posts default to now and
+/// scheduled posts need a publish time in the future.
+pub(crate) fn prepare_post(req: &CreatePostRequest) -> Result<PreparedPost, &'static str> {
+    let slug = match &req.slug {
+        Some(slug) => slug.clone(),
+        None => slugify(&req.title),
+    };
+    if slug.is_empty() {
+        return Err("Could not generate a slug from the title; please provide one");
+    }
+
+    let published_at = match req.status.as_str() {
+        "scheduled" => match req.published_at {
+            Some(published_at) if published_at > Utc::now() => Some(published_at),
+            _ => return Err("Scheduled posts need a published_at in the future"),
+        },
+        "published" => Some(req.published_at.unwrap_or_else(Utc::now)),
+        _ => None,
+    };
+
+    let mut tags: Vec<String> = req
+        .tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+
+    Ok(PreparedPost {
+        slug,
+        body_html: render_markdown(&req.body_markdown),
+        tags,
+        published_at,
+    })
+}
@@ -0,0 +1,364 @@
+use actix_web::{web, HttpResponse, Result, Scope};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::handlers::bookings::MAX_REFERENCE_ATTEMPTS;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::{Booking, CreateRfqRequest, CustomerQuoteItem, CustomerQuoteResponse, Rfq, RfqDetailResponse, RfqQuote, TaxRule};
+use crate::utils::notifications::queue_notification;
+use crate::utils::payments::{insert_installments, plan_quote_installments};
+use crate::utils::reference::generate_booking_reference;
+use crate::utils::tax::{applicable_tax_rules, compute_tax, insert_booking_tax_lines};
+
+pub fn rfq_routes() -> Scope {
+    web::scope("/rfqs")
+        .route("", web::post().to(create_rfq))
+        .route("", web::get().to(get_user_rfqs))
+        .route("/{id}", web::get().to(get_rfq))
+        .route("/{id}/quotes/{quote_id}/accept", web::post().to(accept_quote))
+}
+
+async fn create_rfq(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    req: web::Json<CreateRfqRequest>,
+) -> Result<HttpResponse> {
+    if let Err(errors) = req.validate() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Validation failed",
+            "details": errors
+        })));
+    }
+
+    if req.start_date < Utc::now().date_naive() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Start date cannot be in the past"
+        })));
+    }
+    if req.end_date < req.start_date {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "End date cannot be before the start date"
+        })));
+    }
+
+    let result: Result<Rfq, sqlx::Error> = async {
+        let mut tx = pool.begin().await?;
+
+        let rfq = sqlx::query_as::<_, Rfq>(
+            r#"
+            INSERT INTO rfqs (id, user_id, title, event_type, company_name, contact_name, contact_email, contact_phone, city, start_date, end_date, headcount, budget_amount, rooms_required, venue_requirements, notes, status, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, 'submitted', NOW(), NOW())
+            RETURNING *
+            "#
+        )
+        .bind(Uuid::new_v4())
+        .bind(user.user_id)
+        .bind(&req.title)
+        .bind(&req.event_type)
+        .bind(&req.company_name)
+        .bind(&req.contact_name)
+        .bind(&req.contact_email)
+        .bind(&req.contact_phone)
+        .bind(&req.city)
+        .bind(req.start_date)
+        .bind(req.end_date)
+        .bind(req.headcount)
+        .bind(req.budget_amount)
+        .bind(req.rooms_required)
+        .bind(&req.venue_requirements)
+        .bind(&req.notes)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        queue_notification(
+            &mut *tx,
+            Some(user.user_id),
+            &rfq.contact_email,
+            "rfq_received",
+            &format!("We have received your request for {}", rfq.title),
+            &format!(
+                "Hi {}, thank you for your request. Our events team will prepare a quote and get back to you shortly.",
+                rfq.contact_name
+            ),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(rfq)
+    }
+    .await;
+
+    match result {
+        Ok(rfq) => Ok(HttpResponse::Created().json(rfq)),
+        Err(e) => {
+            log::error!("Failed to create RFQ: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to submit request"
+            })))
+        }
+    }
+}
+
+async fn get_user_rfqs(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse> {
+    let rfqs = sqlx::query_as::<_, Rfq>(
+        "SELECT * FROM rfqs WHERE user_id = $1 ORDER BY created_at DESC"
+    )
+    .bind(user.user_id)
+    .fetch_all(pool.get_ref())
+    .await;
+
+    match rfqs {
+        Ok(rfqs) => Ok(HttpResponse::Ok().json(rfqs)),
+        Err(e) => {
+            log::error!("Failed to fetch RFQs: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch requests"
+            })))
+        }
+    }
+}
+
+/// Returns the RFQ with every quote sent to the customer. Drafts, costs and
+/// margins stay internal.
+async fn get_rfq(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse> {
+    let rfq_id = path.into_inner();
+
+    let result: Result<Option<RfqDetailResponse<CustomerQuoteResponse>>, sqlx::Error> = async {
+        let rfq = sqlx::query_as::<_, Rfq>("SELECT * FROM rfqs WHERE id = $1 AND user_id = $2")
+            .bind(rfq_id)
+            .bind(user.user_id)
+            .fetch_optional(pool.get_ref())
+            .await?;
+
+        let Some(rfq) = rfq else {
+            return Ok(None);
+        };
+
+        let mut quotes = sqlx::query_as::<_, CustomerQuoteResponse>(
+            r#"
+            SELECT id, version, status, total_amount, deposit_basis_points, balance_due_days_before,
+                   valid_until, notes, sent_at, accepted_at
+            FROM rfq_quotes
+            WHERE rfq_id = $1 AND status <> 'draft'
+            ORDER BY version DESC
+            "#
+        )
+        .bind(rfq_id)
+        .fetch_all(pool.get_ref())
+        .await?;
+
+        let quote_ids: Vec<Uuid> = quotes.iter().map(|quote| quote.id).collect();
+        let items = sqlx::query_as::<_, CustomerQuoteItem>(
+            r#"
+            SELECT quote_id, position, category, description, quantity, sell_amount as amount
+            FROM rfq_quote_items
+            WHERE quote_id = ANY($1)
+            ORDER BY position
+            "#
+        )
+        .bind(&quote_ids)
+        .fetch_all(pool.get_ref())
+        .await?;
+
+        for item in items {
+            if let Some(quote) = quotes.iter_mut().find(|quote| quote.id == item.quote_id) {
+                quote.items.push(item);
+            }
+        }
+
+        Ok(Some(RfqDetailResponse { rfq, quotes }))
+    }
+    .await;
+
+    match result {
+        Ok(Some(rfq)) => Ok(HttpResponse::Ok().json(rfq)),
+        Ok(None) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Request not found"
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to fetch RFQ: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch request"
+            })))
+        }
+    }
+}
+
+enum QuoteAcceptance {
+    Accepted(Box<Booking>),
+    NotFound,
+    Refused(&'static str),
+}
+
+async fn accept_quote(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse> {
+    let (rfq_id, quote_id) = path.into_inner();
+
+    let mut attempts = 0;
+    let result = loop {
+        attempts += 1;
+        let reference = generate_booking_reference();
+        match try_accept_quote(pool.get_ref(), user.user_id, rfq_id, quote_id, &reference).await {
+            Err(sqlx::Error::Database(e))
+                if attempts < MAX_REFERENCE_ATTEMPTS && e.constraint() == Some("idx_bookings_reference") =>
+            {
+                log::warn!("Booking reference {} already taken, retrying", reference);
+            }
+            result => break result,
+        }
+    };
+
+    match result {
+        Ok(QuoteAcceptance::Accepted(booking)) => {
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "message": "Quote accepted",
+                "booking_id": booking.id,
+                "reference": booking.reference,
+                "total_amount": booking.total_amount,
+                "status": booking.status
+            })))
+        }
+        Ok(QuoteAcceptance::NotFound) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Quote not found"
+            })))
+        }
+        Ok(QuoteAcceptance::Refused(reason)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "error": reason
+            })))
+        }
+        Err(e) => {
+            log::error!("Failed to accept quote: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to accept quote"
+            })))
+        }
+    }
+}
+
+/// Books the event at the quoted total, with the quote's deposit and balance
+/// as installments. The booking stays pending until the first one is paid.
+///
+/// The quoted total is what the client agreed to pay, so the domestic tax
+/// rules that apply to every category are carved out of it as inclusive tax.
+async fn try_accept_quote(
+    pool: &PgPool,
+    user_id: Uuid,
+    rfq_id: Uuid,
+    quote_id: Uuid,
+    reference: &str,
+) -> Result<QuoteAcceptance, sqlx::Error> {
+    let today = Utc::now().date_naive();
+    let tax_rules: Vec<TaxRule> = applicable_tax_rules(pool, None, false)
+        .await?
+        .into_iter()
+        .map(|rule| TaxRule { mode: "inclusive".to_string(), ..rule })
+        .collect();
+    let mut tx = pool.begin().await?;
+
+    // Locking the RFQ serialises acceptance of its quotes
+    let rfq = sqlx::query_as::<_, Rfq>("SELECT * FROM rfqs WHERE id = $1 AND user_id = $2 FOR UPDATE")
+        .bind(rfq_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+    let quote = sqlx::query_as::<_, RfqQuote>(
+        "SELECT * FROM rfq_quotes WHERE id = $1 AND rfq_id = $2 AND status <> 'draft'"
+    )
+    .bind(quote_id)
+    .bind(rfq_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let (Some(rfq), Some(quote)) = (rfq, quote) else {
+        return Ok(QuoteAcceptance::NotFound);
+    };
+
+    if rfq.status != "quoted" {
+        return Ok(QuoteAcceptance::Refused("This request is no longer open for acceptance"));
+    }
+    if quote.status != "sent" {
+        return Ok(QuoteAcceptance::Refused("This quote has been replaced by a newer version"));
+    }
+    if quote.valid_until.is_some_and(|valid_until| valid_until < today) {
+        return Ok(QuoteAcceptance::Refused("This quote has expired"));
+    }
+    if rfq.start_date < today {
+        return Ok(QuoteAcceptance::Refused("The event has already started"));
+    }
+
+    let Some(breakdown) = compute_tax(quote.total_amount, &tax_rules) else {
+        return Ok(QuoteAcceptance::Refused("This quote's total is too large to book"));
+    };
+    let booking = sqlx::query_as::<_, Booking>(
+        r#"
+        INSERT INTO bookings (id, reference, user_id, rfq_id, booking_date, number_of_people, base_amount, tax_amount, total_amount, status, traveller_names, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'pending', '{}', NOW(), NOW())
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(reference)
+    .bind(user_id)
+    .bind(rfq.id)
+    .bind(rfq.start_date)
+    .bind(rfq.headcount)
+    .bind(breakdown.base_amount)
+    .bind(breakdown.tax_amount)
+    .bind(breakdown.total_amount)
+    .fetch_one(&mut *tx)
+    .await?;
+    insert_booking_tax_lines(&mut tx, booking.id, &breakdown.tax_lines).await?;
+
+    let plans = plan_quote_installments(
+        quote.total_amount,
+        quote.deposit_basis_points,
+        quote.balance_due_days_before,
+        rfq.start_date,
+        today,
+    );
+    insert_installments(&mut tx, booking.id, &plans).await?;
+
+    sqlx::query("UPDATE rfq_quotes SET status = 'accepted', accepted_at = NOW() WHERE id = $1")
+        .bind(quote.id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("UPDATE rfqs SET status = 'accepted', booking_id = $2 WHERE id = $1")
+        .bind(rfq.id)
+        .bind(booking.id)
+        .execute(&mut *tx)
+        .await?;
+
+    queue_notification(
+        &mut *tx,
+        Some(user_id),
+        &rfq.contact_email,
+        "rfq_quote_accepted",
+        &format!("Your booking {} for {}", booking.reference, rfq.title),
+        &format!(
+            "Thank you for accepting quote v{} for {}. Your booking reference is {}.",
+            quote.version, rfq.title, booking.reference
+        ),
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(QuoteAcceptance::Accepted(Box::new(booking)))
+}
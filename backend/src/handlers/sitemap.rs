@@ -0,0 +1,93 @@
+use actix_web::{web, HttpResponse, Result};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::handlers::posts::VISIBLE_POST_SQL;
+use crate::utils::publishing::VISIBLE_PACKAGE_SQL;
+
+struct SitemapUrl {
+    loc: String,
+    last_modified: Option<DateTime<Utc>>,
+}
+
+/// `sitemap.xml` for the public site: the home page, every published package,
+/// every active category, and every published blog post.
+pub async fn get_sitemap(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+) -> Result<HttpResponse> {
+    let result: Result<Vec<SitemapUrl>, sqlx::Error> = async {
+        let packages = sqlx::query_as::<_, (String, DateTime<Utc>)>(&format!(
+            "SELECT p.slug, p.updated_at FROM packages p WHERE {} ORDER BY p.slug",
+            VISIBLE_PACKAGE_SQL
+        ))
+        .fetch_all(pool.get_ref())
+        .await?;
+
+        let categories = sqlx::query_as::<_, (String, DateTime<Utc>)>(
+            "SELECT slug, updated_at FROM categories WHERE is_active = true ORDER BY slug"
+        )
+        .fetch_all(pool.get_ref())
+        .await?;
+
+        let posts = sqlx::query_as::<_, (String, DateTime<Utc>)>(&format!(
+            "SELECT po.slug, po.updated_at FROM posts po WHERE {} ORDER BY po.slug",
+            VISIBLE_POST_SQL
+        ))
+        .fetch_all(pool.get_ref())
+        .await?;
+
+        let mut urls = vec![SitemapUrl {
+            loc: format!("{}/", config.site_url),
+            last_modified: None,
+        }];
+        for (path, rows) in [("package", packages), ("category", categories), ("blog", posts)] {
+            urls.extend(rows.into_iter().map(|(slug, updated_at)| SitemapUrl {
+                loc: format!("{}/{}/{}", config.site_url, path, slug),
+                last_modified: Some(updated_at),
+            }));
+        }
+        Ok(urls)
+    }
+    .await;
+
+    match result {
+        Ok(urls) => {
+            Ok(HttpResponse::Ok()
+                .content_type("application/xml; charset=utf-8")
+                .body(render_sitemap(&urls)))
+        }
+        Err(e) => {
+            log::error!("Failed to generate sitemap: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to generate sitemap"
+            })))
+        }
+    }
+}
+
+fn render_sitemap(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for url in urls {
+        xml.push_str("  <url>\n    <loc>");
+        xml.push_str(&escape_xml(&url.loc));
+        xml.push_str("</loc>\n");
+        if let Some(last_modified) = url.last_modified {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", last_modified.format("%Y-%m-%d")));
+        }
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
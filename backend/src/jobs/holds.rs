@@ -0,0 +1,111 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::jobs::waitlist::{close_offers, offer_freed_seats};
+
+/// Periodically expires unpaid bookings whose hold has lapsed, which returns
+/// their seats to the departure's availability and offers them to the waitlist.
+pub fn spawn_hold_sweeper(pool: PgPool, config: Config) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(config.hold_sweep_interval_secs));
+        loop {
+            ticker.tick().await;
+            match expire_holds(&pool, config.waitlist_offer_hours).await {
+                Ok(0) => {}
+                Ok(expired) => log::info!("Expired {} unpaid booking hold(s)", expired),
+                Err(e) => log::error!("Failed to expire booking holds: {}", e),
+            }
+        }
+    });
+}
+
+/// Part-paid bookings are left to the payment sweeper, which cancels them
+/// once their final installment is overdue.
+async fn expire_holds(pool: &PgPool, offer_hours: i64) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let expired = sqlx::query_as::<_, (Uuid, Option<Uuid>, NaiveDate)>(
+        r#"
+        UPDATE bookings
+        SET status = 'expired', updated_at = NOW()
+        WHERE status = 'pending' AND hold_expires_at IS NOT NULL AND hold_expires_at <= NOW()
+          AND amount_paid = 0
+        RETURNING id, package_id, booking_date
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let booking_ids: Vec<Uuid> = expired.iter().map(|(id, _, _)| *id).collect();
+    close_offers(&mut *tx, &booking_ids, "expired").await?;
+    tx.commit().await?;
+
+    // Bookings from RFQ quotes hold no package seats
+    let mut departures: Vec<(Uuid, NaiveDate)> = expired.iter().filter_map(|(_, p, d)| p.map(|p| (p, *d))).collect();
+    departures.sort();
+    departures.dedup();
+
+    for (package_id, booking_date) in departures {
+        offer_freed_seats(pool, offer_hours, package_id, booking_date).await?;
+    }
+
+    Ok(booking_ids.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL pointing at a migrated database"]
+    async fn part_paid_holds_are_not_expired() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+
+        let package_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO packages (id, title, description, price, duration_days, max_people, slug, status)
+            VALUES ($1, 'Hold sweep test', 'Test package', 1000, 1, 10, $2, 'published')
+            "#
+        )
+        .bind(package_id)
+        .bind(format!("hold-sweep-test-{}", package_id))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut bookings = Vec::new();
+        for (reference, amount_paid) in [("WMT-HOLDT0", 0), ("WMT-HOLDT1", 100)] {
+            let id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO bookings (reference, package_id, booking_date, number_of_people, base_amount, total_amount, amount_paid, status, hold_expires_at)
+                VALUES ($1, $2, CURRENT_DATE + 30, 1, 1000, 1000, $3, 'pending', NOW() - INTERVAL '1 minute')
+                RETURNING id
+                "#
+            )
+            .bind(reference)
+            .bind(package_id)
+            .bind(amount_paid)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            bookings.push(id);
+        }
+
+        expire_holds(&pool, 24).await.unwrap();
+
+        let statuses: Vec<String> = sqlx::query_scalar("SELECT status FROM bookings WHERE id = ANY($1) ORDER BY reference")
+            .bind(&bookings)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM bookings WHERE package_id = $1").bind(package_id).execute(&pool).await.unwrap();
+        sqlx::query("DELETE FROM packages WHERE id = $1").bind(package_id).execute(&pool).await.unwrap();
+
+        assert_eq!(statuses, vec!["expired", "pending"]);
+    }
+}
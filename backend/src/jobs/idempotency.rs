@@ -0,0 +1,25 @@
+use sqlx::PgPool;
+use std::time::Duration;
+
+const PURGE_INTERVAL_SECS: u64 = 3600;
+
+/// Periodically deletes stored idempotent responses whose TTL has passed.
+pub fn spawn_idempotency_purger(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(PURGE_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            let purged = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= NOW()")
+                .execute(&pool)
+                .await;
+
+            match purged {
+                Ok(result) if result.rows_affected() > 0 => {
+                    log::info!("Purged {} expired idempotency key(s)", result.rows_affected())
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("Failed to purge idempotency keys: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,5 @@
+pub mod holds;
+pub mod idempotency;
+pub mod notifications;
+pub mod payments;
+pub mod waitlist;
@@ -0,0 +1,143 @@
+use lettre::message::header::{ContentType, HeaderName, HeaderValue};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config::Config;
+
+const BATCH_SIZE: i64 = 50;
+const MAX_ATTEMPTS: i32 = 5;
+
+type Mailer = AsyncSmtpTransport<Tokio1Executor>;
+
+/// Periodically sends queued notifications over SMTP. Without `SMTP_HOST`
+/// the dispatcher is not started and notifications stay queued.
+pub fn spawn_notification_dispatcher(pool: PgPool, config: Config) {
+    let Some(host) = config.smtp_host.clone() else {
+        log::warn!("SMTP_HOST is not set; queued notifications will not be sent");
+        return;
+    };
+
+    let from = match config.mail_from.parse::<Mailbox>() {
+        Ok(from) => from,
+        Err(e) => {
+            log::error!("MAIL_FROM '{}' is not a valid address: {}", config.mail_from, e);
+            return;
+        }
+    };
+
+    let builder = if config.smtp_starttls {
+        match Mailer::starttls_relay(&host) {
+            Ok(builder) => builder,
+            Err(e) => {
+                log::error!("Failed to set up SMTP relay {}: {}", host, e);
+                return;
+            }
+        }
+    } else {
+        Mailer::builder_dangerous(&host)
+    };
+    let mut builder = builder.port(config.smtp_port);
+    if let (Some(username), Some(password)) = (config.smtp_username.clone(), config.smtp_password.clone()) {
+        builder = builder.credentials(Credentials::new(username, password));
+    }
+    let mailer = builder.build();
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(config.notification_sweep_interval_secs));
+        loop {
+            ticker.tick().await;
+            match send_queued(&pool, &mailer, &from).await {
+                Ok(0) => {}
+                Ok(sent) => log::info!("Sent {} notification(s)", sent),
+                Err(e) => log::error!("Failed to send queued notifications: {}", e),
+            }
+        }
+    });
+}
+
+/// Sends one batch of due notifications. Rows are locked while they are sent so
+/// several instances never deliver the same notification. A failed send is
+/// retried with a growing delay and given up after `MAX_ATTEMPTS`.
+async fn send_queued(pool: &PgPool, mailer: &Mailer, from: &Mailbox) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let queued = sqlx::query_as::<_, (Uuid, String, String, String, Option<String>, i32)>(
+        r#"
+        SELECT id, email, subject, body, list_unsubscribe_url, attempts
+        FROM notifications
+        WHERE status = 'queued' AND next_attempt_at <= NOW()
+        ORDER BY created_at
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .bind(BATCH_SIZE)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut sent = 0;
+    for (id, email, subject, body, unsubscribe_url, attempts) in queued {
+        let delivery = match build_message(from, &email, &subject, body, unsubscribe_url.as_deref()) {
+            Ok(message) => mailer.send(message).await.map(|_| ()).map_err(|e| (e.to_string(), e.is_permanent())),
+            Err(e) => Err((e, true)),
+        };
+
+        match delivery {
+            Ok(()) => {
+                sqlx::query("UPDATE notifications SET status = 'sent', sent_at = NOW(), attempts = attempts + 1 WHERE id = $1")
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                sent += 1;
+            }
+            Err((error, permanent)) => {
+                let give_up = permanent || attempts + 1 >= MAX_ATTEMPTS;
+                log::warn!("Failed to send notification {} to {}: {}", id, email, error);
+                sqlx::query(
+                    r#"
+                    UPDATE notifications
+                    SET status = CASE WHEN $3 THEN 'failed' ELSE 'queued' END,
+                        attempts = attempts + 1,
+                        last_error = $2,
+                        next_attempt_at = NOW() + INTERVAL '1 minute' * POWER(2, attempts)
+                    WHERE id = $1
+                    "#
+                )
+                .bind(id)
+                .bind(&error)
+                .bind(give_up)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(sent)
+}
+
+fn build_message(
+    from: &Mailbox,
+    email: &str,
+    subject: &str,
+    body: String,
+    unsubscribe_url: Option<&str>,
+) -> Result<Message, String> {
+    let to = email.parse::<Mailbox>().map_err(|e| format!("invalid recipient: {}", e))?;
+    let mut builder = Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN);
+    if let Some(url) = unsubscribe_url {
+        builder = builder.raw_header(HeaderValue::new(
+            HeaderName::new_from_ascii_str("List-Unsubscribe"),
+            format!("<{}>", url),
+        ));
+    }
+    builder.body(body).map_err(|e| e.to_string())
+}
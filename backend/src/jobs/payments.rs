@@ -0,0 +1,129 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::jobs::waitlist::{close_offers, offer_freed_seats};
+use crate::utils::notifications::queue_notification;
+
+/// Periodically reminds customers of upcoming installments and cancels
+/// bookings still unpaid after their final due date plus the grace period.
+pub fn spawn_payment_sweeper(pool: PgPool, config: Config) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(config.payment_sweep_interval_secs));
+        loop {
+            ticker.tick().await;
+            match send_due_reminders(&pool, config.payment_reminder_days).await {
+                Ok(0) => {}
+                Ok(sent) => log::info!("Queued {} payment reminder(s)", sent),
+                Err(e) => log::error!("Failed to queue payment reminders: {}", e),
+            }
+            match cancel_overdue_bookings(&pool, config.payment_grace_days, config.waitlist_offer_hours).await {
+                Ok(0) => {}
+                Ok(cancelled) => log::info!("Cancelled {} overdue booking(s)", cancelled),
+                Err(e) => log::error!("Failed to cancel overdue bookings: {}", e),
+            }
+        }
+    });
+}
+
+async fn send_due_reminders(pool: &PgPool, reminder_days: i64) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let due = sqlx::query_as::<_, (Uuid, Uuid, String, String, String, i32, NaiveDate)>(
+        r#"
+        SELECT i.id, u.id, u.email, b.reference, i.label, i.amount - i.paid_amount, i.due_date
+        FROM booking_installments i
+        JOIN bookings b ON i.booking_id = b.id
+        JOIN users u ON b.user_id = u.id
+        WHERE i.paid_amount < i.amount
+          AND i.reminder_sent_at IS NULL
+          AND i.due_date <= CURRENT_DATE + $1::INTEGER
+          AND b.status IN ('pending', 'confirmed')
+        FOR UPDATE OF i SKIP LOCKED
+        "#
+    )
+    .bind(reminder_days as i32)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for (installment_id, user_id, email, reference, label, amount_due, due_date) in &due {
+        queue_notification(
+            &mut *tx,
+            Some(*user_id),
+            email,
+            "payment_reminder",
+            &format!("Payment due for booking {}", reference),
+            &format!(
+                "This is a reminder that the {} of INR {} for booking {} is due on {}.",
+                label.to_lowercase(),
+                amount_due,
+                reference,
+                due_date.format("%d %b %Y"),
+            ),
+        )
+        .await?;
+
+        sqlx::query("UPDATE booking_installments SET reminder_sent_at = NOW() WHERE id = $1")
+            .bind(installment_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(due.len())
+}
+
+async fn cancel_overdue_bookings(pool: &PgPool, grace_days: i64, offer_hours: i64) -> Result<usize, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let cancelled = sqlx::query_as::<_, (Uuid, Option<Uuid>, NaiveDate, Uuid, String, String)>(
+        r#"
+        UPDATE bookings b
+        SET status = 'cancelled', hold_expires_at = NULL, updated_at = NOW()
+        FROM users u
+        WHERE b.user_id = u.id
+          AND b.status IN ('pending', 'confirmed')
+          AND b.amount_paid < b.total_amount
+          AND (
+              SELECT MAX(i.due_date) FROM booking_installments i WHERE i.booking_id = b.id
+          ) + $1::INTEGER < CURRENT_DATE
+        RETURNING b.id, b.package_id, b.booking_date, u.id, u.email, b.reference
+        "#
+    )
+    .bind(grace_days as i32)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let booking_ids: Vec<Uuid> = cancelled.iter().map(|row| row.0).collect();
+    close_offers(&mut *tx, &booking_ids, "declined").await?;
+
+    for (_, _, _, user_id, email, reference) in &cancelled {
+        queue_notification(
+            &mut *tx,
+            Some(*user_id),
+            email,
+            "booking_cancelled_unpaid",
+            &format!("Booking {} has been cancelled", reference),
+            &format!(
+                "Booking {} was cancelled because the outstanding balance was not paid by the final due date.",
+                reference
+            ),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    // Bookings from RFQ quotes hold no package seats
+    let mut departures: Vec<(Uuid, NaiveDate)> = cancelled.iter().filter_map(|row| row.1.map(|p| (p, row.2))).collect();
+    departures.sort();
+    departures.dedup();
+
+    for (package_id, booking_date) in departures {
+        offer_freed_seats(pool, offer_hours, package_id, booking_date).await?;
+    }
+
+    Ok(booking_ids.len())
+}
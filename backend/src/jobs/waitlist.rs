@@ -0,0 +1,188 @@
+use chrono::{Duration, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::handlers::bookings::{insert_booking_in, quote_package, BookingInsert, PackageQuote};
+use crate::models::{CreateBookingRequest, WaitlistEntry};
+use crate::utils::inventory::seats_available;
+use crate::utils::notifications::queue_notification;
+
+/// Runs `offer_freed_seats` in the background so the triggering request
+/// does not wait on it.
+pub fn spawn_offer_freed_seats(pool: PgPool, offer_hours: i64, package_id: Uuid, booking_date: NaiveDate) {
+    tokio::spawn(async move {
+        if let Err(e) = offer_freed_seats(&pool, offer_hours, package_id, booking_date).await {
+            log::error!("Failed to process waitlist for package {} on {}: {}", package_id, booking_date, e);
+        }
+    });
+}
+
+/// Same as `spawn_offer_freed_seats` for every upcoming departure of a package
+/// with someone waiting, e.g. after its capacity was raised.
+pub fn spawn_offer_freed_seats_for_package(pool: PgPool, offer_hours: i64, package_id: Uuid) {
+    tokio::spawn(async move {
+        let dates = sqlx::query_scalar::<_, NaiveDate>(
+            r#"
+            SELECT DISTINCT booking_date FROM waitlist_entries
+            WHERE package_id = $1 AND status = 'waiting' AND booking_date >= CURRENT_DATE
+            ORDER BY booking_date
+            "#
+        )
+        .bind(package_id)
+        .fetch_all(&pool)
+        .await;
+
+        match dates {
+            Ok(dates) => {
+                for booking_date in dates {
+                    if let Err(e) = offer_freed_seats(&pool, offer_hours, package_id, booking_date).await {
+                        log::error!("Failed to process waitlist for package {} on {}: {}", package_id, booking_date, e);
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to fetch waitlisted departures for package {}: {}", package_id, e),
+        }
+    });
+}
+
+/// Offers free seats on a departure to waitlisted customers in FIFO order.
+///
+/// The earliest entry whose party fits the free seats, and those left at its
+/// variant, gets a pending booking at that variant held for `offer_hours`;
+/// this repeats until nothing else fits. Entries without a variant are skipped
+/// while the package has active variants. The entry is
+/// locked, and the booking, the entry update and the notification commit
+/// together, so concurrent runs never offer the same entry twice.
+pub async fn offer_freed_seats(
+    pool: &PgPool,
+    offer_hours: i64,
+    package_id: Uuid,
+    booking_date: NaiveDate,
+) -> Result<(), sqlx::Error> {
+    loop {
+        let mut tx = pool.begin().await?;
+
+        let available = seats_available(&mut *tx, package_id, booking_date).await?;
+        if available <= 0 {
+            return Ok(());
+        }
+
+        let entry = sqlx::query_as::<_, WaitlistEntry>(
+            r#"
+            SELECT * FROM waitlist_entries w
+            WHERE w.package_id = $1 AND w.booking_date = $2 AND w.status = 'waiting'
+              AND w.party_size <= $3 AND w.booking_date >= CURRENT_DATE
+              AND CASE WHEN w.variant_id IS NULL
+                  THEN NOT EXISTS (SELECT 1 FROM package_variants v WHERE v.package_id = w.package_id AND v.is_active)
+                  ELSE w.party_size <= (
+                      SELECT v.max_people - COALESCE((
+                          SELECT SUM(b.number_of_people) FROM bookings b
+                          WHERE b.variant_id = v.id AND b.booking_date = w.booking_date
+                            AND b.status IN ('pending', 'confirmed')
+                      ), 0)
+                      FROM package_variants v
+                      WHERE v.id = w.variant_id AND v.is_active
+                  )
+              END
+            ORDER BY w.created_at
+            LIMIT 1
+            FOR UPDATE OF w SKIP LOCKED
+            "#
+        )
+        .bind(package_id)
+        .bind(booking_date)
+        .bind(available as i32)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(entry) = entry else {
+            return Ok(());
+        };
+
+        let PackageQuote::Quoted(quote) = quote_package(pool, package_id, entry.variant_id, entry.party_size).await? else {
+            // Package is no longer bookable; nothing on this departure can be offered
+            return Ok(());
+        };
+
+        let req = CreateBookingRequest {
+            package_id,
+            variant_id: entry.variant_id,
+            booking_date,
+            number_of_people: entry.party_size,
+            special_requests: None,
+            traveller_names: None,
+        };
+        let offer_expires_at = Utc::now() + Duration::hours(offer_hours);
+
+        let booking = match insert_booking_in(&mut tx, entry.user_id, &req, &quote, offer_expires_at).await? {
+            BookingInsert::Created(booking) => booking,
+            // Someone else took the seats in the meantime
+            BookingInsert::SoldOut { .. } => return Ok(()),
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE waitlist_entries
+            SET status = 'offered', offer_booking_id = $2, offer_expires_at = $3, updated_at = NOW()
+            WHERE id = $1
+            "#
+        )
+        .bind(entry.id)
+        .bind(booking.id)
+        .bind(offer_expires_at)
+        .execute(&mut *tx)
+        .await?;
+
+        let recipient = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT u.email, p.title FROM users u, packages p
+            WHERE u.id = $1 AND p.id = $2
+            "#
+        )
+        .bind(entry.user_id)
+        .bind(package_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let (email, package_title) = recipient;
+        queue_notification(
+            &mut *tx,
+            Some(entry.user_id),
+            &email,
+            "waitlist_offer",
+            &format!("Seats are available for {}", package_title),
+            &format!(
+                "Good news! {} seat(s) opened up for {} on {}. We are holding booking {} for you until {} UTC. Complete payment before then to confirm it.",
+                entry.party_size,
+                package_title,
+                booking_date.format("%d %b %Y"),
+                booking.reference,
+                offer_expires_at.format("%d %b %Y %H:%M"),
+            ),
+        )
+        .await?;
+
+        tx.commit().await?;
+        log::info!("Offered booking {} to waitlist entry {}", booking.reference, entry.id);
+    }
+}
+
+/// Moves waitlist entries whose offer bookings were settled to their final state.
+pub async fn close_offers<'e, E: sqlx::PgExecutor<'e>>(
+    executor: E,
+    booking_ids: &[Uuid],
+    status: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE waitlist_entries SET status = $2, updated_at = NOW()
+        WHERE offer_booking_id = ANY($1) AND status = 'offered'
+        "#
+    )
+    .bind(booking_ids)
+    .bind(status)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
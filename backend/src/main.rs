@@ -4,21 +4,24 @@ mod handlers;
 mod middleware;
 mod utils;
 mod database;
+mod jobs;
 
 use actix_web::{web, App, HttpServer, middleware::Logger};
 use actix_cors::Cors;
 use sqlx::PgPool;
 use std::env;
 
+use config::Config;
+use middleware::idempotency::Idempotency;
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init();
     dotenv::dotenv().ok();
 
-    let database_url = env::var("DATABASE_URL")
-        .expect("DATABASE_URL must be set");
-    
-    let pool = PgPool::connect(&database_url)
+    let config = Config::from_env();
+
+    let pool = PgPool::connect(&config.database_url)
         .await
         .expect("Failed to connect to Postgres");
 
@@ -28,6 +31,11 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to run migrations");
 
+    jobs::holds::spawn_hold_sweeper(pool.clone(), config.clone());
+    jobs::payments::spawn_payment_sweeper(pool.clone(), config.clone());
+    jobs::idempotency::spawn_idempotency_purger(pool.clone());
+    jobs::notifications::spawn_notification_dispatcher(pool.clone(), config.clone());
+
     let bind_address = env::var("BIND_ADDRESS").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
 
     log::info!("Starting server at http://{}", bind_address);
@@ -36,21 +44,31 @@ async fn main() -> std::io::Result<()> {
         let cors = Cors::default()
             .allowed_origin("http://localhost:3000")
             .allowed_origin("http://127.0.0.1:3000")
-            .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
-            .allowed_headers(vec!["Content-Type", "Authorization"])
+            .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"])
+            .allowed_headers(vec!["Content-Type", "Authorization", "Idempotency-Key", "If-Match"])
+            .expose_headers(vec!["Idempotent-Replayed", "X-Total-Count", "Content-Disposition", "ETag"])
             .supports_credentials();
 
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::new(config.clone()))
             .wrap(cors)
             .wrap(Logger::default())
             .service(
                 web::scope("/api")
+                    .wrap(Idempotency)
                     .service(handlers::auth::auth_routes())
                     .service(handlers::packages::package_routes())
+                    .service(handlers::categories::category_routes())
                     .service(handlers::bookings::booking_routes())
+                    .service(handlers::posts::post_routes())
+                    .service(handlers::offers::offer_routes())
+                    .service(handlers::enquiries::enquiry_routes())
+                    .service(handlers::newsletter::newsletter_routes())
+                    .service(handlers::rfqs::rfq_routes())
                     .service(handlers::admin::admin_routes())
             )
+            .route("/sitemap.xml", web::get().to(handlers::sitemap::get_sitemap))
     })
     .bind(&bind_address)?
     .run()
@@ -20,9 +20,7 @@ impl FromRequest for AuthenticatedUser {
         
         if let Some(auth_header) = auth_header {
             if let Ok(auth_str) = auth_header.to_str() {
-                if auth_str.starts_with("Bearer ") {
-                    let token = &auth_str[7..];
-                    
+                if let Some(token) = auth_str.strip_prefix("Bearer ") {
                     match verify_jwt(token) {
                         Ok(claims) => {
                             if let Ok(user_id) = Uuid::from_str(&claims.sub) {
@@ -43,7 +41,6 @@ impl FromRequest for AuthenticatedUser {
 
 #[derive(Debug, Clone)]
 pub struct AdminOnly {
-    #[allow(dead_code)]
     pub user_id: Uuid,
 }
 
@@ -57,9 +54,7 @@ impl FromRequest for AdminOnly {
         
         if let Some(auth_header) = auth_header {
             if let Ok(auth_str) = auth_header.to_str() {
-                if auth_str.starts_with("Bearer ") {
-                    let token = &auth_str[7..];
-                    
+                if let Some(token) = auth_str.strip_prefix("Bearer ") {
                     match verify_jwt(token) {
                         Ok(claims) => {
                             if let Ok(user_id) = Uuid::from_str(&claims.sub) {
@@ -0,0 +1,324 @@
+use actix_web::body::{self, BoxBody, MessageBody};
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::http::{Method, StatusCode};
+use actix_web::{web, Error, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+use crate::config::Config;
+use crate::handlers::admin::IMPORT_PAYLOAD_LIMIT;
+
+const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+const MAX_KEY_LENGTH: usize = 255;
+/// The largest body any route accepts, so buffering here never refuses a
+/// request the route itself would take.
+const MAX_BODY_LENGTH: usize = IMPORT_PAYLOAD_LIMIT;
+
+/// Honours the `Idempotency-Key` header on POST requests.
+///
+/// The first request with a key is executed and its response stored for
+/// `IDEMPOTENCY_KEY_TTL_HOURS`. A retry with the same key and body gets that
+/// response replayed; the same key with a different body is rejected with 422.
+/// A key whose request never finished (say the process died mid-request) can
+/// be claimed again by a retry once `IDEMPOTENCY_LEASE_SECS` have passed.
+/// Keys are scoped to the caller's `Authorization` header so users cannot
+/// replay each other's responses.
+pub struct Idempotency;
+
+impl<S, B> Transform<S, ServiceRequest> for Idempotency
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = IdempotencyMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct IdempotencyMiddleware<S> {
+    service: Rc<S>,
+}
+
+/// What was found (or reserved) for an incoming key.
+enum KeyState {
+    Reserved { claimed_at: DateTime<Utc> },
+    InFlight,
+    Mismatch,
+    Completed {
+        status_code: i16,
+        content_type: Option<String>,
+        body: Vec<u8>,
+    },
+}
+
+impl<S, B> Service<ServiceRequest> for IdempotencyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let key = match req.headers().get(IDEMPOTENCY_HEADER) {
+                Some(key) if req.method() == Method::POST => key.to_str().map(str::to_owned),
+                _ => return Ok(service.call(req).await?.map_into_boxed_body()),
+            };
+
+            let key = match key {
+                Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key,
+                _ => {
+                    let response = HttpResponse::BadRequest().json(serde_json::json!({
+                        "error": format!("{} must be 1-{} visible ASCII characters", IDEMPOTENCY_HEADER, MAX_KEY_LENGTH)
+                    }));
+                    return Ok(req.into_response(response));
+                }
+            };
+
+            let (Some(pool), Some(config)) = (
+                req.app_data::<web::Data<PgPool>>().cloned(),
+                req.app_data::<web::Data<Config>>().cloned(),
+            ) else {
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            // Buffer the body so it can be fingerprinted and then handed on unchanged.
+            // The route's own extractor still applies its own, smaller limit.
+            let payload = match req.extract::<web::Payload>().await {
+                Ok(payload) => payload,
+                Err(e) => return Ok(req.error_response(e)),
+            };
+            let payload = match payload.to_bytes_limited(MAX_BODY_LENGTH).await {
+                Ok(Ok(payload)) => payload,
+                Ok(Err(e)) => return Ok(req.error_response(e)),
+                Err(_) => {
+                    let response = HttpResponse::PayloadTooLarge().json(serde_json::json!({
+                        "error": "Request body is too large"
+                    }));
+                    return Ok(req.into_response(response));
+                }
+            };
+            let scope = caller_scope(&req);
+            let fingerprint = request_fingerprint(&req, &payload);
+            req.set_payload(payload.into());
+
+            let claimed_at = match reserve_key(
+                &pool,
+                &key,
+                &scope,
+                &fingerprint,
+                config.idempotency_key_ttl_hours,
+                config.idempotency_lease_secs,
+            )
+            .await
+            {
+                Ok(KeyState::Reserved { claimed_at }) => claimed_at,
+                Ok(KeyState::InFlight) => {
+                    let response = HttpResponse::Conflict().json(serde_json::json!({
+                        "error": "A request with this Idempotency-Key is still being processed"
+                    }));
+                    return Ok(req.into_response(response));
+                }
+                Ok(KeyState::Mismatch) => {
+                    let response = HttpResponse::UnprocessableEntity().json(serde_json::json!({
+                        "error": "Idempotency-Key was already used with a different request"
+                    }));
+                    return Ok(req.into_response(response));
+                }
+                Ok(KeyState::Completed { status_code, content_type, body }) => {
+                    let status = StatusCode::from_u16(status_code as u16).unwrap_or(StatusCode::OK);
+                    let mut response = HttpResponse::build(status);
+                    if let Some(content_type) = content_type {
+                        response.insert_header((header::CONTENT_TYPE, content_type));
+                    }
+                    response.insert_header(("Idempotent-Replayed", "true"));
+                    return Ok(req.into_response(response.body(body)));
+                }
+                Err(e) => {
+                    log::error!("Failed to reserve idempotency key: {}", e);
+                    let response = HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to process request"
+                    }));
+                    return Ok(req.into_response(response));
+                }
+            };
+
+            let response = match service.call(req).await {
+                Ok(response) => response,
+                Err(e) => {
+                    release_key(&pool, &key, &scope, claimed_at).await;
+                    return Err(e);
+                }
+            };
+
+            let (req, response) = response.into_parts();
+            let (head, response_body) = response.into_parts();
+            let response_body = match body::to_bytes(response_body).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    release_key(&pool, &key, &scope, claimed_at).await;
+                    return Err(actix_web::error::ErrorInternalServerError(e.into().to_string()));
+                }
+            };
+
+            // Server errors are not remembered so the client can retry them
+            if head.status().is_server_error() {
+                release_key(&pool, &key, &scope, claimed_at).await;
+            } else {
+                let content_type = head
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok());
+                if let Err(e) = store_response(&pool, &key, &scope, claimed_at, head.status(), content_type, &response_body).await {
+                    log::error!("Failed to store idempotent response: {}", e);
+                }
+            }
+
+            let response = head.set_body(response_body).map_into_boxed_body();
+            Ok(ServiceResponse::new(req, response))
+        })
+    }
+}
+
+fn caller_scope(req: &ServiceRequest) -> String {
+    let authorization = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .map(HeaderValue::as_bytes)
+        .unwrap_or_default();
+    format!("{:x}", Sha256::digest(authorization))
+}
+
+fn request_fingerprint(req: &ServiceRequest, payload: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(req.method().as_str());
+    hasher.update(b"\n");
+    hasher.update(req.uri().to_string());
+    hasher.update(b"\n");
+    hasher.update(payload);
+    format!("{:x}", hasher.finalize())
+}
+
+async fn reserve_key(
+    pool: &PgPool,
+    key: &str,
+    scope: &str,
+    fingerprint: &str,
+    ttl_hours: i64,
+    lease_secs: i64,
+) -> Result<KeyState, sqlx::Error> {
+    sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND scope = $2 AND expires_at <= NOW()")
+        .bind(key)
+        .bind(scope)
+        .execute(pool)
+        .await?;
+
+    // Claim a new key, or take over one whose request stopped without storing
+    // a response and whose lease has run out
+    let now = Utc::now();
+    let reserved = sqlx::query_scalar::<_, DateTime<Utc>>(
+        r#"
+        INSERT INTO idempotency_keys (key, scope, fingerprint, expires_at, claimed_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (key, scope) DO UPDATE
+        SET expires_at = EXCLUDED.expires_at, claimed_at = EXCLUDED.claimed_at
+        WHERE idempotency_keys.status_code IS NULL
+          AND idempotency_keys.fingerprint = EXCLUDED.fingerprint
+          AND idempotency_keys.claimed_at <= $6
+        RETURNING claimed_at
+        "#
+    )
+    .bind(key)
+    .bind(scope)
+    .bind(fingerprint)
+    .bind(now + Duration::hours(ttl_hours))
+    .bind(now)
+    .bind(now - Duration::seconds(lease_secs))
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(claimed_at) = reserved {
+        return Ok(KeyState::Reserved { claimed_at });
+    }
+
+    let existing = sqlx::query_as::<_, (String, Option<i16>, Option<String>, Option<Vec<u8>>)>(
+        "SELECT fingerprint, status_code, content_type, response_body FROM idempotency_keys WHERE key = $1 AND scope = $2"
+    )
+    .bind(key)
+    .bind(scope)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(match existing {
+        Some((stored, _, _, _)) if stored != fingerprint => KeyState::Mismatch,
+        Some((_, Some(status_code), content_type, body)) => KeyState::Completed {
+            status_code,
+            content_type,
+            body: body.unwrap_or_default(),
+        },
+        // Either still running within its lease, or released between our insert and select
+        _ => KeyState::InFlight,
+    })
+}
+
+async fn store_response(
+    pool: &PgPool,
+    key: &str,
+    scope: &str,
+    claimed_at: DateTime<Utc>,
+    status: StatusCode,
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE idempotency_keys
+        SET status_code = $4, content_type = $5, response_body = $6
+        WHERE key = $1 AND scope = $2 AND claimed_at = $3
+        "#
+    )
+    .bind(key)
+    .bind(scope)
+    .bind(claimed_at)
+    .bind(status.as_u16() as i16)
+    .bind(content_type)
+    .bind(body)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Drops our claim on a key. A retry that took the key over after our lease
+/// ran out has its own `claimed_at`, so it is left alone.
+async fn release_key(pool: &PgPool, key: &str, scope: &str, claimed_at: DateTime<Utc>) {
+    let released = sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND scope = $2 AND claimed_at = $3")
+        .bind(key)
+        .bind(scope)
+        .bind(claimed_at)
+        .execute(pool)
+        .await;
+
+    if let Err(e) = released {
+        log::error!("Failed to release idempotency key: {}", e);
+    }
+}
@@ -0,0 +1,118 @@
+use actix_web::{web, Error, FromRequest, HttpRequest};
+use std::future::{Ready, ready};
+
+use crate::config::Config;
+
+/// The language to serve content in: `?lang=` when it names a supported
+/// locale, otherwise the best supported match from `Accept-Language`,
+/// otherwise the default locale.
+#[derive(Debug, Clone)]
+pub struct Locale {
+    pub code: String,
+    pub is_default: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct LangQuery {
+    lang: Option<String>,
+}
+
+impl FromRequest for Locale {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        let Some(config) = req.app_data::<web::Data<Config>>() else {
+            return ready(Ok(Locale { code: "en".to_string(), is_default: true }));
+        };
+
+        let lang = web::Query::<LangQuery>::from_query(req.query_string())
+            .ok()
+            .and_then(|query| query.into_inner().lang);
+        let accept_language = req
+            .headers()
+            .get("Accept-Language")
+            .and_then(|value| value.to_str().ok());
+
+        let code = negotiate_locale(lang.as_deref(), accept_language, &config.supported_locales)
+            .unwrap_or_else(|| config.default_locale.clone());
+        let is_default = code == config.default_locale;
+
+        ready(Ok(Locale { code, is_default }))
+    }
+}
+
+/// Picks a supported locale, matching on the primary language subtag so that
+/// `hi-IN` serves `hi`. `Accept-Language` entries are tried by quality.
+fn negotiate_locale(lang: Option<&str>, accept_language: Option<&str>, supported: &[String]) -> Option<String> {
+    let matches = |tag: &str| {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default().trim().to_lowercase();
+        supported.iter().find(|locale| **locale == primary).cloned()
+    };
+
+    if let Some(locale) = lang.and_then(&matches) {
+        return Some(locale);
+    }
+
+    let mut ranges: Vec<(&str, f32)> = accept_language
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    // Stable, so equally weighted languages keep the client's order
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges.into_iter().find_map(|(tag, _)| matches(tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supported() -> Vec<String> {
+        vec!["en".to_string(), "hi".to_string(), "ar".to_string()]
+    }
+
+    fn negotiate(lang: Option<&str>, accept_language: Option<&str>) -> Option<String> {
+        negotiate_locale(lang, accept_language, &supported())
+    }
+
+    #[test]
+    fn query_parameter_wins_when_supported() {
+        assert_eq!(negotiate(Some("ar"), Some("hi")), Some("ar".to_string()));
+        assert_eq!(negotiate(Some("fr"), Some("hi")), Some("hi".to_string()));
+    }
+
+    #[test]
+    fn highest_quality_supported_language_wins() {
+        assert_eq!(negotiate(None, Some("en;q=0.5, hi;q=0.9")), Some("hi".to_string()));
+        assert_eq!(negotiate(None, Some("fr, ar;q=0.8, en;q=0.7")), Some("ar".to_string()));
+    }
+
+    #[test]
+    fn equal_quality_keeps_client_order() {
+        assert_eq!(negotiate(None, Some("ar, hi")), Some("ar".to_string()));
+        assert_eq!(negotiate(None, Some("hi;q=0.5, ar;q=0.5")), Some("hi".to_string()));
+    }
+
+    #[test]
+    fn region_subtags_match_the_primary_language() {
+        assert_eq!(negotiate(Some("HI_in"), None), Some("hi".to_string()));
+        assert_eq!(negotiate(None, Some("ar-EG;q=0.8")), Some("ar".to_string()));
+    }
+
+    #[test]
+    fn refused_wildcard_and_malformed_ranges_are_skipped() {
+        assert_eq!(negotiate(None, Some("hi;q=0, en;q=0.1")), Some("en".to_string()));
+        assert_eq!(negotiate(None, Some("*, ar;q=abc, en;q=0.2")), Some("en".to_string()));
+        assert_eq!(negotiate(None, Some("fr, de")), None);
+        assert_eq!(negotiate(None, None), None);
+    }
+}
@@ -4,11 +4,14 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc, NaiveDate};
 use validator::Validate;
 
+use super::TaxLine;
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Booking {
     pub id: Uuid,
     pub user_id: Uuid,
-    pub package_id: Uuid,
+    /// Unset for bookings made from an accepted RFQ quote.
+    pub package_id: Option<Uuid>,
     pub booking_date: NaiveDate,
     pub number_of_people: i32,
     pub total_amount: i32,
@@ -16,25 +19,86 @@ pub struct Booking {
     pub special_requests: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub base_amount: i32,
+    pub tax_amount: i32,
+    pub traveller_names: Vec<String>,
+    pub reference: String,
+    pub hold_expires_at: Option<DateTime<Utc>>,
+    pub amount_paid: i32,
+    pub rfq_id: Option<Uuid>,
+    /// Offer whose price the booking was made at.
+    pub offer_id: Option<Uuid>,
+    pub version: i32,
+    pub variant_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateBookingRequest {
     pub package_id: Uuid,
+    /// Required when the package has active variants.
+    pub variant_id: Option<Uuid>,
     pub booking_date: NaiveDate,
-    #[validate(range(min = 1))]
+    #[validate(range(min = 1, max = 1000))]
     pub number_of_people: i32,
     pub special_requests: Option<String>,
+    pub traveller_names: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, FromRow)]
 pub struct BookingResponse {
     pub id: Uuid,
+    pub reference: String,
+    pub package_title: String,
+    pub booking_date: NaiveDate,
+    pub number_of_people: i32,
+    pub base_amount: i32,
+    pub tax_amount: i32,
+    #[sqlx(skip)]
+    pub tax_lines: Vec<TaxLine>,
+    pub total_amount: i32,
+    pub amount_paid: i32,
+    pub status: String,
+    pub special_requests: Option<String>,
+    pub traveller_names: Vec<String>,
+    pub hold_expires_at: Option<DateTime<Utc>>,
+    pub unread_messages: i64,
+    pub created_at: DateTime<Utc>,
+    pub version: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookingSearchQuery {
+    /// One status or a comma-separated list, e.g. `pending,confirmed`.
+    pub status: Option<String>,
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+    pub package_id: Option<Uuid>,
+    pub email: Option<String>,
+    pub reference: Option<String>,
+    pub min_amount: Option<i32>,
+    pub max_amount: Option<i32>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct AdminBookingResponse {
+    pub id: Uuid,
+    pub reference: String,
     pub package_title: String,
+    pub user_name: String,
+    pub user_email: String,
     pub booking_date: NaiveDate,
     pub number_of_people: i32,
+    pub base_amount: i32,
+    pub tax_amount: i32,
     pub total_amount: i32,
+    pub amount_paid: i32,
     pub status: String,
     pub special_requests: Option<String>,
+    pub hold_expires_at: Option<DateTime<Utc>>,
+    pub unread_messages: i64,
     pub created_at: DateTime<Utc>,
 }
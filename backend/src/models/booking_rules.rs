@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use validator::Validate;
+
+use crate::utils::validation::validate_weekdays;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PackageBookingRules {
+    pub package_id: Uuid,
+    pub min_lead_days: i32,
+    pub max_advance_days: Option<i32>,
+    pub departure_weekdays: Vec<i32>,
+    pub fixed_departure_dates: Vec<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateBookingRulesRequest {
+    #[validate(range(min = 0, max = 365))]
+    pub min_lead_days: i32,
+    #[validate(range(min = 1, max = 1095))]
+    pub max_advance_days: Option<i32>,
+    #[serde(default)]
+    #[validate(custom(function = "validate_weekdays"))]
+    pub departure_weekdays: Vec<i32>,
+    #[serde(default)]
+    pub fixed_departure_dates: Vec<NaiveDate>,
+}
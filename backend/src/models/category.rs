@@ -4,6 +4,8 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use validator::Validate;
 
+use crate::utils::validation::validate_slug;
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Category {
     pub id: Uuid,
@@ -13,19 +15,37 @@ pub struct Category {
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub slug: String,
+    pub version: i32,
 }
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateCategoryRequest {
     #[validate(length(min = 1))]
     pub name: String,
+    /// Generated from the name when omitted.
+    #[validate(length(max = 255), custom(function = "validate_slug"))]
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+}
+
+/// Partial category update: omitted fields are left unchanged.
+#[derive(Debug, Deserialize, Validate)]
+pub struct PatchCategoryRequest {
+    #[validate(length(min = 1))]
+    pub name: Option<String>,
+    #[validate(length(max = 255), custom(function = "validate_slug"))]
+    pub slug: Option<String>,
     pub description: Option<String>,
     pub icon: Option<String>,
+    pub version: Option<i32>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, FromRow)]
 pub struct CategoryResponse {
     pub id: Uuid,
+    pub slug: String,
     pub name: String,
     pub description: Option<String>,
     pub icon: Option<String>,
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use validator::Validate;
+
+use crate::utils::validation::{validate_enquiry_status, validate_phone_number};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Enquiry {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    pub subject: Option<String>,
+    pub message: String,
+    pub package_id: Option<Uuid>,
+    pub travel_date: Option<NaiveDate>,
+    pub number_of_people: Option<i32>,
+    pub status: String,
+    pub assigned_to: Option<Uuid>,
+    pub booking_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateEnquiryRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+    #[validate(email)]
+    pub email: String,
+    #[validate(custom(function = "validate_phone_number"))]
+    pub phone: Option<String>,
+    #[validate(length(max = 255))]
+    pub subject: Option<String>,
+    #[validate(length(min = 1, max = 5000))]
+    pub message: String,
+    pub package_id: Option<Uuid>,
+    pub travel_date: Option<NaiveDate>,
+    #[validate(range(min = 1))]
+    pub number_of_people: Option<i32>,
+    /// Token from `GET /api/enquiries/form-token`, issued when the form was shown.
+    pub form_token: String,
+    /// Honeypot: hidden from people, so only bots fill it in.
+    #[serde(default)]
+    pub website: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignEnquiryRequest {
+    /// Staff member to assign, or null to unassign.
+    pub user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateEnquiryStatusRequest {
+    #[validate(custom(function = "validate_enquiry_status"))]
+    pub status: String,
+}
+
+/// Overrides for details the enquiry did not capture.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ConvertEnquiryRequest {
+    /// Customer account to book for; defaults to the account with the enquiry's email.
+    pub user_id: Option<Uuid>,
+    pub package_id: Option<Uuid>,
+    /// Required when the package has active variants.
+    pub variant_id: Option<Uuid>,
+    pub booking_date: Option<NaiveDate>,
+    #[validate(range(min = 1, max = 1000))]
+    pub number_of_people: Option<i32>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct EnquiryResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    pub subject: Option<String>,
+    pub message: String,
+    pub package_id: Option<Uuid>,
+    pub package_title: Option<String>,
+    pub travel_date: Option<NaiveDate>,
+    pub number_of_people: Option<i32>,
+    pub status: String,
+    pub assigned_to: Option<Uuid>,
+    pub assigned_to_email: Option<String>,
+    pub booking_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
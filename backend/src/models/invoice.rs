@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Invoice {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub invoice_number: String,
+    pub financial_year: String,
+    pub sequence_number: i32,
+    pub base_amount: i32,
+    pub tax_amount: i32,
+    pub total_amount: i32,
+    pub issued_at: DateTime<Utc>,
+}
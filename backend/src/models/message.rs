@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::utils::validation::validate_web_url;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct MessageAttachment {
+    #[validate(length(min = 1, max = 255))]
+    pub file_name: String,
+    #[validate(url, custom(function = "validate_web_url"))]
+    pub url: String,
+    pub content_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct BookingMessage {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub author_id: Option<Uuid>,
+    pub author_role: String,
+    pub body: String,
+    pub attachments: Json<Vec<MessageAttachment>>,
+    pub is_internal: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateMessageRequest {
+    #[validate(length(min = 1, max = 5000))]
+    pub body: String,
+    #[serde(default)]
+    #[validate(length(max = 10), nested)]
+    pub attachments: Vec<MessageAttachment>,
+    /// Staff-only note hidden from the customer; ignored on customer posts.
+    pub is_internal: Option<bool>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct UnansweredBookingResponse {
+    pub booking_id: Uuid,
+    pub reference: String,
+    pub package_title: String,
+    pub user_email: String,
+    pub last_message: String,
+    pub last_message_at: DateTime<Utc>,
+    pub unread_messages: i64,
+}
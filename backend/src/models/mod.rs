@@ -2,8 +2,40 @@ pub mod user;
 pub mod package;
 pub mod booking;
 pub mod category;
+pub mod tax;
+pub mod invoice;
+pub mod waitlist;
+pub mod payment;
+pub mod message;
+pub mod booking_rules;
+pub mod post;
+pub mod enquiry;
+pub mod rfq;
+pub mod offer;
+pub mod translation;
+pub mod newsletter;
+pub mod package_content;
+pub mod revision;
+pub mod package_transfer;
+pub mod variant;
 
 pub use user::*;
 pub use package::*;
 pub use booking::*;
 pub use category::*;
+pub use tax::*;
+pub use invoice::*;
+pub use waitlist::*;
+pub use payment::*;
+pub use message::*;
+pub use booking_rules::*;
+pub use post::*;
+pub use enquiry::*;
+pub use rfq::*;
+pub use offer::*;
+pub use translation::*;
+pub use newsletter::*;
+pub use package_content::*;
+pub use revision::*;
+pub use package_transfer::*;
+pub use variant::*;
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::utils::validation::validate_travel_scope;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct NewsletterSubscriber {
+    pub id: Uuid,
+    pub email: String,
+    pub first_name: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub status: String,
+    pub interests: Vec<String>,
+    pub destinations: Vec<String>,
+    pub travel_scope: String,
+    pub source: Option<String>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub unsubscribed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SubscribeRequest {
+    #[validate(email, length(max = 255))]
+    pub email: String,
+    #[validate(length(min = 1, max = 100))]
+    pub first_name: Option<String>,
+    /// Segment tags, e.g. `["beach", "honeymoon"]`.
+    #[serde(default)]
+    #[validate(length(max = 20))]
+    pub interests: Vec<String>,
+    #[serde(default)]
+    #[validate(length(max = 20))]
+    pub destinations: Vec<String>,
+    #[validate(custom(function = "validate_travel_scope"))]
+    pub travel_scope: Option<String>,
+    /// Where the sign-up came from, e.g. `footer` or `checkout`.
+    #[validate(length(max = 50))]
+    pub source: Option<String>,
+}
+
+/// Segment filters for listing and exporting subscribers. A tag filter
+/// matches subscribers carrying that tag.
+#[derive(Debug, Deserialize)]
+pub struct SubscriberQuery {
+    pub status: Option<String>,
+    pub interest: Option<String>,
+    pub destination: Option<String>,
+    pub travel_scope: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::utils::validation::validate_discount_type;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Offer {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub badge_label: Option<String>,
+    pub banner_image_url: Option<String>,
+    pub discount_type: String,
+    pub discount_value: i32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateOfferRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    pub description: Option<String>,
+    /// Short label shown on the package card, e.g. "Early bird".
+    #[validate(length(max = 50))]
+    pub badge_label: Option<String>,
+    #[validate(url)]
+    pub banner_image_url: Option<String>,
+    #[validate(custom(function = "validate_discount_type"))]
+    pub discount_type: String,
+    /// Basis points off for `percentage` (e.g. `1500` for 15%), or the offer
+    /// price per person for `fixed_price`. Variants get the same saving off
+    /// their own price as the package does.
+    #[validate(range(min = 1))]
+    pub discount_value: i32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub is_active: Option<bool>,
+    #[validate(length(min = 1))]
+    pub package_ids: Vec<Uuid>,
+}
+
+/// A package an offer applies to, priced under that offer.
+#[derive(Debug, Serialize, FromRow)]
+pub struct OfferPackage {
+    #[serde(skip)]
+    pub offer_id: Uuid,
+    pub id: Uuid,
+    pub title: String,
+    pub image_url: Option<String>,
+    pub original_price: i32,
+    pub offer_price: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfferResponse {
+    #[serde(flatten)]
+    pub offer: Offer,
+    pub packages: Vec<OfferPackage>,
+}
+
+/// The live offer shown on a package, when one lowers its price.
+#[derive(Debug, Serialize)]
+pub struct PackageOffer {
+    pub id: Uuid,
+    pub title: String,
+    pub badge_label: Option<String>,
+    pub ends_at: DateTime<Utc>,
+}
@@ -4,6 +4,9 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use validator::Validate;
 
+use super::PackageOffer;
+use crate::utils::validation::{validate_package_status, validate_slug};
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Package {
     pub id: Uuid,
@@ -19,15 +22,28 @@ pub struct Package {
     pub exclusions: Vec<String>,
     pub itinerary: serde_json::Value,
     pub is_featured: bool,
-    pub is_active: bool,
+    pub is_international: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub slug: String,
+    pub status: String,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
+    pub version: i32,
+    /// Key of the package in the content team's spreadsheets, set by import.
+    pub external_id: Option<String>,
+    /// The package this one was cloned from.
+    pub cloned_from: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreatePackageRequest {
     #[validate(length(min = 1))]
     pub title: String,
+    /// Generated from the title when a package is created without one; kept
+    /// unchanged on update unless given.
+    #[validate(length(max = 255), custom(function = "validate_slug"))]
+    pub slug: Option<String>,
     #[validate(length(min = 10))]
     pub description: String,
     #[validate(range(min = 1))]
@@ -43,14 +59,92 @@ pub struct CreatePackageRequest {
     pub exclusions: Vec<String>,
     pub itinerary: serde_json::Value,
     pub is_featured: Option<bool>,
+    pub is_international: Option<bool>,
+    /// Workflow state for a new package, `draft` unless given. Existing
+    /// packages change state through the status endpoint.
+    #[validate(custom(function = "validate_package_status"))]
+    pub status: Option<String>,
+    pub publish_at: Option<DateTime<Utc>>,
+    pub unpublish_at: Option<DateTime<Utc>>,
+    /// On update, the version being replaced; stale writes are rejected.
+    pub version: Option<i32>,
+}
+
+/// Partial package update: omitted fields are left unchanged, and an empty
+/// `image_url` removes the image.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct PatchPackageRequest {
+    #[validate(length(min = 1))]
+    pub title: Option<String>,
+    #[validate(length(max = 255), custom(function = "validate_slug"))]
+    pub slug: Option<String>,
+    #[validate(length(min = 10))]
+    pub description: Option<String>,
+    #[validate(range(min = 1))]
+    pub price: Option<i32>,
+    #[validate(range(min = 1))]
+    pub duration_days: Option<i32>,
+    #[validate(range(min = 1))]
+    pub max_people: Option<i32>,
+    pub category_id: Option<Uuid>,
+    pub image_url: Option<String>,
+    pub highlights: Option<Vec<String>>,
+    pub inclusions: Option<Vec<String>>,
+    pub exclusions: Option<Vec<String>>,
+    pub itinerary: Option<serde_json::Value>,
+    pub is_featured: Option<bool>,
+    pub is_international: Option<bool>,
+    pub version: Option<i32>,
+}
+
+/// Overrides for a cloned package; anything omitted is copied.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ClonePackageRequest {
+    /// Defaults to the source title with " (copy)" appended.
+    #[validate(length(min = 1))]
+    pub title: Option<String>,
+    /// Generated from the title when omitted.
+    #[validate(length(max = 255), custom(function = "validate_slug"))]
+    pub slug: Option<String>,
+    #[validate(range(min = 1))]
+    pub duration_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePackageStatusRequest {
+    #[validate(custom(function = "validate_package_status"))]
+    pub status: String,
+    /// Go-live time; required when scheduling.
+    pub publish_at: Option<DateTime<Utc>>,
+    /// When the package comes down again, if ever.
+    pub unpublish_at: Option<DateTime<Utc>>,
+}
+
+/// An archived package in the admin archive, with how many bookings keep it
+/// from being deleted permanently.
+#[derive(Debug, Serialize, FromRow)]
+pub struct ArchivedPackage {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub external_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    pub booking_count: i64,
 }
 
 #[derive(Debug, Serialize)]
 pub struct PackageResponse {
     pub id: Uuid,
+    pub slug: String,
     pub title: String,
     pub description: String,
+    /// Price per person, after any live offer.
     pub price: i32,
+    /// List price to show struck through while an offer applies.
+    pub original_price: Option<i32>,
+    /// Whether `price` is the cheapest variant's, to show as "from".
+    pub is_from_price: bool,
+    pub offer: Option<PackageOffer>,
     pub duration_days: i32,
     pub max_people: i32,
     pub category: Option<String>,
@@ -60,5 +154,35 @@ pub struct PackageResponse {
     pub exclusions: Vec<String>,
     pub itinerary: serde_json::Value,
     pub is_featured: bool,
+    pub is_international: bool,
     pub created_at: DateTime<Utc>,
+    /// Locale of the translated text, or `None` for the default language.
+    pub locale: Option<String>,
+}
+
+impl From<Package> for PackageResponse {
+    fn from(package: Package) -> Self {
+        Self {
+            id: package.id,
+            slug: package.slug,
+            title: package.title,
+            description: package.description,
+            price: package.price,
+            original_price: None,
+            is_from_price: false,
+            offer: None,
+            duration_days: package.duration_days,
+            max_people: package.max_people,
+            category: None, // Will be populated with JOIN in production
+            image_url: package.image_url,
+            highlights: package.highlights,
+            inclusions: package.inclusions,
+            exclusions: package.exclusions,
+            itinerary: package.itinerary,
+            is_featured: package.is_featured,
+            is_international: package.is_international,
+            created_at: package.created_at,
+            locale: None,
+        }
+    }
 }
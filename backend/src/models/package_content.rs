@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use super::{PackageResponse, PackageVariantResponse};
+use crate::utils::validation::{validate_policy_section_type, validate_template_kind};
+
+/// A FAQ entry or policy section that packages can share. For FAQs the
+/// title is the question and the body the answer.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ContentTemplate {
+    pub id: Uuid,
+    pub kind: String,
+    pub section_type: Option<String>,
+    pub title: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateContentTemplateRequest {
+    #[validate(custom(function = "validate_template_kind"))]
+    pub kind: String,
+    /// Required for policy templates.
+    #[validate(custom(function = "validate_policy_section_type"))]
+    pub section_type: Option<String>,
+    #[validate(length(min = 1, max = 500))]
+    pub title: String,
+    #[validate(length(min = 1, max = 20000))]
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateContentTemplateRequest {
+    /// Ignored for FAQ templates.
+    #[validate(custom(function = "validate_policy_section_type"))]
+    pub section_type: Option<String>,
+    #[validate(length(min = 1, max = 500))]
+    pub title: String,
+    #[validate(length(min = 1, max = 20000))]
+    pub body: String,
+}
+
+/// A package FAQ, with the text of its template when it uses one.
+#[derive(Debug, Serialize, FromRow)]
+pub struct PackageFaq {
+    pub id: Uuid,
+    pub template_id: Option<Uuid>,
+    pub position: i32,
+    pub question: String,
+    pub answer: String,
+}
+
+/// A package policy section, with the text of its template when it uses one.
+#[derive(Debug, Serialize, FromRow)]
+pub struct PackagePolicy {
+    pub id: Uuid,
+    pub template_id: Option<Uuid>,
+    pub position: i32,
+    pub section_type: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// Either a shared template or the package's own question and answer.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct FaqItemRequest {
+    pub template_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 500))]
+    pub question: Option<String>,
+    #[validate(length(min = 1, max = 20000))]
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePackageFaqsRequest {
+    #[validate(length(max = 100), nested)]
+    pub faqs: Vec<FaqItemRequest>,
+}
+
+/// Either a shared template or the package's own section.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct PolicyItemRequest {
+    pub template_id: Option<Uuid>,
+    #[validate(custom(function = "validate_policy_section_type"))]
+    pub section_type: Option<String>,
+    #[validate(length(min = 1, max = 500))]
+    pub title: Option<String>,
+    #[validate(length(min = 1, max = 20000))]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePackagePoliciesRequest {
+    #[validate(length(max = 100), nested)]
+    pub policies: Vec<PolicyItemRequest>,
+}
+
+/// The package detail page: the package with its variants, FAQs and policy sections.
+#[derive(Debug, Serialize)]
+pub struct PackageDetailResponse {
+    #[serde(flatten)]
+    pub package: PackageResponse,
+    pub variants: Vec<PackageVariantResponse>,
+    pub faqs: Vec<PackageFaq>,
+    pub policies: Vec<PackagePolicy>,
+}
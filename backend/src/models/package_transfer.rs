@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A package as exported for editing in a spreadsheet. The same columns are
+/// accepted back by the import, which ignores `status`.
+#[derive(Debug, Serialize, FromRow)]
+pub struct PackageExportRow {
+    pub external_id: Option<String>,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub price: i32,
+    pub duration_days: i32,
+    pub max_people: i32,
+    pub category_slug: String,
+    pub image_url: Option<String>,
+    pub highlights: Vec<String>,
+    pub inclusions: Vec<String>,
+    pub exclusions: Vec<String>,
+    pub itinerary: serde_json::Value,
+    pub is_featured: bool,
+    pub is_international: bool,
+    pub status: String,
+}
+
+/// One row of an import. It updates the package with the same `external_id`,
+/// else the one with the same `slug`, else creates a draft.
+#[derive(Debug, Deserialize)]
+pub struct PackageImportRow {
+    pub external_id: Option<String>,
+    pub slug: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub price: i32,
+    pub duration_days: i32,
+    pub max_people: i32,
+    pub category_slug: String,
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+    #[serde(default)]
+    pub inclusions: Vec<String>,
+    #[serde(default)]
+    pub exclusions: Vec<String>,
+    #[serde(default)]
+    pub itinerary: Option<serde_json::Value>,
+    pub is_featured: Option<bool>,
+    pub is_international: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    /// 1-based position of the row in the file, not counting the CSV header.
+    pub row: usize,
+    pub external_id: Option<String>,
+    pub slug: Option<String>,
+    /// `create`, `update` or `error`.
+    pub action: &'static str,
+    pub package_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// Whether the import was saved. Nothing is saved if any row fails.
+    pub applied: bool,
+    pub creates: usize,
+    pub updates: usize,
+    pub errors: usize,
+    pub rows: Vec<ImportRowResult>,
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc, NaiveDate};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PaymentScheduleTemplate {
+    pub id: Uuid,
+    pub package_id: Uuid,
+    pub position: i32,
+    pub label: String,
+    pub percent_basis_points: i32,
+    pub due_days_before_departure: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PaymentScheduleItemRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub label: String,
+    #[validate(range(min = 1, max = 10000))]
+    pub percent_basis_points: i32,
+    #[validate(range(min = 0))]
+    pub due_days_before_departure: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePaymentScheduleRequest {
+    #[validate(nested)]
+    pub installments: Vec<PaymentScheduleItemRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct BookingInstallment {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub position: i32,
+    pub label: String,
+    pub amount: i32,
+    pub due_date: NaiveDate,
+    pub paid_amount: i32,
+    pub reminder_sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct BookingPayment {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub amount: i32,
+    pub method: String,
+    pub transaction_reference: Option<String>,
+    pub recorded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RecordPaymentRequest {
+    #[validate(range(min = 1))]
+    pub amount: i32,
+    #[validate(length(min = 1, max = 50))]
+    pub method: String,
+    #[validate(length(max = 100))]
+    pub transaction_reference: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentSummaryResponse {
+    pub booking_id: Uuid,
+    pub reference: String,
+    pub status: String,
+    pub total_amount: i32,
+    pub amount_paid: i32,
+    pub outstanding: i32,
+    pub next_due_date: Option<NaiveDate>,
+    pub installments: Vec<BookingInstallment>,
+    pub payments: Vec<BookingPayment>,
+}
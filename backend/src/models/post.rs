@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::utils::validation::{validate_post_status, validate_slug};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Post {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub excerpt: Option<String>,
+    pub body_markdown: String,
+    pub body_html: String,
+    pub cover_image_url: Option<String>,
+    pub tags: Vec<String>,
+    pub author_id: Uuid,
+    pub status: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePostRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    /// Generated from the title when omitted.
+    #[validate(length(max = 255), custom(function = "validate_slug"))]
+    pub slug: Option<String>,
+    pub excerpt: Option<String>,
+    #[validate(length(min = 1))]
+    pub body_markdown: String,
+    #[validate(url)]
+    pub cover_image_url: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Defaults to the admin making the request.
+    pub author_id: Option<Uuid>,
+    #[validate(custom(function = "validate_post_status"))]
+    pub status: String,
+    /// Required for scheduled posts; defaults to now when publishing.
+    pub published_at: Option<DateTime<Utc>>,
+    #[validate(length(max = 255))]
+    pub meta_title: Option<String>,
+    #[validate(length(max = 500))]
+    pub meta_description: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct PostSummary {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub excerpt: Option<String>,
+    pub cover_image_url: Option<String>,
+    pub tags: Vec<String>,
+    pub author_name: String,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct PostResponse {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub excerpt: Option<String>,
+    pub body_html: String,
+    pub cover_image_url: Option<String>,
+    pub tags: Vec<String>,
+    pub author_name: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
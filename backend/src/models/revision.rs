@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// A package as it was before an edit. `edited_by` and `created_at` record
+/// who made that edit and when.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct PackageRevision {
+    pub id: Uuid,
+    pub package_id: Uuid,
+    pub revision_number: i32,
+    pub snapshot: serde_json::Value,
+    pub edited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct PackageRevisionSummary {
+    pub id: Uuid,
+    pub revision_number: i32,
+    pub edited_by: Option<Uuid>,
+    pub editor_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevisionDiffResponse {
+    pub from_revision: i32,
+    /// `None` when comparing against the current package.
+    pub to_revision: Option<i32>,
+    pub changes: Vec<FieldChange>,
+}
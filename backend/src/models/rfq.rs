@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use validator::Validate;
+
+use crate::utils::validation::{validate_phone_number, validate_quote_item_category, validate_rfq_status};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Rfq {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub event_type: String,
+    pub company_name: String,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub contact_phone: Option<String>,
+    pub city: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub headcount: i32,
+    pub budget_amount: Option<i32>,
+    pub rooms_required: Option<i32>,
+    pub venue_requirements: Option<String>,
+    pub notes: Option<String>,
+    pub status: String,
+    pub booking_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateRfqRequest {
+    /// Name of the meeting, incentive trip or event.
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    #[validate(length(min = 1, max = 50))]
+    pub event_type: String,
+    #[validate(length(min = 1, max = 255))]
+    pub company_name: String,
+    #[validate(length(min = 1, max = 200))]
+    pub contact_name: String,
+    #[validate(email)]
+    pub contact_email: String,
+    #[validate(custom(function = "validate_phone_number"))]
+    pub contact_phone: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub city: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    #[validate(range(min = 1))]
+    pub headcount: i32,
+    #[validate(range(min = 1))]
+    pub budget_amount: Option<i32>,
+    #[validate(range(min = 0))]
+    pub rooms_required: Option<i32>,
+    #[validate(length(max = 5000))]
+    pub venue_requirements: Option<String>,
+    #[validate(length(max = 5000))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateRfqStatusRequest {
+    #[validate(custom(function = "validate_rfq_status"))]
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RfqQuote {
+    pub id: Uuid,
+    pub rfq_id: Uuid,
+    pub version: i32,
+    pub status: String,
+    pub cost_total: i32,
+    pub margin_total: i32,
+    pub total_amount: i32,
+    pub deposit_basis_points: i32,
+    pub balance_due_days_before: i32,
+    pub valid_until: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RfqQuoteItem {
+    pub id: Uuid,
+    pub quote_id: Uuid,
+    pub position: i32,
+    pub category: String,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_cost: i32,
+    pub margin_basis_points: i32,
+    pub cost_amount: i32,
+    pub sell_amount: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct QuoteItemRequest {
+    #[validate(custom(function = "validate_quote_item_category"))]
+    pub category: String,
+    #[validate(length(min = 1, max = 500))]
+    pub description: String,
+    #[validate(range(min = 1))]
+    pub quantity: i32,
+    #[validate(range(min = 0))]
+    pub unit_cost: i32,
+    /// Markup on cost, e.g. `1500` for 15%.
+    #[validate(range(min = 0, max = 100000))]
+    pub margin_basis_points: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateQuoteRequest {
+    #[validate(length(min = 1, max = 100), nested)]
+    pub items: Vec<QuoteItemRequest>,
+    /// Share of the total due on acceptance; the balance is due before the event.
+    #[serde(default)]
+    #[validate(range(min = 0, max = 10000))]
+    pub deposit_basis_points: i32,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub balance_due_days_before: i32,
+    pub valid_until: Option<NaiveDate>,
+    #[validate(length(max = 5000))]
+    pub notes: Option<String>,
+}
+
+/// A quote with its line items, including cost and margin, for staff.
+#[derive(Debug, Serialize)]
+pub struct RfqQuoteResponse {
+    #[serde(flatten)]
+    pub quote: RfqQuote,
+    pub items: Vec<RfqQuoteItem>,
+}
+
+/// A quote line as the customer sees it, without cost or margin.
+#[derive(Debug, Serialize, FromRow)]
+pub struct CustomerQuoteItem {
+    #[serde(skip)]
+    pub quote_id: Uuid,
+    pub position: i32,
+    pub category: String,
+    pub description: String,
+    pub quantity: i32,
+    pub amount: i32,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct CustomerQuoteResponse {
+    pub id: Uuid,
+    pub version: i32,
+    pub status: String,
+    pub total_amount: i32,
+    pub deposit_basis_points: i32,
+    pub balance_due_days_before: i32,
+    pub valid_until: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    #[sqlx(skip)]
+    pub items: Vec<CustomerQuoteItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RfqDetailResponse<Q> {
+    #[serde(flatten)]
+    pub rfq: Rfq,
+    pub quotes: Vec<Q>,
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::utils::validation::{validate_tax_mode, validate_tax_region};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaxRule {
+    pub id: Uuid,
+    pub name: String,
+    pub category_id: Option<Uuid>,
+    pub region: String,
+    pub rate_basis_points: i32,
+    pub mode: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTaxRuleRequest {
+    #[validate(length(min = 1))]
+    pub name: String,
+    pub category_id: Option<Uuid>,
+    #[validate(custom(function = "validate_tax_region"))]
+    pub region: String,
+    #[validate(range(min = 0, max = 10000))]
+    pub rate_basis_points: i32,
+    #[validate(custom(function = "validate_tax_mode"))]
+    pub mode: String,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaxLine {
+    pub tax_rule_id: Option<Uuid>,
+    pub name: String,
+    pub rate_basis_points: i32,
+    pub mode: String,
+    pub amount: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxBreakdown {
+    pub base_amount: i32,
+    pub tax_lines: Vec<TaxLine>,
+    pub tax_amount: i32,
+    pub total_amount: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BookingQuoteRequest {
+    pub package_id: Uuid,
+    /// Required when the package has active variants.
+    pub variant_id: Option<Uuid>,
+    #[validate(range(min = 1, max = 1000))]
+    pub number_of_people: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookingQuoteResponse {
+    pub package_id: Uuid,
+    pub variant_id: Option<Uuid>,
+    pub number_of_people: i32,
+    pub unit_price: i32,
+    /// List price per person when a live offer lowered `unit_price`.
+    pub original_unit_price: Option<i32>,
+    pub offer_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub breakdown: TaxBreakdown,
+}
+
+#[derive(Debug, FromRow)]
+pub struct BookingTaxLine {
+    pub booking_id: Uuid,
+    #[sqlx(flatten)]
+    pub line: TaxLine,
+}
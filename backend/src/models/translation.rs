@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct PackageTranslation {
+    pub package_id: Uuid,
+    pub locale: String,
+    pub title: String,
+    pub description: String,
+    pub highlights: Option<Vec<String>>,
+    pub itinerary: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpsertPackageTranslationRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    #[validate(length(min = 10))]
+    pub description: String,
+    /// Left out to show the default-language highlights.
+    pub highlights: Option<Vec<String>>,
+    /// Left out to show the default-language itinerary.
+    pub itinerary: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct CategoryTranslation {
+    pub category_id: Uuid,
+    pub locale: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpsertCategoryTranslationRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// An active package lacking a translation into one or more supported locales.
+#[derive(Debug, Serialize, FromRow)]
+pub struct MissingTranslations {
+    pub id: Uuid,
+    pub slug: String,
+    pub title: String,
+    pub missing_locales: Vec<String>,
+}
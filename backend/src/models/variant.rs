@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+/// A tier of a package, e.g. deluxe hotels on the same itinerary, with its
+/// own price, changes to the package inclusions and seats per departure.
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct PackageVariant {
+    pub id: Uuid,
+    pub package_id: Uuid,
+    pub name: String,
+    pub position: i32,
+    pub price: i32,
+    pub added_inclusions: Vec<String>,
+    pub removed_inclusions: Vec<String>,
+    pub max_people: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PackageVariantRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    pub position: Option<i32>,
+    #[validate(range(min = 1))]
+    pub price: i32,
+    /// Inclusions on top of the package's own.
+    pub added_inclusions: Option<Vec<String>>,
+    /// Package inclusions this variant does not have.
+    pub removed_inclusions: Option<Vec<String>>,
+    #[validate(range(min = 1))]
+    pub max_people: i32,
+    pub is_active: Option<bool>,
+}
+
+/// A bookable variant as customers see it.
+#[derive(Debug, Serialize)]
+pub struct PackageVariantResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// Price per person, after any live offer.
+    pub price: i32,
+    /// List price to show struck through while an offer applies.
+    pub original_price: Option<i32>,
+    /// The package inclusions with this variant's changes applied.
+    pub inclusions: Vec<String>,
+    pub max_people: i32,
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc, NaiveDate};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct WaitlistEntry {
+    pub id: Uuid,
+    pub package_id: Uuid,
+    pub user_id: Uuid,
+    pub booking_date: NaiveDate,
+    pub party_size: i32,
+    pub status: String,
+    pub offer_booking_id: Option<Uuid>,
+    pub offer_expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub variant_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct JoinWaitlistRequest {
+    /// Required when the package has active variants.
+    pub variant_id: Option<Uuid>,
+    pub booking_date: NaiveDate,
+    #[validate(range(min = 1))]
+    pub party_size: i32,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct WaitlistEntryResponse {
+    pub id: Uuid,
+    pub package_id: Uuid,
+    pub package_title: String,
+    pub variant_id: Option<Uuid>,
+    pub variant_name: Option<String>,
+    pub user_email: String,
+    pub booking_date: NaiveDate,
+    pub party_size: i32,
+    pub status: String,
+    pub position: Option<i64>,
+    pub offer_booking_id: Option<Uuid>,
+    pub offer_expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,6 +1,6 @@
-use bcrypt::{hash, verify, DEFAULT_COST};
+use bcrypt::{hash, DEFAULT_COST};
 use jsonwebtoken::{encode, decode, Header, Algorithm, Validation, EncodingKey, DecodingKey};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{Utc, Duration};
 use actix_web::Result;
@@ -18,11 +18,36 @@ pub fn hash_password(password: &str) -> Result<String, bcrypt::BcryptError> {
 }
 
 pub fn verify_password(password: &str, hash: &str) -> Result<bool, bcrypt::BcryptError> {
-    verify(password, hash)
+    bcrypt::verify(password, hash)
+}
+
+fn secret() -> String {
+    env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string())
+}
+
+fn sign<T: Serialize>(claims: &T) -> Result<String, jsonwebtoken::errors::Error> {
+    encode(
+        &Header::default(),
+        claims,
+        &EncodingKey::from_secret(secret().as_ref()),
+    )
+}
+
+/// Decodes a token signed by `sign`. Session tokens carry no audience, so
+/// `None` rejects any token that has one; every other kind must name its
+/// own audience, so one kind can't stand in for another.
+fn verify<T: DeserializeOwned>(token: &str, audience: Option<&str>) -> Result<T, jsonwebtoken::errors::Error> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    if let Some(audience) = audience {
+        validation.set_audience(&[audience]);
+        validation.set_required_spec_claims(&["exp", "aud"]);
+    }
+
+    decode::<T>(token, &DecodingKey::from_secret(secret().as_ref()), &validation)
+        .map(|data| data.claims)
 }
 
 pub fn create_jwt(user_id: Uuid) -> Result<String, actix_web::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
     let now = Utc::now();
     let exp = now + Duration::hours(24);
 
@@ -32,24 +57,80 @@ pub fn create_jwt(user_id: Uuid) -> Result<String, actix_web::Error> {
         iat: now.timestamp() as usize,
     };
 
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_ref()),
-    )
-    .map_err(|e| {
+    sign(&claims).map_err(|e| {
         log::error!("JWT creation error: {}", e);
         actix_web::error::ErrorInternalServerError("Failed to create token")
     })
 }
 
 pub fn verify_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "your-secret-key".to_string());
-    
-    decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_ref()),
-        &Validation::new(Algorithm::HS256),
-    )
-    .map(|data| data.claims)
+    verify(token, None)
+}
+
+const FORM_AUDIENCE: &str = "form";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FormClaims {
+    pub aud: String,
+    pub form: String, // which form the token was issued for
+    pub exp: usize,
+    pub iat: usize,
+}
+
+/// Issues a short-lived token a public form echoes back on submit, so the
+/// server can tell how long the visitor spent on the form.
+pub fn create_form_token(form: &str) -> Result<String, actix_web::Error> {
+    let now = Utc::now();
+    let exp = now + Duration::hours(2);
+
+    let claims = FormClaims {
+        aud: FORM_AUDIENCE.to_string(),
+        form: form.to_string(),
+        exp: exp.timestamp() as usize,
+        iat: now.timestamp() as usize,
+    };
+
+    sign(&claims).map_err(|e| {
+        log::error!("Form token creation error: {}", e);
+        actix_web::error::ErrorInternalServerError("Failed to create token")
+    })
+}
+
+pub fn verify_form_token(token: &str) -> Result<FormClaims, jsonwebtoken::errors::Error> {
+    verify(token, Some(FORM_AUDIENCE))
+}
+
+const NEWSLETTER_AUDIENCE: &str = "newsletter";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewsletterClaims {
+    pub aud: String,
+    pub email: String,  // subscriber email, lowercased
+    pub action: String, // "confirm" or "unsubscribe"
+    pub exp: usize,
+    pub iat: usize,
+}
+
+/// Signs a newsletter confirmation or unsubscribe link for an email address.
+/// Unsubscribe links sit in every email, so they stay valid for years.
+pub fn create_newsletter_token(email: &str, action: &str) -> Result<String, jsonwebtoken::errors::Error> {
+    let now = Utc::now();
+    let exp = match action {
+        "confirm" => now + Duration::days(3),
+        _ => now + Duration::days(3650),
+    };
+
+    let claims = NewsletterClaims {
+        aud: NEWSLETTER_AUDIENCE.to_string(),
+        email: email.to_string(),
+        action: action.to_string(),
+        exp: exp.timestamp() as usize,
+        iat: now.timestamp() as usize,
+    };
+
+    sign(&claims)
+}
+
+pub fn verify_newsletter_token(token: &str) -> Result<NewsletterClaims, jsonwebtoken::errors::Error> {
+    verify(token, Some(NEWSLETTER_AUDIENCE))
 }
@@ -0,0 +1,194 @@
+use actix_web::web::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use rust_xlsxwriter::Workbook;
+use sqlx::PgPool;
+use tokio::io::AsyncReadExt;
+use tokio::sync::mpsc;
+
+use crate::models::{AdminBookingResponse, BookingSearchQuery};
+use crate::utils::booking_search::select_bookings;
+use crate::utils::spreadsheet::escape_formula;
+
+type ExportError = Box<dyn std::error::Error + Send + Sync>;
+type ExportSender = mpsc::Sender<Result<Bytes, std::io::Error>>;
+
+const EXPORT_HEADERS: [&str; 13] = [
+    "Reference",
+    "Booking date",
+    "Package",
+    "Customer",
+    "Email",
+    "People",
+    "Base amount",
+    "Tax amount",
+    "Total amount",
+    "Amount paid",
+    "Outstanding",
+    "Status",
+    "Created at",
+];
+const CSV_CHUNK_ROWS: usize = 500;
+const FILE_CHUNK_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn parse(format: Option<&str>) -> Option<Self> {
+        match format.unwrap_or("csv") {
+            "csv" => Some(ExportFormat::Csv),
+            "xlsx" => Some(ExportFormat::Xlsx),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+enum Cell {
+    Text(String),
+    Number(i64),
+}
+
+/// Streams the bookings matching `query` as a CSV or XLSX file.
+///
+/// Rows are read from a database cursor in a background task and sent on as
+/// they are encoded, so the full result set is never held in memory. XLSX
+/// rows are spooled to temporary files by the writer and the finished file
+/// is streamed from disk.
+pub fn stream_bookings_export(
+    pool: PgPool,
+    query: BookingSearchQuery,
+    format: ExportFormat,
+) -> impl Stream<Item = Result<Bytes, std::io::Error>> {
+    let (sender, receiver) = mpsc::channel(8);
+
+    tokio::spawn(async move {
+        let result = match format {
+            ExportFormat::Csv => write_csv(&pool, &query, &sender).await,
+            ExportFormat::Xlsx => write_xlsx(&pool, &query, &sender).await,
+        };
+
+        if let Err(e) = result {
+            log::error!("Failed to export bookings: {}", e);
+            // Aborts the response so the client does not keep a truncated file
+            let _ = sender
+                .send(Err(std::io::Error::other("Failed to export bookings")))
+                .await;
+        }
+    });
+
+    futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    })
+}
+
+async fn write_csv(pool: &PgPool, query: &BookingSearchQuery, sender: &ExportSender) -> Result<(), ExportError> {
+    let mut builder = select_bookings(query);
+    let mut bookings = builder.build_query_as::<AdminBookingResponse>().fetch(pool);
+
+    let header: Vec<Cell> = EXPORT_HEADERS.iter().map(|header| Cell::Text(header.to_string())).collect();
+    let mut chunk = vec![header];
+    while let Some(booking) = bookings.try_next().await? {
+        chunk.push(export_cells(&booking));
+        if chunk.len() >= CSV_CHUNK_ROWS {
+            if !send(sender, encode_csv(&chunk)?).await {
+                return Ok(());
+            }
+            chunk.clear();
+        }
+    }
+
+    if !chunk.is_empty() {
+        send(sender, encode_csv(&chunk)?).await;
+    }
+    Ok(())
+}
+
+async fn write_xlsx(pool: &PgPool, query: &BookingSearchQuery, sender: &ExportSender) -> Result<(), ExportError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    worksheet.set_name("Bookings")?;
+
+    for (col, header) in EXPORT_HEADERS.iter().enumerate() {
+        worksheet.write_string(0, col as u16, *header)?;
+    }
+
+    let mut builder = select_bookings(query);
+    let mut bookings = builder.build_query_as::<AdminBookingResponse>().fetch(pool);
+    let mut row = 1;
+    while let Some(booking) = bookings.try_next().await? {
+        for (col, cell) in export_cells(&booking).into_iter().enumerate() {
+            match cell {
+                Cell::Text(text) => worksheet.write_string(row, col as u16, text)?,
+                Cell::Number(number) => worksheet.write_number(row, col as u16, number as f64)?,
+            };
+        }
+        row += 1;
+    }
+    drop(bookings);
+
+    let file = tempfile::NamedTempFile::new()?;
+    let path = file.path().to_path_buf();
+    tokio::task::spawn_blocking(move || workbook.save(path)).await??;
+
+    let mut file = tokio::fs::File::open(file.path()).await?;
+    let mut buffer = vec![0; FILE_CHUNK_BYTES];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 || !send(sender, Bytes::copy_from_slice(&buffer[..read])).await {
+            return Ok(());
+        }
+    }
+}
+
+fn export_cells(booking: &AdminBookingResponse) -> Vec<Cell> {
+    vec![
+        Cell::Text(booking.reference.clone()),
+        Cell::Text(booking.booking_date.to_string()),
+        Cell::Text(booking.package_title.clone()),
+        Cell::Text(booking.user_name.clone()),
+        Cell::Text(booking.user_email.clone()),
+        Cell::Number(booking.number_of_people as i64),
+        Cell::Number(booking.base_amount as i64),
+        Cell::Number(booking.tax_amount as i64),
+        Cell::Number(booking.total_amount as i64),
+        Cell::Number(booking.amount_paid as i64),
+        Cell::Number((booking.total_amount - booking.amount_paid) as i64),
+        Cell::Text(booking.status.clone()),
+        Cell::Text(booking.created_at.to_rfc3339()),
+    ]
+}
+
+/// Text cells are escaped so customer-entered values cannot run as formulas.
+/// The XLSX path needs no escaping as `write_string` never stores formulas.
+fn encode_csv(rows: &[Vec<Cell>]) -> Result<Bytes, ExportError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.write_record(row.iter().map(|cell| match cell {
+            Cell::Text(text) => escape_formula(text),
+            Cell::Number(number) => number.to_string(),
+        }))?;
+    }
+    Ok(Bytes::from(writer.into_inner().map_err(|e| e.to_string())?))
+}
+
+// Returns false once the client has gone away
+async fn send(sender: &ExportSender, chunk: Bytes) -> bool {
+    sender.send(Ok(chunk)).await.is_ok()
+}
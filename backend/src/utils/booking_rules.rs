@@ -0,0 +1,223 @@
+use actix_web::HttpResponse;
+use chrono::{Datelike, Duration, NaiveDate};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::PackageBookingRules;
+
+/// Why a departure date was refused. `code` is stable for the frontend to map.
+#[derive(Debug)]
+pub enum DateRuleViolation {
+    InPast,
+    InsideLeadTime { min_lead_days: i32, earliest_date: NaiveDate },
+    BeyondAdvanceWindow { max_advance_days: i32, latest_date: NaiveDate },
+    WeekdayNotAllowed { allowed_weekdays: Vec<i32> },
+    NotADepartureDate { departure_dates: Vec<NaiveDate> },
+}
+
+impl DateRuleViolation {
+    pub fn code(&self) -> &'static str {
+        match self {
+            DateRuleViolation::InPast => "booking_date_in_past",
+            DateRuleViolation::InsideLeadTime { .. } => "booking_date_inside_lead_time",
+            DateRuleViolation::BeyondAdvanceWindow { .. } => "booking_date_beyond_advance_window",
+            DateRuleViolation::WeekdayNotAllowed { .. } => "booking_date_weekday_not_allowed",
+            DateRuleViolation::NotADepartureDate { .. } => "booking_date_not_a_departure",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            DateRuleViolation::InPast => "Booking date cannot be in the past".to_string(),
+            DateRuleViolation::InsideLeadTime { min_lead_days, .. } => {
+                format!("This package must be booked at least {} day(s) in advance", min_lead_days)
+            }
+            DateRuleViolation::BeyondAdvanceWindow { max_advance_days, .. } => {
+                format!("This package can be booked at most {} day(s) in advance", max_advance_days)
+            }
+            DateRuleViolation::WeekdayNotAllowed { .. } => "This package does not depart on that weekday".to_string(),
+            DateRuleViolation::NotADepartureDate { .. } => "This package has no departure on that date".to_string(),
+        }
+    }
+
+    fn details(&self) -> serde_json::Value {
+        match self {
+            DateRuleViolation::InPast => serde_json::json!({}),
+            DateRuleViolation::InsideLeadTime { min_lead_days, earliest_date } => serde_json::json!({
+                "min_lead_days": min_lead_days,
+                "earliest_date": earliest_date
+            }),
+            DateRuleViolation::BeyondAdvanceWindow { max_advance_days, latest_date } => serde_json::json!({
+                "max_advance_days": max_advance_days,
+                "latest_date": latest_date
+            }),
+            DateRuleViolation::WeekdayNotAllowed { allowed_weekdays } => serde_json::json!({
+                "allowed_weekdays": allowed_weekdays
+            }),
+            DateRuleViolation::NotADepartureDate { departure_dates } => serde_json::json!({
+                "departure_dates": departure_dates
+            }),
+        }
+    }
+
+    /// The 400 response sent for this violation.
+    pub fn to_response(&self) -> HttpResponse {
+        HttpResponse::BadRequest().json(serde_json::json!({
+            "error": self.message(),
+            "code": self.code(),
+            "details": self.details()
+        }))
+    }
+}
+
+pub async fn package_booking_rules(pool: &PgPool, package_id: Uuid) -> Result<Option<PackageBookingRules>, sqlx::Error> {
+    sqlx::query_as::<_, PackageBookingRules>("SELECT * FROM package_booking_rules WHERE package_id = $1")
+        .bind(package_id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Checks a departure date against a package's rules.
+///
+/// Packages without rules only refuse past dates. Weekdays are ISO numbers
+/// (Monday = 1) and fixed departure dates, when set, are the only dates allowed;
+/// only upcoming fixed dates are reported back in the error.
+pub fn check_booking_date(
+    rules: Option<&PackageBookingRules>,
+    booking_date: NaiveDate,
+    today: NaiveDate,
+) -> Result<(), DateRuleViolation> {
+    if booking_date < today {
+        return Err(DateRuleViolation::InPast);
+    }
+
+    let Some(rules) = rules else {
+        return Ok(());
+    };
+
+    let earliest_date = today + Duration::days(rules.min_lead_days as i64);
+    if booking_date < earliest_date {
+        return Err(DateRuleViolation::InsideLeadTime {
+            min_lead_days: rules.min_lead_days,
+            earliest_date,
+        });
+    }
+
+    if let Some(max_advance_days) = rules.max_advance_days {
+        let latest_date = today + Duration::days(max_advance_days as i64);
+        if booking_date > latest_date {
+            return Err(DateRuleViolation::BeyondAdvanceWindow { max_advance_days, latest_date });
+        }
+    }
+
+    let weekday = booking_date.weekday().number_from_monday() as i32;
+    if !rules.departure_weekdays.is_empty() && !rules.departure_weekdays.contains(&weekday) {
+        return Err(DateRuleViolation::WeekdayNotAllowed {
+            allowed_weekdays: rules.departure_weekdays.clone(),
+        });
+    }
+
+    if !rules.fixed_departure_dates.is_empty() && !rules.fixed_departure_dates.contains(&booking_date) {
+        let mut departure_dates: Vec<NaiveDate> = rules
+            .fixed_departure_dates
+            .iter()
+            .copied()
+            .filter(|date| *date >= earliest_date)
+            .collect();
+        departure_dates.sort();
+        return Err(DateRuleViolation::NotADepartureDate { departure_dates });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    // A Monday
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+    }
+
+    fn days(n: i64) -> NaiveDate {
+        today() + Duration::days(n)
+    }
+
+    fn rules(min_lead_days: i32, max_advance_days: Option<i32>) -> PackageBookingRules {
+        PackageBookingRules {
+            package_id: Uuid::new_v4(),
+            min_lead_days,
+            max_advance_days,
+            departure_weekdays: Vec::new(),
+            fixed_departure_dates: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn without_rules_only_past_dates_are_refused() {
+        assert!(check_booking_date(None, today(), today()).is_ok());
+        assert!(check_booking_date(None, days(1000), today()).is_ok());
+        assert!(matches!(check_booking_date(None, days(-1), today()), Err(DateRuleViolation::InPast)));
+    }
+
+    #[test]
+    fn lead_time_edge() {
+        let rules = rules(3, None);
+        assert!(check_booking_date(Some(&rules), days(3), today()).is_ok());
+        match check_booking_date(Some(&rules), days(2), today()) {
+            Err(DateRuleViolation::InsideLeadTime { min_lead_days, earliest_date }) => {
+                assert_eq!(min_lead_days, 3);
+                assert_eq!(earliest_date, days(3));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn past_dates_are_reported_before_lead_time() {
+        let rules = rules(3, None);
+        assert!(matches!(check_booking_date(Some(&rules), days(-1), today()), Err(DateRuleViolation::InPast)));
+    }
+
+    #[test]
+    fn advance_window_edge() {
+        let rules = rules(0, Some(30));
+        assert!(check_booking_date(Some(&rules), days(30), today()).is_ok());
+        match check_booking_date(Some(&rules), days(31), today()) {
+            Err(DateRuleViolation::BeyondAdvanceWindow { max_advance_days, latest_date }) => {
+                assert_eq!(max_advance_days, 30);
+                assert_eq!(latest_date, days(30));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn weekdays_are_iso_numbered() {
+        let mut rules = rules(0, None);
+        rules.departure_weekdays = vec![6, 7];
+        assert!(check_booking_date(Some(&rules), days(5), today()).is_ok()); // Saturday
+        assert!(check_booking_date(Some(&rules), days(6), today()).is_ok()); // Sunday
+        assert!(matches!(
+            check_booking_date(Some(&rules), days(7), today()), // Monday
+            Err(DateRuleViolation::WeekdayNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn fixed_departures_report_only_bookable_dates_in_order() {
+        let mut rules = rules(2, None);
+        rules.fixed_departure_dates = vec![days(20), days(1), days(10)];
+        assert!(check_booking_date(Some(&rules), days(10), today()).is_ok());
+        match check_booking_date(Some(&rules), days(11), today()) {
+            Err(DateRuleViolation::NotADepartureDate { departure_dates }) => {
+                assert_eq!(departure_dates, vec![days(10), days(20)]);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use sqlx::{Postgres, QueryBuilder};
+
+use crate::models::BookingSearchQuery;
+use crate::utils::messages::STAFF_UNREAD_SQL;
+
+const SORT_COLUMNS: &[(&str, &str)] = &[
+    ("created_at", "b.created_at"),
+    ("booking_date", "b.booking_date"),
+    ("total_amount", "b.total_amount"),
+    ("reference", "b.reference"),
+    ("status", "b.status"),
+];
+
+/// Rejects sort fields and directions the listing does not support.
+pub fn validate_search(query: &BookingSearchQuery) -> Result<(), String> {
+    if let Some(sort) = query.sort.as_deref() {
+        if !SORT_COLUMNS.iter().any(|(name, _)| *name == sort) {
+            let names: Vec<&str> = SORT_COLUMNS.iter().map(|(name, _)| *name).collect();
+            return Err(format!("sort must be one of: {}", names.join(", ")));
+        }
+    }
+
+    if let Some(order) = query.order.as_deref() {
+        if !order.eq_ignore_ascii_case("asc") && !order.eq_ignore_ascii_case("desc") {
+            return Err("order must be asc or desc".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the filtered and sorted admin booking query, selecting the
+/// columns of `AdminBookingResponse`. Callers append any LIMIT/OFFSET.
+pub fn select_bookings(query: &BookingSearchQuery) -> QueryBuilder<'static, Postgres> {
+    let mut builder = QueryBuilder::new(format!(
+        r#"
+        SELECT b.id, b.reference, b.booking_date, b.number_of_people, b.base_amount, b.tax_amount,
+               b.total_amount, b.amount_paid, b.status, b.special_requests, b.hold_expires_at, b.created_at, b.version,
+               COALESCE(p.title, r.title) as package_title, u.first_name || ' ' || u.last_name as user_name, u.email as user_email,
+               {} as unread_messages
+        FROM bookings b
+        LEFT JOIN packages p ON b.package_id = p.id
+        LEFT JOIN rfqs r ON b.rfq_id = r.id
+        JOIN users u ON b.user_id = u.id
+        "#,
+        STAFF_UNREAD_SQL
+    ));
+    push_filters(&mut builder, query);
+
+    let column = query
+        .sort
+        .as_deref()
+        .and_then(|sort| SORT_COLUMNS.iter().find(|(name, _)| *name == sort))
+        .map(|(_, column)| *column)
+        .unwrap_or("b.created_at");
+    let direction = match query.order.as_deref() {
+        Some(order) if order.eq_ignore_ascii_case("asc") => "ASC",
+        _ => "DESC",
+    };
+    // Tie-break on id so pages are stable
+    builder.push(format!(" ORDER BY {} {}, b.id", column, direction));
+
+    builder
+}
+
+/// Counts the bookings matching the same filters as `select_bookings`.
+pub fn count_bookings(query: &BookingSearchQuery) -> QueryBuilder<'static, Postgres> {
+    let mut builder = QueryBuilder::new(
+        r#"
+        SELECT COUNT(*)
+        FROM bookings b
+        LEFT JOIN packages p ON b.package_id = p.id
+        LEFT JOIN rfqs r ON b.rfq_id = r.id
+        JOIN users u ON b.user_id = u.id
+        "#
+    );
+    push_filters(&mut builder, query);
+    builder
+}
+
+fn push_filters(builder: &mut QueryBuilder<'static, Postgres>, query: &BookingSearchQuery) {
+    builder.push(" WHERE true");
+
+    if let Some(status) = query.status.as_deref() {
+        let statuses: Vec<String> = status
+            .split(',')
+            .map(|status| status.trim().to_lowercase())
+            .filter(|status| !status.is_empty())
+            .collect();
+        if !statuses.is_empty() {
+            builder.push(" AND b.status = ANY(").push_bind(statuses).push(")");
+        }
+    }
+    if let Some(date_from) = query.date_from {
+        builder.push(" AND b.booking_date >= ").push_bind(date_from);
+    }
+    if let Some(date_to) = query.date_to {
+        builder.push(" AND b.booking_date <= ").push_bind(date_to);
+    }
+    if let Some(package_id) = query.package_id {
+        builder.push(" AND b.package_id = ").push_bind(package_id);
+    }
+    if let Some(email) = query.email.as_deref().filter(|email| !email.is_empty()) {
+        builder.push(" AND u.email ILIKE ").push_bind(like_pattern(email));
+    }
+    if let Some(reference) = query.reference.as_deref().filter(|reference| !reference.is_empty()) {
+        builder.push(" AND b.reference ILIKE ").push_bind(like_pattern(reference));
+    }
+    if let Some(min_amount) = query.min_amount {
+        builder.push(" AND b.total_amount >= ").push_bind(min_amount);
+    }
+    if let Some(max_amount) = query.max_amount {
+        builder.push(" AND b.total_amount <= ").push_bind(max_amount);
+    }
+}
+
+// Substring match with LIKE wildcards in the input taken literally
+fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
@@ -0,0 +1,57 @@
+use actix_web::HttpRequest;
+use std::net::IpAddr;
+
+/// The client's IP address. `X-Forwarded-For` is only honoured when the
+/// connection comes from a trusted proxy, and then the rightmost address that
+/// is not itself a trusted proxy is used, so clients cannot spoof it.
+pub fn client_ip(req: &HttpRequest, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let forwarded_for = req
+        .headers()
+        .get_all("X-Forwarded-For")
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",");
+    resolve_client_ip(req.peer_addr().map(|addr| addr.ip()), &forwarded_for, trusted_proxies)
+}
+
+fn resolve_client_ip(peer: Option<IpAddr>, forwarded_for: &str, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let mut client = peer?;
+    for hop in forwarded_for.rsplit(',') {
+        if !trusted_proxies.contains(&client) {
+            break;
+        }
+        match hop.trim().parse::<IpAddr>() {
+            Ok(ip) => client = ip,
+            Err(_) => break,
+        }
+    }
+    Some(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn ignores_forwarded_header_from_untrusted_peer() {
+        let resolved = resolve_client_ip(Some(ip("203.0.113.9")), "198.51.100.1", &[ip("10.0.0.1")]);
+        assert_eq!(resolved, Some(ip("203.0.113.9")));
+    }
+
+    #[test]
+    fn takes_rightmost_untrusted_hop_behind_proxies() {
+        let trusted = [ip("10.0.0.1"), ip("10.0.0.2")];
+        let resolved = resolve_client_ip(Some(ip("10.0.0.1")), "1.1.1.1, 198.51.100.7, 10.0.0.2", &trusted);
+        assert_eq!(resolved, Some(ip("198.51.100.7")));
+    }
+
+    #[test]
+    fn stops_at_unparseable_hop() {
+        let resolved = resolve_client_ip(Some(ip("10.0.0.1")), "garbage", &[ip("10.0.0.1")]);
+        assert_eq!(resolved, Some(ip("10.0.0.1")));
+    }
+}
@@ -0,0 +1,55 @@
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The strong ETag for a record version.
+pub fn etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// The version a write expects to replace, taken from `If-Match` or else the
+/// `version` in the body. `Ok(None)` means the write is unconditional
+/// (no precondition, or `If-Match: *`). An `If-Match` that names no version
+/// we could have issued can never match, so it fails straight away.
+pub fn expected_version(req: &HttpRequest, body_version: Option<i32>) -> Result<Option<i32>, HttpResponse> {
+    let Some(if_match) = req.headers().get("If-Match") else {
+        return Ok(body_version);
+    };
+
+    let if_match = if_match.to_str().unwrap_or_default().trim();
+    if if_match == "*" {
+        return Ok(None);
+    }
+
+    if_match
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .and_then(|version| version.parse::<i32>().ok())
+        .map(Some)
+        .ok_or_else(|| precondition_failed(None))
+}
+
+/// Rejects a partial update sent without `If-Match` or `version`.
+pub fn precondition_required() -> HttpResponse {
+    HttpResponse::PreconditionRequired().json(serde_json::json!({
+        "error": "Send the version you edited as an If-Match header or a version field"
+    }))
+}
+
+/// Rejects a stale write, telling the client which version is current.
+pub fn precondition_failed(current_version: Option<i32>) -> HttpResponse {
+    let mut response = HttpResponse::PreconditionFailed();
+    if let Some(version) = current_version {
+        response.insert_header(("ETag", etag(version)));
+    }
+    response.json(serde_json::json!({
+        "error": "This record has been changed since you loaded it; reload and try again",
+        "current_version": current_version
+    }))
+}
+
+/// Outcome of a conditional write.
+pub enum VersionedWrite<T> {
+    Updated(T),
+    NotFound,
+    /// The record has moved on to this version.
+    Stale(i32),
+}
@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+/// Seats left on a departure: the package capacity minus every booking that
+/// still holds seats (pending holds and confirmed bookings).
+pub async fn seats_available<'e, E: PgExecutor<'e>>(
+    executor: E,
+    package_id: Uuid,
+    booking_date: NaiveDate,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT p.max_people::BIGINT - COALESCE((
+            SELECT SUM(b.number_of_people)
+            FROM bookings b
+            WHERE b.package_id = p.id AND b.booking_date = $2
+              AND b.status IN ('pending', 'confirmed')
+        ), 0)::BIGINT
+        FROM packages p
+        WHERE p.id = $1
+        "#
+    )
+    .bind(package_id)
+    .bind(booking_date)
+    .fetch_one(executor)
+    .await
+}
+
+/// Seats left on a departure for one variant: its capacity minus the
+/// bookings at that variant still holding seats. The package-wide limit
+/// from `seats_available` applies as well.
+pub async fn variant_seats_available<'e, E: PgExecutor<'e>>(
+    executor: E,
+    variant_id: Uuid,
+    booking_date: NaiveDate,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT v.max_people::BIGINT - COALESCE((
+            SELECT SUM(b.number_of_people)
+            FROM bookings b
+            WHERE b.variant_id = v.id AND b.booking_date = $2
+              AND b.status IN ('pending', 'confirmed')
+        ), 0)::BIGINT
+        FROM package_variants v
+        WHERE v.id = $1
+        "#
+    )
+    .bind(variant_id)
+    .bind(booking_date)
+    .fetch_one(executor)
+    .await
+}
@@ -0,0 +1,11 @@
+use pulldown_cmark::{html, Options, Parser};
+
+/// Renders Markdown to HTML with scripts, event handlers and other unsafe
+/// markup stripped, so the result can be served as-is.
+pub fn render_markdown(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_FOOTNOTES;
+    let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, Parser::new_ext(markdown, options));
+
+    ammonia::clean(&unsafe_html)
+}
@@ -0,0 +1,130 @@
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{BookingMessage, CreateMessageRequest};
+use crate::utils::notifications::queue_notification;
+
+/// Which side of a booking thread is reading or posting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadSide {
+    Customer,
+    Staff,
+}
+
+impl ThreadSide {
+    fn role(self) -> &'static str {
+        match self {
+            ThreadSide::Customer => "customer",
+            ThreadSide::Staff => "staff",
+        }
+    }
+
+    fn last_read_column(self) -> &'static str {
+        match self {
+            ThreadSide::Customer => "customer_last_read_at",
+            ThreadSide::Staff => "staff_last_read_at",
+        }
+    }
+}
+
+/// Unread customer-facing staff replies, for a query aliasing bookings as `b`.
+pub const CUSTOMER_UNREAD_SQL: &str = r#"(
+    SELECT COUNT(*) FROM booking_messages m
+    WHERE m.booking_id = b.id AND m.author_role = 'staff' AND NOT m.is_internal
+      AND m.created_at > COALESCE(b.customer_last_read_at, '-infinity')
+)"#;
+
+/// Unread customer messages, for a query aliasing bookings as `b`.
+pub const STAFF_UNREAD_SQL: &str = r#"(
+    SELECT COUNT(*) FROM booking_messages m
+    WHERE m.booking_id = b.id AND m.author_role = 'customer'
+      AND m.created_at > COALESCE(b.staff_last_read_at, '-infinity')
+)"#;
+
+/// Returns the thread in posting order and marks it read for `side`.
+///
+/// Internal staff notes are only included for staff.
+pub async fn read_thread(pool: &PgPool, booking_id: Uuid, side: ThreadSide) -> Result<Vec<BookingMessage>, sqlx::Error> {
+    let messages = sqlx::query_as::<_, BookingMessage>(
+        r#"
+        SELECT * FROM booking_messages
+        WHERE booking_id = $1 AND ($2 OR NOT is_internal)
+        ORDER BY created_at
+        "#
+    )
+    .bind(booking_id)
+    .bind(side == ThreadSide::Staff)
+    .fetch_all(pool)
+    .await?;
+
+    sqlx::query(&format!("UPDATE bookings SET {} = NOW() WHERE id = $1", side.last_read_column()))
+        .bind(booking_id)
+        .execute(pool)
+        .await?;
+
+    Ok(messages)
+}
+
+/// Adds a message to a booking thread.
+///
+/// Staff replies visible to the customer also queue an email to them.
+pub async fn post_message(
+    pool: &PgPool,
+    booking_id: Uuid,
+    author_id: Uuid,
+    side: ThreadSide,
+    req: &CreateMessageRequest,
+) -> Result<BookingMessage, sqlx::Error> {
+    let is_internal = side == ThreadSide::Staff && req.is_internal.unwrap_or(false);
+    let mut tx = pool.begin().await?;
+
+    let message = sqlx::query_as::<_, BookingMessage>(
+        r#"
+        INSERT INTO booking_messages (id, booking_id, author_id, author_role, body, attachments, is_internal, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(booking_id)
+    .bind(author_id)
+    .bind(side.role())
+    .bind(&req.body)
+    .bind(Json(&req.attachments))
+    .bind(is_internal)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Posting implies the author has seen the thread up to now
+    sqlx::query(&format!("UPDATE bookings SET {} = NOW() WHERE id = $1", side.last_read_column()))
+        .bind(booking_id)
+        .execute(&mut *tx)
+        .await?;
+
+    if side == ThreadSide::Staff && !is_internal {
+        let (user_id, email, reference) = sqlx::query_as::<_, (Uuid, String, String)>(
+            r#"
+            SELECT u.id, u.email, b.reference FROM bookings b
+            JOIN users u ON b.user_id = u.id
+            WHERE b.id = $1
+            "#
+        )
+        .bind(booking_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        queue_notification(
+            &mut *tx,
+            Some(user_id),
+            &email,
+            "booking_message",
+            &format!("New message about booking {}", reference),
+            &req.body,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(message)
+}
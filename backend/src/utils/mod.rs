@@ -1,4 +1,29 @@
 pub mod auth;
 pub mod validation;
+pub mod tax;
+pub mod pdf;
+pub mod reference;
+pub mod inventory;
+pub mod notifications;
+pub mod payments;
+pub mod messages;
+pub mod booking_rules;
+pub mod booking_search;
+pub mod booking_export;
+pub mod slug;
+pub mod markdown;
+pub mod quotes;
+pub mod offers;
+pub mod translations;
+pub mod newsletter;
+pub mod package_content;
+pub mod publishing;
+pub mod revisions;
+pub mod concurrency;
+pub mod package_transfer;
+pub mod package_clone;
+pub mod variants;
+pub mod client_ip;
+pub mod spreadsheet;
 
 pub use auth::*;
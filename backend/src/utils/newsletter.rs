@@ -0,0 +1,78 @@
+use sqlx::PgPool;
+
+use crate::models::{NewsletterSubscriber, SubscriberQuery};
+use crate::utils::spreadsheet::escape_formula;
+
+const EXPORT_HEADERS: [&str; 9] = [
+    "Email",
+    "First name",
+    "Status",
+    "Interests",
+    "Destinations",
+    "Travel scope",
+    "Source",
+    "Registered user",
+    "Confirmed at",
+];
+
+/// Trims and lowercases segment tags, dropping blanks and duplicates.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+/// Subscribers matching the segment filters, newest first. A `None` limit
+/// returns every match.
+pub async fn find_subscribers(
+    pool: &PgPool,
+    query: &SubscriberQuery,
+    limit: Option<i64>,
+    offset: i64,
+) -> Result<Vec<NewsletterSubscriber>, sqlx::Error> {
+    sqlx::query_as::<_, NewsletterSubscriber>(
+        r#"
+        SELECT * FROM newsletter_subscribers
+        WHERE ($1::TEXT IS NULL OR status = $1)
+          AND ($2::TEXT IS NULL OR $2 = ANY(interests))
+          AND ($3::TEXT IS NULL OR $3 = ANY(destinations))
+          AND ($4::TEXT IS NULL OR travel_scope = $4 OR travel_scope = 'any')
+        ORDER BY created_at DESC, id
+        LIMIT $5 OFFSET $6
+        "#
+    )
+    .bind(&query.status)
+    .bind(query.interest.as_deref().map(|tag| tag.trim().to_lowercase()))
+    .bind(query.destination.as_deref().map(|tag| tag.trim().to_lowercase()))
+    .bind(&query.travel_scope)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+}
+
+/// Cells are escaped so subscriber-entered text cannot run as a formula.
+pub fn encode_subscribers_csv(subscribers: &[NewsletterSubscriber]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(EXPORT_HEADERS)?;
+    for subscriber in subscribers {
+        let record = [
+            subscriber.email.clone(),
+            subscriber.first_name.clone().unwrap_or_default(),
+            subscriber.status.clone(),
+            subscriber.interests.join(";"),
+            subscriber.destinations.join(";"),
+            subscriber.travel_scope.clone(),
+            subscriber.source.clone().unwrap_or_default(),
+            if subscriber.user_id.is_some() { "yes" } else { "no" }.to_string(),
+            subscriber.confirmed_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+        ];
+        writer.write_record(record.iter().map(|cell| escape_formula(cell)))?;
+    }
+    Ok(writer.into_inner().map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,57 @@
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+/// Queues a notification in the outbox; `jobs::notifications` sends it over SMTP.
+pub async fn queue_notification<'e, E: PgExecutor<'e>>(
+    executor: E,
+    user_id: Option<Uuid>,
+    email: &str,
+    kind: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), sqlx::Error> {
+    insert_notification(executor, user_id, email, kind, subject, body, None).await
+}
+
+/// Same as `queue_notification` for mailing list emails, which are sent with a
+/// `List-Unsubscribe` header pointing at `unsubscribe_url`.
+pub async fn queue_list_notification<'e, E: PgExecutor<'e>>(
+    executor: E,
+    user_id: Option<Uuid>,
+    email: &str,
+    kind: &str,
+    subject: &str,
+    body: &str,
+    unsubscribe_url: &str,
+) -> Result<(), sqlx::Error> {
+    insert_notification(executor, user_id, email, kind, subject, body, Some(unsubscribe_url)).await
+}
+
+async fn insert_notification<'e, E: PgExecutor<'e>>(
+    executor: E,
+    user_id: Option<Uuid>,
+    email: &str,
+    kind: &str,
+    subject: &str,
+    body: &str,
+    unsubscribe_url: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO notifications (id, user_id, email, kind, subject, body, list_unsubscribe_url, status, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, 'queued', NOW())
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(user_id)
+    .bind(email)
+    .bind(kind)
+    .bind(subject)
+    .bind(body)
+    .bind(unsubscribe_url)
+    .execute(executor)
+    .await?;
+
+    log::info!("Queued {} notification for {}", kind, email);
+    Ok(())
+}
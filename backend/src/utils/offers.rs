@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::models::{Offer, OfferPackage, OfferResponse, PackageOffer, PackageResponse};
+use crate::utils::publishing::VISIBLE_PACKAGE_SQL;
+
+/// Per-person price `p.price` under offer `o`, where `p.price` may be a
+/// variant's price and `p.base_price` is the package's own. A fixed price is
+/// set against the package price, so a variant gets the same saving off its
+/// price, but is never sold below the fixed price. Never above `p.price`.
+pub const OFFER_PRICE_SQL: &str = r#"
+    CASE WHEN o.discount_type = 'percentage'
+         THEN p.price - (p.price::BIGINT * o.discount_value / 10000)::INTEGER
+         ELSE GREATEST(p.price - GREATEST(p.base_price - o.discount_value, 0), LEAST(o.discount_value, p.price))
+    END
+"#;
+
+/// Offers running right now, for a query aliasing offers as `o`.
+pub const LIVE_OFFER_SQL: &str = "o.is_active AND o.starts_at <= NOW() AND o.ends_at > NOW()";
+
+/// Lateral subquery picking the cheapest live offer for package `p`, which
+/// must provide `p.base_price`. Join it with `ON lo.offer_price < p.price` so
+/// offers that save nothing are ignored.
+pub fn best_offer_sql() -> String {
+    format!(
+        r#"
+        SELECT o.id as offer_id, o.title as offer_title, o.badge_label, o.ends_at as offer_ends_at,
+               {} as offer_price
+        FROM offer_packages op
+        JOIN offers o ON op.offer_id = o.id
+        WHERE op.package_id = p.id AND {}
+        ORDER BY offer_price, o.ends_at
+        LIMIT 1
+        "#,
+        OFFER_PRICE_SQL, LIVE_OFFER_SQL
+    )
+}
+
+#[derive(FromRow)]
+struct LiveOffer {
+    package_id: Uuid,
+    offer_id: Uuid,
+    offer_title: String,
+    badge_label: Option<String>,
+    offer_ends_at: DateTime<Utc>,
+    offer_price: i32,
+}
+
+/// Reprices packages that have a live offer, keeping the price they had as
+/// `original_price` for display. Offers apply to that price, so a variant
+/// "from" price is discounted like a list price.
+pub async fn apply_live_offers(pool: &PgPool, packages: &mut [PackageResponse]) -> Result<(), sqlx::Error> {
+    let package_ids: Vec<Uuid> = packages.iter().map(|p| p.id).collect();
+    let prices: Vec<i32> = packages.iter().map(|p| p.price).collect();
+
+    let offers = sqlx::query_as::<_, LiveOffer>(&format!(
+        r#"
+        SELECT p.id as package_id, lo.*
+        FROM (
+            SELECT u.id, u.price, pk.price as base_price
+            FROM UNNEST($1::UUID[], $2::INTEGER[]) AS u(id, price)
+            JOIN packages pk ON pk.id = u.id
+        ) p
+        JOIN LATERAL ({}) lo ON lo.offer_price < p.price
+        "#,
+        best_offer_sql()
+    ))
+    .bind(&package_ids)
+    .bind(&prices)
+    .fetch_all(pool)
+    .await?;
+
+    for offer in offers {
+        if let Some(package) = packages.iter_mut().find(|p| p.id == offer.package_id) {
+            package.original_price = Some(package.price);
+            package.price = offer.offer_price;
+            package.offer = Some(PackageOffer {
+                id: offer.offer_id,
+                title: offer.offer_title,
+                badge_label: offer.badge_label,
+                ends_at: offer.offer_ends_at,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Loads the packages linked to each offer, priced under that offer.
+/// Packages hidden from the public are left out unless `include_inactive` is set.
+pub async fn attach_offer_packages(
+    pool: &PgPool,
+    offers: Vec<Offer>,
+    include_inactive: bool,
+) -> Result<Vec<OfferResponse>, sqlx::Error> {
+    let offer_ids: Vec<Uuid> = offers.iter().map(|o| o.id).collect();
+
+    let packages = sqlx::query_as::<_, OfferPackage>(&format!(
+        r#"
+        SELECT op.offer_id, p.id, p.title, p.image_url, p.price as original_price,
+               {} as offer_price
+        FROM offer_packages op
+        JOIN offers o ON op.offer_id = o.id
+        JOIN (SELECT *, price as base_price FROM packages) p ON op.package_id = p.id
+        WHERE op.offer_id = ANY($1) AND ($2 OR ({}))
+        ORDER BY p.title
+        "#,
+        OFFER_PRICE_SQL,
+        VISIBLE_PACKAGE_SQL
+    ))
+    .bind(&offer_ids)
+    .bind(include_inactive)
+    .fetch_all(pool)
+    .await?;
+
+    let mut responses: Vec<OfferResponse> = offers
+        .into_iter()
+        .map(|offer| OfferResponse { offer, packages: Vec::new() })
+        .collect();
+    for package in packages {
+        if let Some(response) = responses.iter_mut().find(|r| r.offer.id == package.offer_id) {
+            response.packages.push(package);
+        }
+    }
+
+    Ok(responses)
+}
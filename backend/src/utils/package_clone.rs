@@ -0,0 +1,117 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::{ClonePackageRequest, Package};
+use crate::utils::slug::{unique_slug, SlugEntity};
+
+/// Copies a package into a new draft linked back to it, with its variants,
+/// FAQs, policy sections, payment schedule and departure rules. Translations and
+/// offers are not copied. Returns `None` if the source does not exist.
+pub async fn copy_package(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    req: &ClonePackageRequest,
+) -> Result<Option<Package>, sqlx::Error> {
+    let source = sqlx::query_as::<_, Package>("SELECT * FROM packages WHERE id = $1")
+        .bind(source_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    let Some(source) = source else {
+        return Ok(None);
+    };
+
+    let title = req.title.clone().unwrap_or_else(|| format!("{} (copy)", source.title));
+    let slug = match &req.slug {
+        Some(slug) => slug.clone(),
+        None => match unique_slug(&mut *conn, SlugEntity::Package, &title).await? {
+            Some(slug) => slug,
+            // The title has nothing to slugify; fall back to the source's slug
+            None => unique_slug(&mut *conn, SlugEntity::Package, &format!("{} copy", source.slug))
+                .await?
+                .unwrap_or_default(),
+        },
+    };
+
+    let package = sqlx::query_as::<_, Package>(
+        r#"
+        INSERT INTO packages (id, slug, title, description, price, duration_days, max_people, category_id, image_url,
+                              highlights, inclusions, exclusions, itinerary, is_featured, is_international, status,
+                              cloned_from, created_at, updated_at)
+        SELECT $1, $2, $3, description, price, COALESCE($4, duration_days), max_people, category_id, image_url,
+               highlights, inclusions, exclusions, itinerary, is_featured, is_international, 'draft',
+               id, NOW(), NOW()
+        FROM packages
+        WHERE id = $5
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(&slug)
+    .bind(&title)
+    .bind(req.duration_days)
+    .bind(source_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO package_variants (id, package_id, name, position, price, added_inclusions, removed_inclusions, max_people, is_active, created_at, updated_at)
+        SELECT uuid_generate_v4(), $2, name, position, price, added_inclusions, removed_inclusions, max_people, is_active, NOW(), NOW()
+        FROM package_variants WHERE package_id = $1
+        "#
+    )
+    .bind(source_id)
+    .bind(package.id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO package_faqs (id, package_id, position, template_id, question, answer, created_at)
+        SELECT uuid_generate_v4(), $2, position, template_id, question, answer, NOW()
+        FROM package_faqs WHERE package_id = $1
+        "#
+    )
+    .bind(source_id)
+    .bind(package.id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO package_policies (id, package_id, position, template_id, section_type, title, body, created_at)
+        SELECT uuid_generate_v4(), $2, position, template_id, section_type, title, body, NOW()
+        FROM package_policies WHERE package_id = $1
+        "#
+    )
+    .bind(source_id)
+    .bind(package.id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO payment_schedule_templates (id, package_id, position, label, percent_basis_points, due_days_before_departure, created_at)
+        SELECT uuid_generate_v4(), $2, position, label, percent_basis_points, due_days_before_departure, NOW()
+        FROM payment_schedule_templates WHERE package_id = $1
+        "#
+    )
+    .bind(source_id)
+    .bind(package.id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO package_booking_rules (package_id, min_lead_days, max_advance_days, departure_weekdays, fixed_departure_dates, created_at, updated_at)
+        SELECT $2, min_lead_days, max_advance_days, departure_weekdays, fixed_departure_dates, NOW(), NOW()
+        FROM package_booking_rules WHERE package_id = $1
+        "#
+    )
+    .bind(source_id)
+    .bind(package.id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(Some(package))
+}
@@ -0,0 +1,56 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{PackageFaq, PackagePolicy};
+
+/// A package's FAQs in order, taking the text from linked templates.
+pub async fn package_faqs(pool: &PgPool, package_id: Uuid) -> Result<Vec<PackageFaq>, sqlx::Error> {
+    sqlx::query_as::<_, PackageFaq>(
+        r#"
+        SELECT f.id, f.template_id, f.position,
+               COALESCE(t.title, f.question) as question, COALESCE(t.body, f.answer) as answer
+        FROM package_faqs f
+        LEFT JOIN content_templates t ON f.template_id = t.id
+        WHERE f.package_id = $1
+        ORDER BY f.position
+        "#
+    )
+    .bind(package_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// A package's policy sections in order, taking the text from linked templates.
+pub async fn package_policies(pool: &PgPool, package_id: Uuid) -> Result<Vec<PackagePolicy>, sqlx::Error> {
+    sqlx::query_as::<_, PackagePolicy>(
+        r#"
+        SELECT pp.id, pp.template_id, pp.position,
+               COALESCE(t.section_type, pp.section_type) as section_type,
+               COALESCE(t.title, pp.title) as title, COALESCE(t.body, pp.body) as body
+        FROM package_policies pp
+        LEFT JOIN content_templates t ON pp.template_id = t.id
+        WHERE pp.package_id = $1
+        ORDER BY pp.position
+        "#
+    )
+    .bind(package_id)
+    .fetch_all(pool)
+    .await
+}
+
+/// Whether every id names a template of `kind`.
+pub async fn templates_exist(pool: &PgPool, kind: &str, template_ids: &[Uuid]) -> Result<bool, sqlx::Error> {
+    let mut ids = template_ids.to_vec();
+    ids.sort();
+    ids.dedup();
+
+    let found = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM content_templates WHERE id = ANY($1) AND kind = $2"
+    )
+    .bind(&ids)
+    .bind(kind)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(found == ids.len() as i64)
+}
@@ -0,0 +1,374 @@
+use sqlx::{Acquire, PgPool};
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{CreatePackageRequest, ImportReport, ImportRowResult, PackageExportRow, PackageImportRow};
+use crate::utils::revisions::record_package_revision;
+use crate::utils::slug::{unique_slug, SlugEntity};
+use crate::utils::spreadsheet::{escape_formula, unescape_formula};
+
+const CSV_HEADERS: [&str; 16] = [
+    "external_id",
+    "slug",
+    "title",
+    "description",
+    "price",
+    "duration_days",
+    "max_people",
+    "category_slug",
+    "image_url",
+    "highlights",
+    "inclusions",
+    "exclusions",
+    "itinerary",
+    "is_featured",
+    "is_international",
+    "status",
+];
+
+/// List columns, written as `|`-separated items.
+const LIST_COLUMNS: [&str; 3] = ["highlights", "inclusions", "exclusions"];
+const INTEGER_COLUMNS: [&str; 3] = ["price", "duration_days", "max_people"];
+const BOOLEAN_COLUMNS: [&str; 2] = ["is_featured", "is_international"];
+
+/// Every package, archived ones included, oldest first.
+pub async fn export_packages(pool: &PgPool) -> Result<Vec<PackageExportRow>, sqlx::Error> {
+    sqlx::query_as::<_, PackageExportRow>(
+        r#"
+        SELECT p.external_id, p.slug, p.title, p.description, p.price, p.duration_days, p.max_people,
+               c.slug as category_slug, p.image_url, p.highlights, p.inclusions, p.exclusions,
+               p.itinerary, p.is_featured, p.is_international, p.status
+        FROM packages p
+        JOIN categories c ON c.id = p.category_id
+        ORDER BY p.created_at, p.id
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Cells are escaped so text that looks like a formula stays text.
+pub fn encode_packages_csv(packages: &[PackageExportRow]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(CSV_HEADERS)?;
+    for package in packages {
+        let record = [
+            package.external_id.clone().unwrap_or_default(),
+            package.slug.clone(),
+            package.title.clone(),
+            package.description.clone(),
+            package.price.to_string(),
+            package.duration_days.to_string(),
+            package.max_people.to_string(),
+            package.category_slug.clone(),
+            package.image_url.clone().unwrap_or_default(),
+            package.highlights.join(" | "),
+            package.inclusions.join(" | "),
+            package.exclusions.join(" | "),
+            package.itinerary.to_string(),
+            package.is_featured.to_string(),
+            package.is_international.to_string(),
+            package.status.clone(),
+        ];
+        writer.write_record(record.iter().map(|cell| escape_formula(cell)))?;
+    }
+    Ok(writer.into_inner().map_err(|e| e.to_string())?)
+}
+
+/// Reads CSV rows into the JSON shape of `PackageImportRow`, so both formats
+/// share one validation path. Formula escaping added on export is undone, and
+/// empty cells count as missing. A cell that
+/// cannot be converted is passed through as text and reported when the row
+/// is checked.
+pub fn decode_packages_csv(data: &[u8]) -> Result<Vec<serde_json::Value>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(data);
+    let headers = reader.headers()?.clone();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let mut row = serde_json::Map::new();
+        for (header, cell) in headers.iter().zip(record.iter()) {
+            let cell = unescape_formula(cell);
+            if cell.is_empty() {
+                continue;
+            }
+            let value = if LIST_COLUMNS.contains(&header) {
+                serde_json::Value::from(
+                    cell.split('|')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .collect::<Vec<_>>(),
+                )
+            } else if INTEGER_COLUMNS.contains(&header) {
+                cell.parse::<i64>().map_or_else(|_| cell.into(), serde_json::Value::from)
+            } else if BOOLEAN_COLUMNS.contains(&header) {
+                match cell.to_lowercase().as_str() {
+                    "true" | "yes" | "1" => true.into(),
+                    "false" | "no" | "0" => false.into(),
+                    _ => cell.into(),
+                }
+            } else if header == "itinerary" {
+                serde_json::from_str(cell).unwrap_or_else(|_| cell.into())
+            } else {
+                cell.into()
+            };
+            row.insert(header.to_string(), value);
+        }
+        rows.push(serde_json::Value::Object(row));
+    }
+    Ok(rows)
+}
+
+/// Checks and applies an import in one transaction. Each row is written
+/// under a savepoint so that one bad row is reported without hiding the
+/// others. The transaction is only committed when every row succeeds and
+/// this is not a dry run. Returns the report and the ids of updated packages.
+pub async fn import_packages(
+    pool: &PgPool,
+    editor_id: Uuid,
+    rows: Vec<serde_json::Value>,
+    dry_run: bool,
+) -> Result<(ImportReport, Vec<Uuid>), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut results = Vec::with_capacity(rows.len());
+    let mut seen_keys = HashSet::new();
+
+    for (index, value) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+        let key = |field: &str| value.get(field).and_then(|v| v.as_str()).map(str::to_string);
+        let mut result = ImportRowResult {
+            row: row_number,
+            external_id: key("external_id"),
+            slug: key("slug"),
+            action: "error",
+            package_id: None,
+            errors: None,
+        };
+
+        let row = match serde_json::from_value::<PackageImportRow>(value) {
+            Ok(row) => row,
+            Err(e) => {
+                result.errors = Some(serde_json::json!(e.to_string()));
+                results.push(result);
+                continue;
+            }
+        };
+
+        let duplicate = [("external_id", &row.external_id), ("slug", &row.slug)]
+            .into_iter()
+            .filter_map(|(field, value)| value.as_ref().map(|value| (field, value.clone())))
+            .find(|key| !seen_keys.insert(key.clone()));
+        if let Some((field, _)) = duplicate {
+            result.errors = Some(serde_json::json!(format!("Another row has the same {}", field)));
+            results.push(result);
+            continue;
+        }
+
+        let category_id = sqlx::query_scalar::<_, Uuid>("SELECT id FROM categories WHERE slug = $1")
+            .bind(&row.category_slug)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(category_id) = category_id else {
+            result.errors = Some(serde_json::json!(format!("Unknown category '{}'", row.category_slug)));
+            results.push(result);
+            continue;
+        };
+
+        let request = CreatePackageRequest {
+            title: row.title,
+            slug: row.slug,
+            description: row.description,
+            price: row.price,
+            duration_days: row.duration_days,
+            max_people: row.max_people,
+            category_id,
+            image_url: row.image_url,
+            highlights: row.highlights,
+            inclusions: row.inclusions,
+            exclusions: row.exclusions,
+            itinerary: row.itinerary.unwrap_or_else(|| serde_json::json!({})),
+            is_featured: row.is_featured,
+            is_international: row.is_international,
+            status: None,
+            publish_at: None,
+            unpublish_at: None,
+            version: None,
+        };
+        if let Err(errors) = request.validate() {
+            result.errors = Some(serde_json::json!(errors));
+            results.push(result);
+            continue;
+        }
+
+        let existing = sqlx::query_as::<_, (Uuid, Option<String>)>(
+            r#"
+            SELECT id, external_id FROM packages
+            WHERE external_id = $1 OR (slug = $2 AND NOT EXISTS (SELECT 1 FROM packages WHERE external_id = $1))
+            "#
+        )
+        .bind(&row.external_id)
+        .bind(&request.slug)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let (Some((_, Some(current))), Some(external_id)) = (&existing, &row.external_id) {
+            if current != external_id {
+                result.errors = Some(serde_json::json!("This slug belongs to a package with a different external_id"));
+                results.push(result);
+                continue;
+            }
+        }
+
+        let mut savepoint = (&mut *tx).begin().await?;
+        let written = match existing {
+            Some((package_id, _)) => {
+                update_imported_package(&mut savepoint, editor_id, package_id, &request, row.external_id.as_deref())
+                    .await
+                    .map(|()| ("update", Some(package_id)))
+            }
+            None => {
+                create_imported_package(&mut savepoint, &request, row.external_id.as_deref())
+                    .await
+                    .map(|package_id| ("create", package_id))
+            }
+        };
+
+        match written {
+            Ok((_, None)) => {
+                savepoint.rollback().await?;
+                result.errors = Some(serde_json::json!("Could not generate a slug from the title; please provide one"));
+            }
+            Ok((action, Some(package_id))) => {
+                savepoint.commit().await?;
+                result.action = action;
+                result.package_id = Some(package_id);
+            }
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                savepoint.rollback().await?;
+                result.errors = Some(serde_json::json!("A package with this slug already exists"));
+            }
+            Err(e) => return Err(e),
+        }
+        results.push(result);
+    }
+
+    let errors = results.iter().filter(|result| result.action == "error").count();
+    let applied = !dry_run && errors == 0;
+    if applied {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
+        // Packages that would have been created do not exist
+        for result in results.iter_mut().filter(|result| result.action == "create") {
+            result.package_id = None;
+        }
+    }
+
+    let count = |action: &str| results.iter().filter(|result| result.action == action).count();
+
+    let updated_ids = results
+        .iter()
+        .filter(|result| result.action == "update")
+        .filter_map(|result| result.package_id)
+        .collect();
+    let report = ImportReport {
+        dry_run,
+        applied,
+        creates: count("create"),
+        updates: count("update"),
+        errors,
+        rows: results,
+    };
+    Ok((report, updated_ids))
+}
+
+/// Creates a draft, returning `None` if no slug was given and none can be
+/// generated from the title.
+async fn create_imported_package(
+    conn: &mut sqlx::PgConnection,
+    req: &CreatePackageRequest,
+    external_id: Option<&str>,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let slug = match &req.slug {
+        Some(slug) => slug.clone(),
+        None => match unique_slug(&mut *conn, SlugEntity::Package, &req.title).await? {
+            Some(slug) => slug,
+            None => return Ok(None),
+        },
+    };
+
+    let package_id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO packages (id, slug, title, description, price, duration_days, max_people, category_id,
+                              image_url, highlights, inclusions, exclusions, itinerary, is_featured,
+                              is_international, status, external_id, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, 'draft', $16, NOW(), NOW())
+        RETURNING id
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(&slug)
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(req.price)
+    .bind(req.duration_days)
+    .bind(req.max_people)
+    .bind(req.category_id)
+    .bind(&req.image_url)
+    .bind(&req.highlights)
+    .bind(&req.inclusions)
+    .bind(&req.exclusions)
+    .bind(&req.itinerary)
+    .bind(req.is_featured.unwrap_or(false))
+    .bind(req.is_international.unwrap_or(false))
+    .bind(external_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(package_id))
+}
+
+/// Overwrites the package's content, saving the previous version as a
+/// revision. The workflow status is left alone.
+async fn update_imported_package(
+    conn: &mut sqlx::PgConnection,
+    editor_id: Uuid,
+    package_id: Uuid,
+    req: &CreatePackageRequest,
+    external_id: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    record_package_revision(&mut *conn, package_id, editor_id).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE packages
+        SET title = $2, description = $3, price = $4, duration_days = $5, max_people = $6,
+            category_id = $7, image_url = $8, highlights = $9, inclusions = $10,
+            exclusions = $11, itinerary = $12, is_featured = $13, is_international = $14,
+            slug = COALESCE($15, slug), external_id = COALESCE($16, external_id), updated_at = NOW()
+        WHERE id = $1
+        "#
+    )
+    .bind(package_id)
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(req.price)
+    .bind(req.duration_days)
+    .bind(req.max_people)
+    .bind(req.category_id)
+    .bind(&req.image_url)
+    .bind(&req.highlights)
+    .bind(&req.inclusions)
+    .bind(&req.exclusions)
+    .bind(&req.itinerary)
+    .bind(req.is_featured.unwrap_or(false))
+    .bind(req.is_international.unwrap_or(false))
+    .bind(&req.slug)
+    .bind(external_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,350 @@
+use chrono::{Duration, NaiveDate};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::jobs::waitlist::close_offers;
+use crate::models::{
+    BookingInstallment, BookingPayment, PaymentScheduleTemplate, PaymentSummaryResponse,
+    RecordPaymentRequest,
+};
+
+const BASIS_POINTS: i64 = 10_000;
+
+pub struct InstallmentPlan {
+    pub position: i32,
+    pub label: String,
+    pub amount: i32,
+    pub due_date: NaiveDate,
+}
+
+pub enum PaymentOutcome {
+    Recorded { payment: BookingPayment, status: String },
+    Overpayment { outstanding: i32 },
+    NotPayable { status: String },
+}
+
+/// Splits a booking total into installments following the package template.
+///
+/// Without a template the full amount is due immediately. Due dates that
+/// would fall before `today` (late bookings) are pulled forward to `today`,
+/// and the last installment absorbs rounding so the amounts add up exactly.
+pub fn plan_installments(
+    total_amount: i32,
+    departure: NaiveDate,
+    today: NaiveDate,
+    templates: &[PaymentScheduleTemplate],
+) -> Vec<InstallmentPlan> {
+    if templates.is_empty() {
+        return vec![InstallmentPlan {
+            position: 1,
+            label: "Full payment".to_string(),
+            amount: total_amount,
+            due_date: today,
+        }];
+    }
+
+    let mut remaining = total_amount as i64;
+    templates
+        .iter()
+        .enumerate()
+        .map(|(index, template)| {
+            let amount = if index + 1 == templates.len() {
+                remaining
+            } else {
+                (total_amount as i64 * template.percent_basis_points as i64 / BASIS_POINTS).min(remaining)
+            };
+            remaining -= amount;
+
+            let due_date = template
+                .due_days_before_departure
+                .map(|days| departure - Duration::days(days as i64))
+                .unwrap_or(today)
+                .max(today);
+
+            InstallmentPlan {
+                position: index as i32 + 1,
+                label: template.label.clone(),
+                amount: amount as i32,
+                due_date,
+            }
+        })
+        .collect()
+}
+
+/// Creates the installment schedule for a newly inserted booking.
+pub async fn create_installments(
+    conn: &mut PgConnection,
+    booking_id: Uuid,
+    package_id: Uuid,
+    total_amount: i32,
+    departure: NaiveDate,
+    today: NaiveDate,
+) -> Result<(), sqlx::Error> {
+    let templates = sqlx::query_as::<_, PaymentScheduleTemplate>(
+        "SELECT * FROM payment_schedule_templates WHERE package_id = $1 ORDER BY position"
+    )
+    .bind(package_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let plans = plan_installments(total_amount, departure, today, &templates);
+    insert_installments(conn, booking_id, &plans).await
+}
+
+/// Splits an accepted quote's total into a deposit due now and a balance due
+/// `balance_due_days_before` the event starts (or now, if that has passed).
+pub fn plan_quote_installments(
+    total_amount: i32,
+    deposit_basis_points: i32,
+    balance_due_days_before: i32,
+    start_date: NaiveDate,
+    today: NaiveDate,
+) -> Vec<InstallmentPlan> {
+    let balance_due_date = (start_date - Duration::days(balance_due_days_before as i64)).max(today);
+    let deposit = (total_amount as i64 * deposit_basis_points as i64 / BASIS_POINTS) as i32;
+
+    if deposit == 0 || deposit == total_amount {
+        return vec![InstallmentPlan {
+            position: 1,
+            label: "Full payment".to_string(),
+            amount: total_amount,
+            due_date: if deposit == 0 { balance_due_date } else { today },
+        }];
+    }
+
+    vec![
+        InstallmentPlan {
+            position: 1,
+            label: "Deposit".to_string(),
+            amount: deposit,
+            due_date: today,
+        },
+        InstallmentPlan {
+            position: 2,
+            label: "Balance".to_string(),
+            amount: total_amount - deposit,
+            due_date: balance_due_date,
+        },
+    ]
+}
+
+pub async fn insert_installments(
+    conn: &mut PgConnection,
+    booking_id: Uuid,
+    plans: &[InstallmentPlan],
+) -> Result<(), sqlx::Error> {
+    for plan in plans {
+        sqlx::query(
+            r#"
+            INSERT INTO booking_installments (booking_id, position, label, amount, due_date)
+            VALUES ($1, $2, $3, $4, $5)
+            "#
+        )
+        .bind(booking_id)
+        .bind(plan.position)
+        .bind(&plan.label)
+        .bind(plan.amount)
+        .bind(plan.due_date)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Paid versus outstanding amounts for a booking, with its schedule and payments.
+pub async fn payment_summary(pool: &PgPool, booking_id: Uuid) -> Result<Option<PaymentSummaryResponse>, sqlx::Error> {
+    let booking = sqlx::query_as::<_, (String, String, i32, i32)>(
+        "SELECT reference, status, total_amount, amount_paid FROM bookings WHERE id = $1"
+    )
+    .bind(booking_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some((reference, status, total_amount, amount_paid)) = booking else {
+        return Ok(None);
+    };
+
+    let installments = sqlx::query_as::<_, BookingInstallment>(
+        "SELECT * FROM booking_installments WHERE booking_id = $1 ORDER BY position"
+    )
+    .bind(booking_id)
+    .fetch_all(pool)
+    .await?;
+
+    let payments = sqlx::query_as::<_, BookingPayment>(
+        "SELECT * FROM booking_payments WHERE booking_id = $1 ORDER BY created_at"
+    )
+    .bind(booking_id)
+    .fetch_all(pool)
+    .await?;
+
+    let next_due_date = installments
+        .iter()
+        .find(|installment| installment.paid_amount < installment.amount)
+        .map(|installment| installment.due_date);
+
+    Ok(Some(PaymentSummaryResponse {
+        booking_id,
+        reference,
+        status,
+        total_amount,
+        amount_paid,
+        outstanding: total_amount - amount_paid,
+        next_due_date,
+        installments,
+        payments,
+    }))
+}
+
+/// Records a payment and allocates it to installments in schedule order.
+///
+/// A pending booking is confirmed (and its hold released) once its first
+/// installment, usually the deposit, is fully paid.
+pub async fn record_payment(
+    pool: &PgPool,
+    booking_id: Uuid,
+    req: &RecordPaymentRequest,
+    recorded_by: Uuid,
+) -> Result<Option<PaymentOutcome>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let booking = sqlx::query_as::<_, (String, i32, i32)>(
+        "SELECT status, total_amount, amount_paid FROM bookings WHERE id = $1 FOR UPDATE"
+    )
+    .bind(booking_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some((status, total_amount, amount_paid)) = booking else {
+        return Ok(None);
+    };
+
+    if matches!(status.as_str(), "cancelled" | "expired") {
+        return Ok(Some(PaymentOutcome::NotPayable { status }));
+    }
+
+    let outstanding = total_amount - amount_paid;
+    if req.amount > outstanding {
+        return Ok(Some(PaymentOutcome::Overpayment { outstanding }));
+    }
+
+    let payment = sqlx::query_as::<_, BookingPayment>(
+        r#"
+        INSERT INTO booking_payments (id, booking_id, amount, method, transaction_reference, recorded_by, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW())
+        RETURNING *
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(booking_id)
+    .bind(req.amount)
+    .bind(&req.method)
+    .bind(&req.transaction_reference)
+    .bind(recorded_by)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let installments = sqlx::query_as::<_, BookingInstallment>(
+        "SELECT * FROM booking_installments WHERE booking_id = $1 ORDER BY position FOR UPDATE"
+    )
+    .bind(booking_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut remaining = req.amount;
+    let mut first_installment_paid = false;
+    for installment in &installments {
+        let due = installment.amount - installment.paid_amount;
+        let applied = due.min(remaining);
+        if applied > 0 {
+            sqlx::query("UPDATE booking_installments SET paid_amount = paid_amount + $2, updated_at = NOW() WHERE id = $1")
+                .bind(installment.id)
+                .bind(applied)
+                .execute(&mut *tx)
+                .await?;
+            remaining -= applied;
+        }
+        if installment.position == 1 {
+            first_installment_paid = installment.paid_amount + applied >= installment.amount;
+        }
+    }
+
+    let status: String = sqlx::query_scalar(
+        r#"
+        UPDATE bookings
+        SET amount_paid = amount_paid + $2,
+            status = CASE WHEN status = 'pending' AND $3 THEN 'confirmed' ELSE status END,
+            hold_expires_at = CASE WHEN status = 'pending' AND $3 THEN NULL ELSE hold_expires_at END,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING status
+        "#
+    )
+    .bind(booking_id)
+    .bind(req.amount)
+    .bind(first_installment_paid)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if status == "confirmed" {
+        close_offers(&mut *tx, &[booking_id], "converted").await?;
+    }
+
+    tx.commit().await?;
+    Ok(Some(PaymentOutcome::Recorded { payment, status }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 11, day).unwrap()
+    }
+
+    fn template(position: i32, percent_basis_points: i32, due_days_before_departure: Option<i32>) -> PaymentScheduleTemplate {
+        PaymentScheduleTemplate {
+            id: Uuid::new_v4(),
+            package_id: Uuid::new_v4(),
+            position,
+            label: format!("Installment {}", position),
+            percent_basis_points,
+            due_days_before_departure,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn without_a_template_everything_is_due_today() {
+        let plans = plan_installments(5000, date(30), date(1), &[]);
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].amount, 5000);
+        assert_eq!(plans[0].due_date, date(1));
+    }
+
+    #[test]
+    fn last_installment_absorbs_rounding() {
+        let templates = [template(1, 3333, None), template(2, 3333, Some(14)), template(3, 3334, Some(7))];
+        let plans = plan_installments(1001, date(30), date(1), &templates);
+        let amounts: Vec<i32> = plans.iter().map(|plan| plan.amount).collect();
+        assert_eq!(amounts, vec![333, 333, 335]);
+        assert_eq!(plans.iter().map(|plan| plan.position).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn due_dates_count_back_from_departure() {
+        let templates = [template(1, 2000, None), template(2, 8000, Some(14))];
+        let plans = plan_installments(1000, date(30), date(1), &templates);
+        assert_eq!(plans[0].due_date, date(1));
+        assert_eq!(plans[1].due_date, date(16));
+    }
+
+    #[test]
+    fn late_bookings_pull_due_dates_forward_to_today() {
+        let templates = [template(1, 2000, None), template(2, 8000, Some(14))];
+        let plans = plan_installments(1000, date(30), date(20), &templates);
+        assert_eq!(plans[1].due_date, date(20));
+    }
+}
@@ -0,0 +1,282 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use printpdf::{
+    BuiltinFont, Color, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+    Rect, Rgb,
+};
+use qrcode::QrCode;
+use std::error::Error;
+
+use crate::models::TaxLine;
+
+const ISSUER_NAME: &str = "Webmeen Travel";
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const WRAP_COLUMNS: usize = 95;
+
+pub struct InvoiceDocument {
+    pub invoice_number: String,
+    pub issued_at: DateTime<Utc>,
+    pub booking_reference: String,
+    pub customer_name: String,
+    pub customer_email: String,
+    pub package_title: String,
+    pub booking_date: NaiveDate,
+    pub number_of_people: i32,
+    pub base_amount: i32,
+    pub tax_lines: Vec<TaxLine>,
+    pub tax_amount: i32,
+    pub total_amount: i32,
+}
+
+pub struct VoucherDocument {
+    pub booking_code: String,
+    pub status: String,
+    pub package_title: String,
+    pub booking_date: NaiveDate,
+    pub duration_days: i32,
+    pub number_of_people: i32,
+    pub travellers: Vec<String>,
+    pub itinerary: serde_json::Value,
+    pub special_requests: Option<String>,
+}
+
+/// Minimal top-down text cursor over printpdf's bottom-up coordinate system.
+struct PageWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    y: f32,
+}
+
+impl PageWriter {
+    fn new(title: &str) -> Result<Self, printpdf::Error> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let regular = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            doc,
+            layer,
+            regular,
+            bold,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn ensure_space(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn text(&mut self, text: &str, size: f32, bold: bool) {
+        let line_height = size * 0.5;
+        for line in wrap(text, WRAP_COLUMNS) {
+            self.ensure_space(line_height);
+            self.y -= line_height;
+            let font = if bold { &self.bold } else { &self.regular };
+            self.layer.use_text(line, size, Mm(MARGIN), Mm(self.y), font);
+        }
+    }
+
+    /// Writes a label with its value in a second column.
+    fn row(&mut self, label: &str, value: &str, bold: bool) {
+        let size = 10.0;
+        self.ensure_space(size * 0.5);
+        self.y -= size * 0.5;
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer.use_text(label, size, Mm(MARGIN), Mm(self.y), font);
+        self.layer.use_text(value, size, Mm(PAGE_WIDTH - MARGIN - 45.0), Mm(self.y), font);
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn rule(&mut self) {
+        self.ensure_space(3.0);
+        self.y -= 2.0;
+        self.layer.add_rect(Rect::new(
+            Mm(MARGIN),
+            Mm(self.y),
+            Mm(PAGE_WIDTH - MARGIN),
+            Mm(self.y + 0.3),
+        ));
+        self.y -= 1.0;
+    }
+
+    /// Draws a QR code with its top-right corner at the current line.
+    fn qr_code(&mut self, data: &str, size: f32) -> Result<(), qrcode::types::QrError> {
+        let code = QrCode::new(data.as_bytes())?;
+        let width = code.width();
+        let module = size / width as f32;
+        let left = PAGE_WIDTH - MARGIN - size;
+        let top = self.y;
+
+        self.layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        for (index, color) in code.to_colors().into_iter().enumerate() {
+            if color == qrcode::Color::Dark {
+                let x = left + (index % width) as f32 * module;
+                let y = top - (index / width) as f32 * module;
+                self.layer.add_rect(Rect::new(Mm(x), Mm(y - module), Mm(x + module), Mm(y)));
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Vec<u8>, printpdf::Error> {
+        self.doc.save_to_bytes()
+    }
+}
+
+pub fn render_invoice(invoice: &InvoiceDocument) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut page = PageWriter::new(&format!("Invoice {}", invoice.invoice_number))?;
+
+    page.text(ISSUER_NAME, 18.0, true);
+    page.text("Tax Invoice", 12.0, false);
+    page.gap(4.0);
+    page.row("Invoice number", &invoice.invoice_number, false);
+    page.row("Invoice date", &invoice.issued_at.format("%d %b %Y").to_string(), false);
+    page.row("Booking reference", &invoice.booking_reference, false);
+    page.gap(4.0);
+
+    page.text("Billed to", 11.0, true);
+    page.text(&invoice.customer_name, 10.0, false);
+    page.text(&invoice.customer_email, 10.0, false);
+    page.gap(4.0);
+
+    page.text(&invoice.package_title, 11.0, true);
+    page.text(
+        &format!(
+            "Travel date {} for {} traveller(s)",
+            invoice.booking_date.format("%d %b %Y"),
+            invoice.number_of_people
+        ),
+        10.0,
+        false,
+    );
+    page.rule();
+    page.row("Taxable value", &format_amount(invoice.base_amount), false);
+    for line in &invoice.tax_lines {
+        let label = format!(
+            "{} @ {}%{}",
+            line.name,
+            format_rate(line.rate_basis_points),
+            if line.mode == "inclusive" { " (incl.)" } else { "" }
+        );
+        page.row(&label, &format_amount(line.amount), false);
+    }
+    page.row("Total tax", &format_amount(invoice.tax_amount), false);
+    page.rule();
+    page.row("Grand total", &format_amount(invoice.total_amount), true);
+    page.gap(8.0);
+    page.text("This is a computer generated invoice and does not require a signature.", 8.0, false);
+
+    Ok(page.finish()?)
+}
+
+pub fn render_voucher(voucher: &VoucherDocument) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut page = PageWriter::new(&format!("Travel voucher {}", voucher.booking_code))?;
+
+    page.qr_code(&voucher.booking_code, 35.0)?;
+    page.text(ISSUER_NAME, 18.0, true);
+    page.text("Travel Voucher", 12.0, false);
+    page.gap(4.0);
+    page.text(&format!("Booking {}", voucher.booking_code), 10.0, true);
+    page.text(&format!("Status: {}", voucher.status), 10.0, false);
+    page.gap(12.0);
+
+    page.text(&voucher.package_title, 12.0, true);
+    page.text(
+        &format!(
+            "Departure {} - {} day(s), {} traveller(s)",
+            voucher.booking_date.format("%d %b %Y"),
+            voucher.duration_days,
+            voucher.number_of_people
+        ),
+        10.0,
+        false,
+    );
+    page.rule();
+
+    page.text("Travellers", 11.0, true);
+    for (index, name) in voucher.travellers.iter().enumerate() {
+        page.text(&format!("{}. {}", index + 1, name), 10.0, false);
+    }
+    page.gap(4.0);
+
+    page.text("Itinerary", 11.0, true);
+    for day in voucher.itinerary.as_array().into_iter().flatten() {
+        let number = day.get("day").and_then(|d| d.as_i64()).unwrap_or_default();
+        let title = day.get("title").and_then(|t| t.as_str()).unwrap_or_default();
+        page.text(&format!("Day {}: {}", number, title), 10.0, true);
+        for activity in day.get("activities").and_then(|a| a.as_array()).into_iter().flatten() {
+            if let Some(activity) = activity.as_str() {
+                page.text(&format!("- {}", activity), 9.0, false);
+            }
+        }
+    }
+
+    if let Some(special_requests) = &voucher.special_requests {
+        page.gap(4.0);
+        page.text("Special requests", 11.0, true);
+        page.text(special_requests, 10.0, false);
+    }
+
+    page.gap(8.0);
+    page.text("Please carry this voucher and a valid photo ID for every traveller.", 8.0, false);
+
+    Ok(page.finish()?)
+}
+
+/// Formats whole-rupee amounts with Indian digit grouping, e.g. `INR 1,23,456`.
+fn format_amount(amount: i32) -> String {
+    let digits = amount.unsigned_abs().to_string();
+    let (head, tail) = digits.split_at(digits.len().saturating_sub(3));
+    let mut groups = Vec::new();
+    let mut head = head;
+    while head.len() > 2 {
+        let (rest, group) = head.split_at(head.len() - 2);
+        groups.push(group);
+        head = rest;
+    }
+    if !head.is_empty() {
+        groups.push(head);
+    }
+    groups.reverse();
+    groups.push(tail);
+
+    let sign = if amount < 0 { "-" } else { "" };
+    format!("INR {}{}", sign, groups.join(","))
+}
+
+fn format_rate(basis_points: i32) -> String {
+    if basis_points % 100 == 0 {
+        (basis_points / 100).to_string()
+    } else {
+        format!("{:.2}", basis_points as f64 / 100.0)
+    }
+}
+
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + word.len() + 1 > columns {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+
+/// Packages visible to the public, for a query aliasing packages as `p`.
+/// Scheduled packages appear once `publish_at` has passed, and any package
+/// disappears again at `unpublish_at`.
+pub const VISIBLE_PACKAGE_SQL: &str = r#"
+    p.status IN ('published', 'scheduled')
+    AND (p.publish_at IS NULL OR p.publish_at <= NOW())
+    AND (p.unpublish_at IS NULL OR p.unpublish_at > NOW())
+"#;
+
+/// Checks a package's status against its publish window. Scheduling needs a
+/// go-live time, and the window must not end before it starts.
+pub fn check_publish_window(
+    status: &str,
+    publish_at: Option<DateTime<Utc>>,
+    unpublish_at: Option<DateTime<Utc>>,
+) -> Result<(), &'static str> {
+    if status == "scheduled" && publish_at.is_none() {
+        return Err("A scheduled package needs publish_at");
+    }
+    if let (Some(publish_at), Some(unpublish_at)) = (publish_at, unpublish_at) {
+        if unpublish_at <= publish_at {
+            return Err("unpublish_at must be after publish_at");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::models::QuoteItemRequest;
+
+const BASIS_POINTS: i64 = 10_000;
+
+pub struct PricedQuoteItem<'a> {
+    pub item: &'a QuoteItemRequest,
+    pub cost_amount: i32,
+    pub sell_amount: i32,
+}
+
+pub struct PricedQuote<'a> {
+    pub items: Vec<PricedQuoteItem<'a>>,
+    pub cost_total: i32,
+    pub margin_total: i32,
+    pub total_amount: i32,
+}
+
+/// Prices each line as quantity times unit cost plus its margin, rounding the
+/// margin down. Returns `None` if any amount would not fit the stored columns.
+pub fn price_quote(items: &[QuoteItemRequest]) -> Option<PricedQuote<'_>> {
+    let mut cost_total: i64 = 0;
+    let mut total_amount: i64 = 0;
+
+    let mut priced = Vec::with_capacity(items.len());
+    for item in items {
+        let cost_amount = item.quantity as i64 * item.unit_cost as i64;
+        let sell_amount = cost_amount + cost_amount * item.margin_basis_points as i64 / BASIS_POINTS;
+        cost_total += cost_amount;
+        total_amount += sell_amount;
+
+        priced.push(PricedQuoteItem {
+            item,
+            cost_amount: i32::try_from(cost_amount).ok()?,
+            sell_amount: i32::try_from(sell_amount).ok()?,
+        });
+    }
+
+    Some(PricedQuote {
+        items: priced,
+        cost_total: i32::try_from(cost_total).ok()?,
+        margin_total: i32::try_from(total_amount - cost_total).ok()?,
+        total_amount: i32::try_from(total_amount).ok()?,
+    })
+}
@@ -0,0 +1,70 @@
+use rand::Rng;
+use uuid::Uuid;
+
+const REFERENCE_PREFIX: &str = "WMT-";
+const REFERENCE_LENGTH: usize = 6;
+
+// Digits and letters without the easily confused 0/O, 1/I/L
+const REFERENCE_ALPHABET: &[u8] = b"23456789ABCDEFGHJKMNPQRSTUVWXYZ";
+
+/// Generates a random booking reference such as `WMT-7K3Q9P`.
+///
+/// References are not unique by construction; callers rely on the unique
+/// index on `bookings.reference` and retry on collision.
+pub fn generate_booking_reference() -> String {
+    let mut rng = rand::thread_rng();
+    let code: String = (0..REFERENCE_LENGTH)
+        .map(|_| REFERENCE_ALPHABET[rng.gen_range(0..REFERENCE_ALPHABET.len())] as char)
+        .collect();
+
+    format!("{}{}", REFERENCE_PREFIX, code)
+}
+
+/// Interprets a booking path parameter as either a UUID or a reference.
+///
+/// References are matched case-insensitively and the `WMT-` prefix is optional.
+pub fn booking_lookup_key(raw: &str) -> (Option<Uuid>, Option<String>) {
+    let raw = raw.trim();
+
+    if let Ok(id) = Uuid::parse_str(raw) {
+        return (Some(id), None);
+    }
+
+    let upper = raw.to_uppercase();
+    let reference = if upper.starts_with(REFERENCE_PREFIX) {
+        upper
+    } else {
+        format!("{}{}", REFERENCE_PREFIX, upper)
+    };
+
+    (None, Some(reference))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_references_use_the_prefix_and_alphabet() {
+        for _ in 0..100 {
+            let reference = generate_booking_reference();
+            let code = reference.strip_prefix(REFERENCE_PREFIX).unwrap();
+            assert_eq!(code.len(), REFERENCE_LENGTH);
+            assert!(code.bytes().all(|c| REFERENCE_ALPHABET.contains(&c)));
+        }
+    }
+
+    #[test]
+    fn lookup_key_accepts_a_uuid() {
+        let id = Uuid::new_v4();
+        assert_eq!(booking_lookup_key(&id.to_string()), (Some(id), None));
+    }
+
+    #[test]
+    fn lookup_key_normalises_references() {
+        let expected = (None, Some("WMT-7K3Q9P".to_string()));
+        assert_eq!(booking_lookup_key("WMT-7K3Q9P"), expected);
+        assert_eq!(booking_lookup_key("wmt-7k3q9p"), expected);
+        assert_eq!(booking_lookup_key(" 7k3q9p\n"), expected);
+    }
+}
@@ -0,0 +1,104 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::FieldChange;
+
+/// Package columns that editors change and a rollback restores. Workflow
+/// state and timestamps are left alone.
+pub const REVISION_FIELDS: [&str; 14] = [
+    "slug",
+    "title",
+    "description",
+    "price",
+    "duration_days",
+    "max_people",
+    "category_id",
+    "image_url",
+    "highlights",
+    "inclusions",
+    "exclusions",
+    "itinerary",
+    "is_featured",
+    "is_international",
+];
+
+/// Locks the package and stores its current state as the next revision.
+/// Returns the revision number, or `None` if the package does not exist.
+pub async fn record_package_revision(
+    conn: &mut PgConnection,
+    package_id: Uuid,
+    edited_by: Uuid,
+) -> Result<Option<i32>, sqlx::Error> {
+    let exists = sqlx::query_scalar::<_, Uuid>("SELECT id FROM packages WHERE id = $1 FOR UPDATE")
+        .bind(package_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+    if exists.is_none() {
+        return Ok(None);
+    }
+
+    let revision_number = sqlx::query_scalar::<_, i32>(
+        r#"
+        INSERT INTO package_revisions (id, package_id, revision_number, snapshot, edited_by, created_at)
+        SELECT $1, p.id,
+               COALESCE((SELECT MAX(revision_number) FROM package_revisions WHERE package_id = p.id), 0) + 1,
+               to_jsonb(p), $3, NOW()
+        FROM packages p
+        WHERE p.id = $2
+        RETURNING revision_number
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(package_id)
+    .bind(edited_by)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Some(revision_number))
+}
+
+/// Field-by-field differences between two package snapshots.
+pub fn diff_snapshots(from: &serde_json::Value, to: &serde_json::Value) -> Vec<FieldChange> {
+    REVISION_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let before = from.get(field).cloned().unwrap_or(serde_json::Value::Null);
+            let after = to.get(field).cloned().unwrap_or(serde_json::Value::Null);
+            (before != after).then_some(FieldChange { field, from: before, to: after })
+        })
+        .collect()
+}
+
+/// Restores the revisioned fields of a package from one of its snapshots,
+/// including fields that were null. Fields missing from an older snapshot
+/// keep their current value.
+pub async fn restore_package_revision(
+    conn: &mut PgConnection,
+    package_id: Uuid,
+    revision_number: i32,
+) -> Result<bool, sqlx::Error> {
+    let assignments: Vec<String> = REVISION_FIELDS
+        .iter()
+        .map(|field| format!("{0} = CASE WHEN r.snapshot ? '{0}' THEN r.{0} ELSE p.{0} END", field))
+        .collect();
+
+    let result = sqlx::query(&format!(
+        r#"
+        UPDATE packages p
+        SET {}, updated_at = NOW()
+        FROM (
+            SELECT snapshot, (jsonb_populate_record(NULL::packages, snapshot)).*
+            FROM package_revisions
+            WHERE package_id = $1 AND revision_number = $2
+        ) r
+        WHERE p.id = $1
+        "#,
+        assignments.join(", ")
+    ))
+    .bind(package_id)
+    .bind(revision_number)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
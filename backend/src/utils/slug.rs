@@ -0,0 +1,133 @@
+use actix_web::HttpResponse;
+use deunicode::deunicode;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Turns a title into a URL slug, transliterating to ASCII first, e.g.
+/// `"Kerala: 5 Days!"` -> `"kerala-5-days"` and `"Café Zürich"` -> `"cafe-zurich"`.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in deunicode(text).chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Records with a public slug. Old slugs are kept in `slug_redirects` by a
+/// database trigger whenever one changes.
+#[derive(Debug, Clone, Copy)]
+pub enum SlugEntity {
+    Package,
+    Category,
+    Post,
+}
+
+impl SlugEntity {
+    fn table(self) -> &'static str {
+        match self {
+            SlugEntity::Package => "packages",
+            SlugEntity::Category => "categories",
+            SlugEntity::Post => "posts",
+        }
+    }
+
+    fn entity_type(self) -> &'static str {
+        match self {
+            SlugEntity::Package => "package",
+            SlugEntity::Category => "category",
+            SlugEntity::Post => "post",
+        }
+    }
+}
+
+/// Slugifies `title`, adding `-2`, `-3`, ... if the slug is already taken or
+/// still redirects somewhere. Returns `None` if the title has nothing to slugify.
+pub async fn unique_slug(executor: impl PgExecutor<'_>, entity: SlugEntity, title: &str) -> Result<Option<String>, sqlx::Error> {
+    let base = slugify(title);
+    if base.is_empty() {
+        return Ok(None);
+    }
+
+    let taken: Vec<String> = sqlx::query_scalar(&format!(
+        r#"
+        SELECT slug FROM {} WHERE slug = $1 OR slug LIKE $2
+        UNION
+        SELECT old_slug FROM slug_redirects WHERE entity_type = $3 AND (old_slug = $1 OR old_slug LIKE $2)
+        "#,
+        entity.table()
+    ))
+    .bind(&base)
+    .bind(format!("{}-%", base))
+    .bind(entity.entity_type())
+    .fetch_all(executor)
+    .await?;
+
+    let mut slug = base.clone();
+    let mut suffix = 1;
+    while taken.contains(&slug) {
+        suffix += 1;
+        slug = format!("{}-{}", base, suffix);
+    }
+    Ok(Some(slug))
+}
+
+/// The current slug of the record an old slug used to point at.
+pub async fn resolve_slug_redirect(pool: &PgPool, entity: SlugEntity, old_slug: &str) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar(&format!(
+        r#"
+        SELECT t.slug FROM slug_redirects r
+        JOIN {} t ON r.target_id = t.id
+        WHERE r.entity_type = $1 AND r.old_slug = $2
+        "#,
+        entity.table()
+    ))
+    .bind(entity.entity_type())
+    .bind(old_slug)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Splits a path segment into an id or a slug, for routes that accept either.
+pub fn slug_lookup_key(value: &str) -> (Option<Uuid>, Option<String>) {
+    match Uuid::parse_str(value) {
+        Ok(id) => (Some(id), None),
+        Err(_) => (None, Some(value.to_string())),
+    }
+}
+
+/// Permanent redirect from an old slug, with the current slug in the body
+/// for clients that do not follow redirects.
+pub fn slug_redirect(location: &str, slug: &str) -> HttpResponse {
+    HttpResponse::MovedPermanently()
+        .insert_header(("Location", location))
+        .json(serde_json::json!({
+            "slug": slug
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_collapses_punctuation_and_case() {
+        assert_eq!(slugify("Kerala: 5 Days!"), "kerala-5-days");
+        assert_eq!(slugify("  --Goa -- Beaches--  "), "goa-beaches");
+    }
+
+    #[test]
+    fn slugify_transliterates_to_ascii() {
+        assert_eq!(slugify("Café Zürich"), "cafe-zurich");
+        assert_eq!(slugify("Ærø Ø"), "aero-o");
+    }
+
+    #[test]
+    fn slugify_can_be_empty() {
+        assert_eq!(slugify(""), "");
+        assert_eq!(slugify("!!! ???"), "");
+    }
+}
@@ -0,0 +1,46 @@
+/// Characters that make spreadsheet apps treat a cell as a formula.
+const FORMULA_TRIGGERS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Prefixes text that a spreadsheet would run as a formula with `'`, so
+/// customer-supplied values in CSV exports are shown as plain text.
+pub fn escape_formula(text: &str) -> String {
+    if text.starts_with(FORMULA_TRIGGERS) {
+        format!("'{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Reverses `escape_formula` for a cell read back from an exported file.
+pub fn unescape_formula(text: &str) -> &str {
+    match text.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_TRIGGERS) => rest,
+        _ => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_formula_triggers() {
+        for text in ["=SUM(A1:A2)", "+91 98765", "-1", "@cmd", "\tx", "\rx"] {
+            assert_eq!(escape_formula(text), format!("'{}", text));
+        }
+    }
+
+    #[test]
+    fn leaves_plain_text_alone() {
+        assert_eq!(escape_formula("Kerala backwaters"), "Kerala backwaters");
+        assert_eq!(escape_formula(""), "");
+        assert_eq!(escape_formula("'quoted"), "'quoted");
+    }
+
+    #[test]
+    fn unescape_round_trips() {
+        for text in ["=1+1", "-5 nights", "plain", "'quoted", ""] {
+            assert_eq!(unescape_formula(&escape_formula(text)), text);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::{TaxBreakdown, TaxLine, TaxRule};
+
+const BASIS_POINTS: i64 = 10_000;
+
+/// Loads the active tax rules that apply to a package of the given category and region.
+pub async fn applicable_tax_rules(
+    pool: &PgPool,
+    category_id: Option<Uuid>,
+    is_international: bool,
+) -> Result<Vec<TaxRule>, sqlx::Error> {
+    let region = if is_international { "international" } else { "domestic" };
+
+    sqlx::query_as::<_, TaxRule>(
+        r#"
+        SELECT * FROM tax_rules
+        WHERE is_active = true
+          AND (category_id IS NULL OR category_id = $1)
+          AND (region = 'all' OR region = $2)
+        ORDER BY created_at
+        "#
+    )
+    .bind(category_id)
+    .bind(region)
+    .fetch_all(pool)
+    .await
+}
+
+/// Splits a listed amount into base and tax lines.
+///
+/// Inclusive rules are carved out of `list_amount`; exclusive rules are then
+/// charged on top of the resulting base. This is the only place tax is
+/// computed, so quotes, bookings and invoices always agree. Returns `None`
+/// if the total with tax would not fit in an amount column.
+pub fn compute_tax(list_amount: i32, rules: &[TaxRule]) -> Option<TaxBreakdown> {
+    let list_amount = list_amount as i64;
+    let (inclusive, exclusive): (Vec<&TaxRule>, Vec<&TaxRule>) =
+        rules.iter().partition(|rule| rule.mode == "inclusive");
+
+    let inclusive_rate: i64 = inclusive.iter().map(|rule| rule.rate_basis_points as i64).sum();
+    let base_amount = divide_rounded(list_amount * BASIS_POINTS, BASIS_POINTS + inclusive_rate);
+
+    let mut tax_lines = Vec::with_capacity(rules.len());
+
+    // The last inclusive line absorbs rounding so base + inclusive tax == list amount
+    let mut inclusive_remaining = list_amount - base_amount;
+    for (index, rule) in inclusive.iter().enumerate() {
+        let amount = if index + 1 == inclusive.len() {
+            inclusive_remaining
+        } else {
+            divide_rounded(base_amount * rule.rate_basis_points as i64, BASIS_POINTS)
+                .min(inclusive_remaining)
+        };
+        inclusive_remaining -= amount;
+        tax_lines.push(tax_line(rule, amount));
+    }
+
+    for rule in exclusive {
+        let amount = divide_rounded(base_amount * rule.rate_basis_points as i64, BASIS_POINTS);
+        tax_lines.push(tax_line(rule, amount));
+    }
+
+    let tax_amount: i64 = tax_lines.iter().map(|line| line.amount as i64).sum();
+
+    Some(TaxBreakdown {
+        base_amount: base_amount as i32,
+        tax_lines,
+        tax_amount: i32::try_from(tax_amount).ok()?,
+        total_amount: i32::try_from(base_amount + tax_amount).ok()?,
+    })
+}
+
+/// Stores a booking's tax lines as computed by `compute_tax`, keeping their
+/// order so quotes, the API and invoices list them the same way.
+pub async fn insert_booking_tax_lines(
+    conn: &mut PgConnection,
+    booking_id: Uuid,
+    tax_lines: &[TaxLine],
+) -> Result<(), sqlx::Error> {
+    for (index, line) in tax_lines.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO booking_tax_lines (booking_id, tax_rule_id, name, rate_basis_points, mode, amount, position)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(booking_id)
+        .bind(line.tax_rule_id)
+        .bind(&line.name)
+        .bind(line.rate_basis_points)
+        .bind(&line.mode)
+        .bind(line.amount)
+        .bind(index as i32 + 1)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn tax_line(rule: &TaxRule, amount: i64) -> TaxLine {
+    TaxLine {
+        tax_rule_id: Some(rule.id),
+        name: rule.name.clone(),
+        rate_basis_points: rule.rate_basis_points,
+        mode: rule.mode.clone(),
+        amount: amount as i32,
+    }
+}
+
+// Half-up rounding for non-negative amounts
+fn divide_rounded(numerator: i64, denominator: i64) -> i64 {
+    (numerator + denominator / 2) / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rule(name: &str, rate_basis_points: i32, mode: &str) -> TaxRule {
+        TaxRule {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            category_id: None,
+            region: "all".to_string(),
+            rate_basis_points,
+            mode: mode.to_string(),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn amounts(breakdown: &TaxBreakdown) -> Vec<i32> {
+        breakdown.tax_lines.iter().map(|line| line.amount).collect()
+    }
+
+    #[test]
+    fn no_rules_leaves_amount_untaxed() {
+        let breakdown = compute_tax(1000, &[]).unwrap();
+        assert_eq!(breakdown.base_amount, 1000);
+        assert_eq!(breakdown.tax_amount, 0);
+        assert_eq!(breakdown.total_amount, 1000);
+        assert!(breakdown.tax_lines.is_empty());
+    }
+
+    #[test]
+    fn exclusive_tax_is_added_on_top() {
+        let breakdown = compute_tax(1000, &[rule("GST", 1800, "exclusive")]).unwrap();
+        assert_eq!(breakdown.base_amount, 1000);
+        assert_eq!(breakdown.tax_amount, 180);
+        assert_eq!(breakdown.total_amount, 1180);
+    }
+
+    #[test]
+    fn exclusive_tax_rounds_half_up() {
+        // 5% of 1010 is 50.5, of 1009 is 50.45
+        assert_eq!(compute_tax(1010, &[rule("VAT", 500, "exclusive")]).unwrap().tax_amount, 51);
+        assert_eq!(compute_tax(1009, &[rule("VAT", 500, "exclusive")]).unwrap().tax_amount, 50);
+    }
+
+    #[test]
+    fn inclusive_tax_is_carved_out_of_the_list_amount() {
+        let breakdown = compute_tax(1180, &[rule("GST", 1800, "inclusive")]).unwrap();
+        assert_eq!(breakdown.base_amount, 1000);
+        assert_eq!(breakdown.tax_amount, 180);
+        assert_eq!(breakdown.total_amount, 1180);
+    }
+
+    #[test]
+    fn inclusive_base_rounds_half_up() {
+        // 1004 / 1.6 is 627.5
+        let breakdown = compute_tax(1004, &[rule("Levy", 6000, "inclusive")]).unwrap();
+        assert_eq!(breakdown.base_amount, 628);
+        assert_eq!(breakdown.tax_amount, 376);
+        assert_eq!(breakdown.total_amount, 1004);
+    }
+
+    #[test]
+    fn last_inclusive_line_absorbs_rounding() {
+        // Base 847.46 rounds to 847, 9% of that is 76.23, leaving 77 for the last line
+        let rules = [rule("CGST", 900, "inclusive"), rule("SGST", 900, "inclusive")];
+        let breakdown = compute_tax(1000, &rules).unwrap();
+        assert_eq!(breakdown.base_amount, 847);
+        assert_eq!(amounts(&breakdown), vec![76, 77]);
+        assert_eq!(breakdown.total_amount, 1000);
+    }
+
+    #[test]
+    fn exclusive_tax_is_charged_on_the_inclusive_base() {
+        let rules = [rule("Service charge", 1000, "exclusive"), rule("VAT", 500, "inclusive")];
+        let breakdown = compute_tax(1050, &rules).unwrap();
+        assert_eq!(breakdown.base_amount, 1000);
+        // Inclusive lines come first whatever the rule order
+        assert_eq!(amounts(&breakdown), vec![50, 100]);
+        assert_eq!(breakdown.total_amount, 1150);
+    }
+
+    #[test]
+    fn total_past_i32_is_refused() {
+        assert!(compute_tax(i32::MAX, &[rule("GST", 1800, "exclusive")]).is_none());
+        assert!(compute_tax(i32::MAX, &[rule("GST", 1800, "inclusive")]).is_some());
+    }
+}
@@ -0,0 +1,43 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::middleware::locale::Locale;
+use crate::models::{PackageResponse, PackageTranslation};
+
+/// Swaps in each package's translated text for `locale`. Packages without a
+/// translation, and fields a translation leaves out, keep the default language.
+pub async fn apply_package_translations(
+    pool: &PgPool,
+    packages: &mut [PackageResponse],
+    locale: &Locale,
+) -> Result<(), sqlx::Error> {
+    if locale.is_default {
+        return Ok(());
+    }
+
+    let package_ids: Vec<Uuid> = packages.iter().map(|p| p.id).collect();
+
+    let translations = sqlx::query_as::<_, PackageTranslation>(
+        "SELECT * FROM package_translations WHERE package_id = ANY($1) AND locale = $2"
+    )
+    .bind(&package_ids)
+    .bind(&locale.code)
+    .fetch_all(pool)
+    .await?;
+
+    for translation in translations {
+        if let Some(package) = packages.iter_mut().find(|p| p.id == translation.package_id) {
+            package.title = translation.title;
+            package.description = translation.description;
+            if let Some(highlights) = translation.highlights {
+                package.highlights = highlights;
+            }
+            if let Some(itinerary) = translation.itinerary {
+                package.itinerary = itinerary;
+            }
+            package.locale = Some(translation.locale);
+        }
+    }
+
+    Ok(())
+}
@@ -8,7 +8,7 @@ pub fn validate_password_strength(password: &str) -> Result<(), ValidationError>
     
     let has_uppercase = password.chars().any(|c| c.is_uppercase());
     let has_lowercase = password.chars().any(|c| c.is_lowercase());
-    let has_digit = password.chars().any(|c| c.is_digit(10));
+    let has_digit = password.chars().any(|c| c.is_ascii_digit());
     
     if !has_uppercase || !has_lowercase || !has_digit {
         return Err(ValidationError::new("Password must contain uppercase, lowercase, and digit"));
@@ -17,7 +17,6 @@ pub fn validate_password_strength(password: &str) -> Result<(), ValidationError>
     Ok(())
 }
 
-#[allow(dead_code)]
 pub fn validate_phone_number(phone: &str) -> Result<(), ValidationError> {
     let cleaned = phone.replace(&[' ', '-', '(', ')', '+'][..], "");
     
@@ -25,9 +24,127 @@ pub fn validate_phone_number(phone: &str) -> Result<(), ValidationError> {
         return Err(ValidationError::new("Invalid phone number length"));
     }
     
-    if !cleaned.chars().all(|c| c.is_digit(10)) {
+    if !cleaned.chars().all(|c| c.is_ascii_digit()) {
         return Err(ValidationError::new("Phone number must contain only digits"));
     }
     
     Ok(())
 }
+
+pub fn validate_tax_mode(mode: &str) -> Result<(), ValidationError> {
+    match mode {
+        "inclusive" | "exclusive" => Ok(()),
+        _ => Err(ValidationError::new("Tax mode must be inclusive or exclusive")),
+    }
+}
+
+pub fn validate_tax_region(region: &str) -> Result<(), ValidationError> {
+    match region {
+        "all" | "domestic" | "international" => Ok(()),
+        _ => Err(ValidationError::new("Tax region must be all, domestic or international")),
+    }
+}
+
+/// ISO weekday numbers, Monday = 1 through Sunday = 7.
+pub fn validate_weekdays(weekdays: &[i32]) -> Result<(), ValidationError> {
+    if weekdays.iter().all(|day| (1..=7).contains(day)) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("Weekdays must be numbers from 1 (Monday) to 7 (Sunday)"))
+    }
+}
+
+pub fn validate_slug(slug: &str) -> Result<(), ValidationError> {
+    let valid = !slug.is_empty()
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && !slug.contains("--")
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("Slug must be lowercase letters, digits and single hyphens"))
+    }
+}
+
+/// Links shown to staff must not carry `javascript:`, `data:` or other
+/// schemes a browser would run, so only plain web links are accepted.
+pub fn validate_web_url(url: &str) -> Result<(), ValidationError> {
+    let lower = url.get(..8).unwrap_or(url).to_ascii_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(ValidationError::new("Link must be an http or https URL"))
+    }
+}
+
+pub fn validate_post_status(status: &str) -> Result<(), ValidationError> {
+    match status {
+        "draft" | "published" | "scheduled" => Ok(()),
+        _ => Err(ValidationError::new("Post status must be draft, published or scheduled")),
+    }
+}
+
+pub fn validate_enquiry_status(status: &str) -> Result<(), ValidationError> {
+    match status {
+        "new" | "contacted" | "quoted" | "won" | "lost" => Ok(()),
+        _ => Err(ValidationError::new("Enquiry status must be new, contacted, quoted, won or lost")),
+    }
+}
+
+/// Staff can only close an RFQ by hand; quoting and acceptance set the other statuses.
+pub fn validate_rfq_status(status: &str) -> Result<(), ValidationError> {
+    match status {
+        "declined" | "cancelled" => Ok(()),
+        _ => Err(ValidationError::new("RFQ status must be declined or cancelled")),
+    }
+}
+
+pub fn validate_quote_item_category(category: &str) -> Result<(), ValidationError> {
+    match category {
+        "accommodation" | "venue" | "catering" | "transport" | "activity" | "other" => Ok(()),
+        _ => Err(ValidationError::new(
+            "Category must be accommodation, venue, catering, transport, activity or other",
+        )),
+    }
+}
+
+pub fn validate_discount_type(discount_type: &str) -> Result<(), ValidationError> {
+    match discount_type {
+        "percentage" | "fixed_price" => Ok(()),
+        _ => Err(ValidationError::new("Discount type must be percentage or fixed_price")),
+    }
+}
+
+pub fn validate_travel_scope(scope: &str) -> Result<(), ValidationError> {
+    match scope {
+        "any" | "domestic" | "international" => Ok(()),
+        _ => Err(ValidationError::new("Travel scope must be any, domestic or international")),
+    }
+}
+
+pub fn validate_template_kind(kind: &str) -> Result<(), ValidationError> {
+    match kind {
+        "faq" | "policy" => Ok(()),
+        _ => Err(ValidationError::new("Template kind must be faq or policy")),
+    }
+}
+
+pub fn validate_policy_section_type(section_type: &str) -> Result<(), ValidationError> {
+    match section_type {
+        "visa" | "terms" | "things_to_carry" | "cancellation" | "health_safety" | "other" => Ok(()),
+        _ => Err(ValidationError::new(
+            "Section type must be visa, terms, things_to_carry, cancellation, health_safety or other",
+        )),
+    }
+}
+
+pub fn validate_package_status(status: &str) -> Result<(), ValidationError> {
+    match status {
+        "draft" | "in_review" | "scheduled" | "published" | "archived" => Ok(()),
+        _ => Err(ValidationError::new(
+            "Package status must be draft, in_review, scheduled, published or archived",
+        )),
+    }
+}
@@ -0,0 +1,113 @@
+use sqlx::{FromRow, PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::models::{PackageResponse, PackageVariantResponse};
+use crate::utils::offers::best_offer_sql;
+
+/// Why a booking's variant choice is not acceptable, if it is not: packages
+/// with active variants must be booked at one of them, and only those.
+pub async fn variant_selection_error<'e, E: PgExecutor<'e>>(
+    executor: E,
+    package_id: Uuid,
+    variant_id: Option<Uuid>,
+) -> Result<Option<&'static str>, sqlx::Error> {
+    let (active, selected) = sqlx::query_as::<_, (i64, Option<bool>)>(
+        r#"
+        SELECT COUNT(*) FILTER (WHERE is_active), BOOL_OR(id = $2 AND is_active)
+        FROM package_variants
+        WHERE package_id = $1
+        "#
+    )
+    .bind(package_id)
+    .bind(variant_id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(match variant_id {
+        Some(_) if selected != Some(true) => Some("Variant not found"),
+        None if active > 0 => Some("Choose one of this package's variants"),
+        _ => None,
+    })
+}
+
+/// Shows packages with active variants at their cheapest variant's price,
+/// as a "from" price. Run before offers are applied.
+pub async fn apply_variant_prices(pool: &PgPool, packages: &mut [PackageResponse]) -> Result<(), sqlx::Error> {
+    let package_ids: Vec<Uuid> = packages.iter().map(|p| p.id).collect();
+
+    let from_prices = sqlx::query_as::<_, (Uuid, i32)>(
+        r#"
+        SELECT package_id, MIN(price)
+        FROM package_variants
+        WHERE package_id = ANY($1) AND is_active
+        GROUP BY package_id
+        "#
+    )
+    .bind(&package_ids)
+    .fetch_all(pool)
+    .await?;
+
+    for (package_id, price) in from_prices {
+        if let Some(package) = packages.iter_mut().find(|p| p.id == package_id) {
+            package.price = price;
+            package.is_from_price = true;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(FromRow)]
+struct VariantRow {
+    id: Uuid,
+    name: String,
+    price: i32,
+    offer_price: Option<i32>,
+    added_inclusions: Vec<String>,
+    removed_inclusions: Vec<String>,
+    max_people: i32,
+}
+
+/// A package's active variants, priced under the package's best live offer,
+/// with `inclusions` being the package's list adjusted for each variant.
+pub async fn package_variants(
+    pool: &PgPool,
+    package_id: Uuid,
+    inclusions: &[String],
+) -> Result<Vec<PackageVariantResponse>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, VariantRow>(&format!(
+        r#"
+        SELECT p.variant_id as id, p.name, p.price, lo.offer_price, p.added_inclusions, p.removed_inclusions, p.max_people
+        FROM (
+            SELECT v.id as variant_id, v.package_id as id, v.name, v.position, v.price, pk.price as base_price,
+                   v.added_inclusions, v.removed_inclusions, v.max_people
+            FROM package_variants v
+            JOIN packages pk ON pk.id = v.package_id
+            WHERE v.package_id = $1 AND v.is_active
+        ) p
+        LEFT JOIN LATERAL ({}) lo ON lo.offer_price < p.price
+        ORDER BY p.position, p.price
+        "#,
+        best_offer_sql()
+    ))
+    .bind(package_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| PackageVariantResponse {
+            id: row.id,
+            name: row.name,
+            price: row.offer_price.unwrap_or(row.price),
+            original_price: row.offer_price.map(|_| row.price),
+            inclusions: inclusions
+                .iter()
+                .filter(|inclusion| !row.removed_inclusions.contains(inclusion))
+                .chain(row.added_inclusions.iter())
+                .cloned()
+                .collect(),
+            max_people: row.max_people,
+        })
+        .collect())
+}